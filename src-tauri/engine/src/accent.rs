@@ -0,0 +1,36 @@
+//! The Windows accent color, for `ColorMode::Accent`.
+
+use windows::core::w;
+use windows::Win32::{
+    Foundation::BOOL,
+    Graphics::Dwm::DwmGetColorizationColor,
+    System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD},
+};
+
+/// Returns the accent color from the personalization settings as 0xRRGGBB,
+/// falling back to the DWM colorization color.
+pub fn accent_color() -> Option<u32> {
+    unsafe {
+        // Stored as 0xAABBGGRR
+        let mut value: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        let status = RegGetValueW(
+            HKEY_CURRENT_USER,
+            w!("Software\\Microsoft\\Windows\\DWM"),
+            w!("AccentColor"),
+            RRF_RT_REG_DWORD,
+            None,
+            Some(&mut value as *mut u32 as *mut std::ffi::c_void),
+            Some(&mut size),
+        );
+        if status.is_ok() {
+            return Some((value & 0xFF) << 16 | (value & 0xFF00) | (value >> 16) & 0xFF);
+        }
+        
+        // 0xAARRGGBB
+        let mut colorization: u32 = 0;
+        let mut opaque = BOOL::default();
+        DwmGetColorizationColor(&mut colorization, &mut opaque).ok()?;
+        Some(colorization & 0xFFFFFF)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,131 @@
+//! Time-based crosshair animations.
+//!
+//! Animations never modify the stored config; the overlay asks for an animated
+//! copy of it on every frame.
+
+use serde::{Deserialize, Serialize};
+
+use crate::color::{alpha, from_rgba, hue_to_rgb};
+use crate::config::{BlendMode, CrosshairConfig, CrosshairStyle};
+use crate::image::load_image;
+
+// How far a pulsing crosshair grows/shrinks relative to its base size
+pub const PULSE_AMPLITUDE: f32 = 0.25;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum Animation {
+    #[default]
+    None,
+    Pulse { speed: f32 },  // Size breathes in and out, speed in cycles per second
+    Blink { speed: f32 },  // Crosshair turns on and off, speed in blinks per second
+    Spin { speed: f32 },   // Rotates around the center, speed in turns per second
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum ColorMode {
+    #[default]
+    Static,
+    Rainbow { speed: f32 },  // Cycles the hue, speed in full cycles per second
+    Accent,                  // Follows the Windows accent color, applied by the overlay
+}
+
+impl Animation {
+    pub fn is_active(&self) -> bool {
+        !matches!(self, Animation::None)
+    }
+}
+
+/// True when `config` changes over time and the overlay has to keep repainting.
+pub fn needs_frames(config: &CrosshairConfig) -> bool {
+    config.animation.is_active()
+        || matches!(config.color_mode, ColorMode::Rainbow { .. })
+        || config.click_spread.enabled
+        || config.movement_spread.enabled
+        || config.spray_pattern.is_animated()
+        || config.secondary_config.as_deref().is_some_and(needs_frames)
+        || has_animated_image(config)
+        // The screen behind an inverting crosshair keeps changing
+        || config.blend_mode == BlendMode::Invert
+}
+
+fn has_animated_image(config: &CrosshairConfig) -> bool {
+    match &config.style {
+        CrosshairStyle::Image { path, .. } => load_image(path).is_ok_and(|image| image.is_animated()),
+        _ => false,
+    }
+}
+
+/// Frames per second to redraw at while `needs_frames` is true. Animated
+/// images cap it with their own `fps_limit` to keep CPU usage down.
+pub fn frame_rate(config: &CrosshairConfig) -> u32 {
+    let fps = match &config.style {
+        CrosshairStyle::Image { fps_limit, .. } if has_animated_image(config) => {
+            config.animation_fps.min(*fps_limit)
+        }
+        _ => config.animation_fps,
+    };
+    fps.clamp(1, 144)
+}
+
+/// Returns the config to draw `elapsed` seconds into the animation, or `None`
+/// when the crosshair should not be drawn in this frame.
+pub fn animate(config: &CrosshairConfig, elapsed: f32) -> Option<CrosshairConfig> {
+    let mut frame = config.clone();
+    
+    match config.animation {
+        Animation::None => {}
+        Animation::Pulse { speed } => {
+            let phase = elapsed * speed * std::f32::consts::TAU;
+            let scale = 1.0 + PULSE_AMPLITUDE * phase.sin();
+            let scaled = |value: i32| (value as f32 * scale).round() as i32;
+            
+            frame.size = config.size * scale;
+            for arm in [
+                &mut frame.arms.top,
+                &mut frame.arms.bottom,
+                &mut frame.arms.left,
+                &mut frame.arms.right,
+            ] {
+                arm.length = arm.length.map(|length| length * scale);
+            }
+            frame.gap = config.gap * scale;
+            frame.t_length = scaled(config.t_length);
+            frame.chevron.length = scaled(config.chevron.length);
+            frame.diamond.size = scaled(config.diamond.size);
+            frame.brackets.spread = scaled(config.brackets.spread);
+            frame.double_circle.inner_radius = scaled(config.double_circle.inner_radius);
+            frame.double_circle.outer_radius = scaled(config.double_circle.outer_radius);
+            frame.mil_dot.spacing = scaled(config.mil_dot.spacing);
+            frame.triangle.size = scaled(config.triangle.size);
+            if let CrosshairStyle::Image { scale: image_scale, .. } = &mut frame.style {
+                *image_scale *= scale;
+            }
+            frame.dot_size = (config.dot_size * scale).max(1.0);
+        }
+        Animation::Blink { speed } => {
+            if (elapsed * speed).fract() >= 0.5 {
+                return None;
+            }
+        }
+        Animation::Spin { speed } => {
+            frame.rotation = (config.rotation + elapsed * speed * 360.0) % 360.0;
+        }
+    }
+    
+    if let ColorMode::Rainbow { speed } = config.color_mode {
+        frame.color = from_rgba(hue_to_rgb(elapsed * speed * 360.0), alpha(config.color));
+        
+        // The whole crosshair cycles, per-element colors included
+        frame.dot_color = None;
+        for arm in [
+            &mut frame.arms.top,
+            &mut frame.arms.bottom,
+            &mut frame.arms.left,
+            &mut frame.arms.right,
+        ] {
+            arm.color = None;
+        }
+    }
+    
+    Some(frame)
+}
\ No newline at end of file
@@ -0,0 +1,378 @@
+//! Overlay backends: what actually puts the crosshair on screen.
+//!
+//! Hosts drive the overlay through [`backend`] instead of calling a platform's
+//! functions directly, so another backend (a different platform, a fallback
+//! window or a test double) can be swapped in with [`set_backend`]. Only
+//! drawing the config is required; every other feature has a default that
+//! reports it as not supported.
+
+use serde::Serialize;
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::clicks::ClickVisualizer;
+use crate::clock::ClockDisplay;
+use crate::config::CrosshairConfig;
+use crate::geometry::{self, Raster};
+use crate::grid::Grid;
+use crate::keystrokes::KeystrokeDisplay;
+use crate::metrics::RenderMetrics;
+use crate::monitor::MonitorInfo;
+
+/// Health information about the GDI overlay window and its thread. Defined on
+/// every platform so hosts can report it, though only Windows fills it in.
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlayStatus {
+    pub exists: bool,          // A window handle has been created
+    pub hwnd_alive: bool,      // The handle still refers to a live window
+    pub visible: bool,         // The window is shown and the crosshair is being drawn
+    pub thread_running: bool,  // The message loop thread hasn't exited
+    pub hwnd: Option<isize>,
+    pub color_keyed: bool,     // Drawing without per-pixel alpha, see pick_color_key
+    pub color_key_error: Option<String>,  // Set while the color key collides with the crosshair
+}
+
+pub trait OverlayBackend: Send + Sync {
+    /// Short name for diagnostics, e.g. "gdi".
+    fn name(&self) -> &'static str;
+    
+    /// Shows the overlay with the current config. Returns once it is starting
+    /// up; calling it while the overlay exists is harmless.
+    fn create(&self) -> Result<(), String>;
+    
+    /// Replaces the config being drawn.
+    fn update_config(&self, config: CrosshairConfig) -> Result<(), String>;
+    
+    /// Batches config updates: until the matching `commit_transaction`,
+    /// `update_config` only records the config and the screen keeps showing
+    /// the old one. Transactions nest.
+    fn begin_transaction(&self) {}
+    
+    /// Ends a `begin_transaction`, showing every change made since at once.
+    fn commit_transaction(&self) -> Result<(), String> {
+        Ok(())
+    }
+    
+    /// Shows or hides the crosshair by setting `config.enabled`.
+    fn toggle(&self, enabled: bool) -> Result<(), String>;
+    
+    /// Closes the overlay and releases what it holds. Safe to call repeatedly.
+    fn destroy(&self) -> Result<(), String>;
+    
+    /// A copy of the config being drawn.
+    fn config(&self) -> CrosshairConfig;
+    
+    /// True for a native overlay window, false for fallbacks and stand-ins.
+    fn is_native(&self) -> bool {
+        false
+    }
+    
+    /// Tears the overlay down and creates it again, e.g. after it stopped
+    /// drawing.
+    fn restart(&self) -> Result<(), String> {
+        not_supported(self, "Restarting the overlay")
+    }
+    
+    /// Health of the overlay window and its thread.
+    fn status(&self) -> Result<OverlayStatus, String> {
+        not_supported(self, "Overlay status")
+    }
+    
+    /// Makes the crosshair draggable instead of click-through.
+    fn set_edit_mode(&self, _enabled: bool) -> Result<(), String> {
+        not_supported(self, "Edit mode")
+    }
+    
+    fn is_edit_mode(&self) -> Result<bool, String> {
+        not_supported(self, "Edit mode")
+    }
+    
+    /// Centers the crosshair on window `target` instead of the monitor, or
+    /// goes back to the monitor with `None`.
+    fn attach_to_window(&self, _target: Option<isize>) -> Result<(), String> {
+        not_supported(self, "Attaching to a window")
+    }
+    
+    fn attached_window(&self) -> Result<Option<isize>, String> {
+        not_supported(self, "Attaching to a window")
+    }
+    
+    fn list_monitors(&self) -> Result<Vec<MonitorInfo>, String> {
+        not_supported(self, "Monitor selection")
+    }
+    
+    /// Moves the overlay to monitor `index` from `list_monitors`, `None` for
+    /// the primary one.
+    fn set_monitor(&self, _index: Option<usize>) -> Result<(), String> {
+        not_supported(self, "Monitor selection")
+    }
+    
+    /// Shows or hides the overlay in screen recordings and screenshots.
+    fn set_capture_visible(&self, _visible: bool) -> Result<(), String> {
+        not_supported(self, "Hiding from screen capture")
+    }
+    
+    /// How often the overlay re-asserts topmost, 0 to stop.
+    fn set_topmost_interval(&self, _interval_ms: u32) -> Result<(), String> {
+        not_supported(self, "The topmost watchdog")
+    }
+    
+    /// Hides the overlay regardless of `config.enabled`, e.g. while no game
+    /// is in the foreground.
+    fn set_suppressed(&self, _suppressed: bool) -> Result<(), String> {
+        not_supported(self, "Auto-hiding")
+    }
+    
+    /// Briefly shows `message` next to the crosshair.
+    fn flash_osd(&self, _message: &str) -> Result<(), String> {
+        not_supported(self, "On-screen messages")
+    }
+    
+    /// Counts `seconds` down next to the crosshair, after `label`.
+    fn start_timer(&self, _seconds: f32, _label: &str) -> Result<(), String> {
+        not_supported(self, "Timers")
+    }
+    
+    fn set_grid(&self, _grid: Option<Grid>) -> Result<(), String> {
+        not_supported(self, "The grid")
+    }
+    
+    fn set_keystroke_display(&self, _display: Option<KeystrokeDisplay>) -> Result<(), String> {
+        not_supported(self, "The keystroke display")
+    }
+    
+    fn set_click_visualizer(&self, _visualizer: Option<ClickVisualizer>) -> Result<(), String> {
+        not_supported(self, "The click visualizer")
+    }
+    
+    fn set_clock_display(&self, _clock: Option<ClockDisplay>) -> Result<(), String> {
+        not_supported(self, "The clock")
+    }
+    
+    /// The screen `radius` pixels each way around the anchor point, without
+    /// the crosshair, as 0xRRGGBB rows.
+    fn capture_anchor_area(&self, _radius: i32) -> Result<Vec<u32>, String> {
+        not_supported(self, "Screen capture")
+    }
+    
+    /// Paint timings, for reports about stutter.
+    fn render_metrics(&self) -> Result<RenderMetrics, String> {
+        not_supported(self, "Render metrics")
+    }
+    
+    /// Runs `handler` when the taskbar is recreated, e.g. after explorer.exe
+    /// restarts. Backends that never see that drop it.
+    fn set_taskbar_created_handler(&self, _handler: Box<dyn Fn() + Send>) {}
+    
+    /// Runs `handler` with the new `position_x`/`position_y` after the
+    /// crosshair is dragged in edit mode. Backends without edit mode drop it.
+    fn set_position_changed_handler(&self, _handler: Box<dyn Fn(f32, f32) + Send>) {}
+}
+
+// What the default methods answer for features a backend doesn't have
+fn not_supported<T>(backend: &(impl OverlayBackend + ?Sized), feature: &str) -> Result<T, String> {
+    Err(format!("{} isn't supported by the {} overlay", feature, backend.name()))
+}
+
+/// Keeps the config in memory and draws nothing. The default where there is
+/// no native overlay, and a stand-in for tests.
+#[derive(Default)]
+pub struct HeadlessBackend {
+    config: Mutex<CrosshairConfig>,
+}
+
+impl HeadlessBackend {
+    /// What the overlay would show, rendered into a `width` x `height` buffer.
+    /// Blank while the crosshair is hidden.
+    pub fn snapshot(&self, width: usize, height: usize) -> Raster {
+        let config = self.config();
+        if config.enabled {
+            geometry::render(&config, width, height)
+        } else {
+            Raster::new(width, height)
+        }
+    }
+}
+
+impl OverlayBackend for HeadlessBackend {
+    fn name(&self) -> &'static str {
+        "headless"
+    }
+    
+    fn create(&self) -> Result<(), String> {
+        Ok(())
+    }
+    
+    fn update_config(&self, config: CrosshairConfig) -> Result<(), String> {
+        *self.config.lock().unwrap() = config;
+        Ok(())
+    }
+    
+    fn toggle(&self, enabled: bool) -> Result<(), String> {
+        self.config.lock().unwrap().enabled = enabled;
+        Ok(())
+    }
+    
+    fn destroy(&self) -> Result<(), String> {
+        Ok(())
+    }
+    
+    fn config(&self) -> CrosshairConfig {
+        self.config.lock().unwrap().clone()
+    }
+}
+
+/// The layered GDI window from [`create_overlay_window`](crate::create_overlay_window).
+#[cfg(windows)]
+pub struct GdiBackend;
+
+#[cfg(windows)]
+impl OverlayBackend for GdiBackend {
+    fn name(&self) -> &'static str {
+        crate::overlay::RENDERER
+    }
+    
+    fn create(&self) -> Result<(), String> {
+        crate::overlay::create_overlay_window().map_err(|e| e.to_string())
+    }
+    
+    fn update_config(&self, config: CrosshairConfig) -> Result<(), String> {
+        crate::overlay::update_config(config).map_err(|e| e.to_string())
+    }
+    
+    fn begin_transaction(&self) {
+        crate::overlay::begin_config_transaction();
+    }
+    
+    fn commit_transaction(&self) -> Result<(), String> {
+        crate::overlay::commit_config_transaction().map_err(|e| e.to_string())
+    }
+    
+    fn toggle(&self, enabled: bool) -> Result<(), String> {
+        crate::overlay::toggle_overlay(enabled).map_err(|e| e.to_string())
+    }
+    
+    fn destroy(&self) -> Result<(), String> {
+        crate::overlay::shutdown_overlay().map_err(|e| e.to_string())
+    }
+    
+    fn config(&self) -> CrosshairConfig {
+        crate::overlay::get_config()
+    }
+    
+    fn is_native(&self) -> bool {
+        true
+    }
+    
+    fn restart(&self) -> Result<(), String> {
+        crate::overlay::restart_overlay().map_err(|e| e.to_string())
+    }
+    
+    fn status(&self) -> Result<OverlayStatus, String> {
+        Ok(crate::overlay::get_overlay_status())
+    }
+    
+    fn set_edit_mode(&self, enabled: bool) -> Result<(), String> {
+        crate::overlay::set_edit_mode(enabled).map_err(|e| e.to_string())
+    }
+    
+    fn is_edit_mode(&self) -> Result<bool, String> {
+        Ok(crate::overlay::is_edit_mode())
+    }
+    
+    fn attach_to_window(&self, target: Option<isize>) -> Result<(), String> {
+        crate::overlay::attach_to_window(target).map_err(|e| e.to_string())
+    }
+    
+    fn attached_window(&self) -> Result<Option<isize>, String> {
+        Ok(crate::overlay::get_attached_window())
+    }
+    
+    fn list_monitors(&self) -> Result<Vec<MonitorInfo>, String> {
+        Ok(crate::monitor::list_monitors())
+    }
+    
+    fn set_monitor(&self, index: Option<usize>) -> Result<(), String> {
+        crate::overlay::set_monitor(index).map_err(|e| e.to_string())
+    }
+    
+    fn set_capture_visible(&self, visible: bool) -> Result<(), String> {
+        crate::overlay::set_capture_visible(visible).map_err(|e| e.to_string())
+    }
+    
+    fn set_topmost_interval(&self, interval_ms: u32) -> Result<(), String> {
+        crate::overlay::set_topmost_interval(interval_ms).map_err(|e| e.to_string())
+    }
+    
+    fn set_suppressed(&self, suppressed: bool) -> Result<(), String> {
+        crate::overlay::set_suppressed(suppressed).map_err(|e| e.to_string())
+    }
+    
+    fn flash_osd(&self, message: &str) -> Result<(), String> {
+        crate::overlay::flash_osd(message).map_err(|e| e.to_string())
+    }
+    
+    fn start_timer(&self, seconds: f32, label: &str) -> Result<(), String> {
+        crate::overlay::start_timer(seconds, label).map_err(|e| e.to_string())
+    }
+    
+    fn set_grid(&self, grid: Option<Grid>) -> Result<(), String> {
+        crate::overlay::set_grid(grid).map_err(|e| e.to_string())
+    }
+    
+    fn set_keystroke_display(&self, display: Option<KeystrokeDisplay>) -> Result<(), String> {
+        crate::overlay::set_keystroke_display(display).map_err(|e| e.to_string())
+    }
+    
+    fn set_click_visualizer(&self, visualizer: Option<ClickVisualizer>) -> Result<(), String> {
+        crate::overlay::set_click_visualizer(visualizer).map_err(|e| e.to_string())
+    }
+    
+    fn set_clock_display(&self, clock: Option<ClockDisplay>) -> Result<(), String> {
+        crate::overlay::set_clock_display(clock).map_err(|e| e.to_string())
+    }
+    
+    fn capture_anchor_area(&self, radius: i32) -> Result<Vec<u32>, String> {
+        crate::overlay::capture_anchor_area(radius).ok_or("Failed to read the screen".to_string())
+    }
+    
+    fn render_metrics(&self) -> Result<RenderMetrics, String> {
+        Ok(crate::metrics::render_metrics())
+    }
+    
+    fn set_taskbar_created_handler(&self, handler: Box<dyn Fn() + Send>) {
+        crate::overlay::set_taskbar_created_handler(handler);
+    }
+    
+    fn set_position_changed_handler(&self, handler: Box<dyn Fn(f32, f32) + Send>) {
+        crate::overlay::set_position_changed_handler(handler);
+    }
+}
+
+// None until first used or replaced
+static BACKEND: RwLock<Option<Arc<dyn OverlayBackend>>> = RwLock::new(None);
+
+/// The backend in use: GDI on Windows and headless elsewhere, unless
+/// [`set_backend`] picked another.
+pub fn backend() -> Arc<dyn OverlayBackend> {
+    if let Some(backend) = BACKEND.read().unwrap().as_ref() {
+        return backend.clone();
+    }
+    BACKEND.write().unwrap().get_or_insert_with(platform_backend).clone()
+}
+
+/// Replaces the backend. The previous one is not destroyed.
+pub fn set_backend(backend: Arc<dyn OverlayBackend>) {
+    *BACKEND.write().unwrap() = Some(backend);
+}
+
+fn platform_backend() -> Arc<dyn OverlayBackend> {
+    #[cfg(windows)]
+    {
+        Arc::new(GdiBackend)
+    }
+    
+    #[cfg(not(windows))]
+    {
+        Arc::new(HeadlessBackend::default())
+    }
+}
\ No newline at end of file
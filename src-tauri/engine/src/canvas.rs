@@ -0,0 +1,426 @@
+//! Per-pixel alpha rendering for the overlay window.
+//!
+//! GDI can't draw with alpha, so every element is drawn on its own scratch
+//! layer and blended into a premultiplied BGRA frame with the element's
+//! opacity. The finished frame is handed to `UpdateLayeredWindow`.
+
+use windows::Win32::{
+    Foundation::*,
+    Graphics::Gdi::*,
+    UI::WindowsAndMessaging::{
+        UpdateLayeredWindowIndirect, ULW_ALPHA, ULW_COLORKEY, UPDATELAYEREDWINDOWINFO, UPDATE_LAYERED_WINDOW_FLAGS,
+    },
+};
+
+use crate::image::CrosshairImage;
+
+// A 32-bit top-down DIB section selected into its own memory DC
+struct Surface {
+    dc: HDC,
+    bitmap: HBITMAP,
+    old_bitmap: HGDIOBJ,
+    bits: *mut u32,
+    len: usize,
+}
+
+impl Surface {
+    fn new(width: i32, height: i32) -> Option<Self> {
+        unsafe {
+            let screen_dc = GetDC(None);
+            let dc = CreateCompatibleDC(screen_dc);
+            ReleaseDC(None, screen_dc);
+            if dc.is_invalid() {
+                return None;
+            }
+            
+            let bmi = BITMAPINFO {
+                bmiHeader: BITMAPINFOHEADER {
+                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                    biWidth: width,
+                    biHeight: -height, // Negative height = top-down rows
+                    biPlanes: 1,
+                    biBitCount: 32,
+                    biCompression: BI_RGB.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            
+            let mut bits: *mut std::ffi::c_void = std::ptr::null_mut();
+            let Ok(bitmap) = CreateDIBSection(dc, &bmi, DIB_RGB_COLORS, &mut bits, None, 0) else {
+                let _ = DeleteDC(dc);
+                return None;
+            };
+            let old_bitmap = SelectObject(dc, bitmap);
+            
+            Some(Self {
+                dc,
+                bitmap,
+                old_bitmap,
+                bits: bits as *mut u32,
+                len: (width * height) as usize,
+            })
+        }
+    }
+    
+    fn pixels(&mut self) -> &mut [u32] {
+        unsafe {
+            // GDI may still be writing to the bitmap
+            let _ = GdiFlush();
+            std::slice::from_raw_parts_mut(self.bits, self.len)
+        }
+    }
+}
+
+impl Drop for Surface {
+    fn drop(&mut self) {
+        unsafe {
+            SelectObject(self.dc, self.old_bitmap);
+            let _ = DeleteObject(self.bitmap);
+            let _ = DeleteDC(self.dc);
+        }
+    }
+}
+
+/// Copies the screen pixels in the given rectangle (screen coordinates) as
+/// 0x00RRGGBB. Layered windows, the overlay included, are left out.
+pub fn sample_screen(x: i32, y: i32, width: i32, height: i32) -> Option<Vec<u32>> {
+    if width <= 0 || height <= 0 {
+        return None;
+    }
+    
+    let mut surface = Surface::new(width, height)?;
+    unsafe {
+        let screen_dc = GetDC(None);
+        // Without CAPTUREBLT, BitBlt skips layered windows
+        let copied = BitBlt(surface.dc, 0, 0, width, height, screen_dc, x, y, SRCCOPY);
+        ReleaseDC(None, screen_dc);
+        copied.ok()?;
+    }
+    
+    Some(surface.pixels().iter().map(|&pixel| pixel & 0xFFFFFF).collect())
+}
+
+/// The last frame shown on a window, to work out what the next one changes.
+#[derive(Default)]
+pub struct PresentedFrame {
+    width: i32,
+    height: i32,
+    opacity: f32,
+    pixels: Vec<u32>,
+}
+
+// Larger blurs cost more than they add
+const MAX_BLUR_RADIUS: i32 = 32;
+
+/// One frame of the overlay, built up layer by layer.
+pub struct Canvas {
+    width: i32,
+    height: i32,
+    frame: Surface,
+    scratch: Surface,
+    backdrop: Option<Vec<u32>>,  // Screen content behind the window, for inverted layers
+}
+
+impl Canvas {
+    pub fn new(width: i32, height: i32) -> Option<Self> {
+        if width <= 0 || height <= 0 {
+            return None;
+        }
+        
+        let mut frame = Surface::new(width, height)?;
+        frame.pixels().fill(0);
+        Some(Self {
+            width,
+            height,
+            frame,
+            scratch: Surface::new(width, height)?,
+            backdrop: None,
+        })
+    }
+    
+    /// Runs `draw` with a DC for a fresh layer, then blends everything it drew
+    /// over the frame at `opacity` (0.0-1.0).
+    pub fn layer(&mut self, opacity: f32, draw: impl FnOnce(HDC)) {
+        let alpha = (opacity.clamp(0.0, 1.0) * 255.0).round() as u32;
+        if alpha == 0 {
+            return;
+        }
+        
+        // GDI zeroes the alpha byte of every pixel it touches, which tells the
+        // drawn pixels apart from the untouched ones, black included
+        self.scratch.pixels().fill(0xFF000000);
+        draw(self.scratch.dc);
+        unsafe {
+            SelectClipRgn(self.scratch.dc, None);
+        }
+        
+        let scratch = self.scratch.pixels();
+        for (dst, &src) in self.frame.pixels().iter_mut().zip(scratch.iter()) {
+            if src >> 24 == 0 {
+                *dst = blend_over(src, alpha, *dst);
+            }
+        }
+    }
+    
+    /// Grabs what is on screen behind the window, whose top-left corner is at
+    /// `x`, `y` in screen coordinates, for `inverted_layer`.
+    pub fn capture_backdrop(&mut self, x: i32, y: i32) {
+        self.backdrop = sample_screen(x, y, self.width, self.height);
+    }
+    
+    /// Like `layer`, but every drawn pixel takes the inverse of the screen
+    /// behind it, so it stands out on any background. Layered windows can't
+    /// use raster ops against the desktop, so this needs `capture_backdrop`;
+    /// without a backdrop it draws white.
+    pub fn inverted_layer(&mut self, opacity: f32, draw: impl FnOnce(HDC)) {
+        let alpha = (opacity.clamp(0.0, 1.0) * 255.0).round() as u32;
+        if alpha == 0 {
+            return;
+        }
+        
+        self.scratch.pixels().fill(0xFF000000);
+        draw(self.scratch.dc);
+        unsafe {
+            SelectClipRgn(self.scratch.dc, None);
+        }
+        
+        let scratch = self.scratch.pixels();
+        let backdrop = self.backdrop.as_deref();
+        for (i, (dst, &src)) in self.frame.pixels().iter_mut().zip(scratch.iter()).enumerate() {
+            if src >> 24 == 0 {
+                let behind = backdrop.map_or(0, |backdrop| backdrop[i]);
+                *dst = blend_over(!behind & 0xFFFFFF, alpha, *dst);
+            }
+        }
+    }
+    
+    /// Like `layer`, but treats everything `draw` touches as a mask that is
+    /// blurred by `radius` pixels and filled with `color` (0xRRGGBB), for soft
+    /// shadows. The blur is three box blur passes, close to a gaussian.
+    pub fn blurred_layer(&mut self, opacity: f32, color: u32, radius: i32, draw: impl FnOnce(HDC)) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        if opacity == 0.0 {
+            return;
+        }
+        
+        self.scratch.pixels().fill(0xFF000000);
+        draw(self.scratch.dc);
+        unsafe {
+            SelectClipRgn(self.scratch.dc, None);
+        }
+        
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut mask: Vec<u8> = self
+            .scratch
+            .pixels()
+            .iter()
+            .map(|&pixel| if pixel >> 24 == 0 { 255 } else { 0 })
+            .collect();
+        let mut buffer = vec![0u8; mask.len()];
+        let radius = radius.clamp(0, MAX_BLUR_RADIUS) as usize;
+        for _ in 0..3 {
+            box_blur(&mask, &mut buffer, width, height, radius, 1, width);
+            box_blur(&buffer, &mut mask, height, width, radius, width, 1);
+        }
+        
+        for (dst, &coverage) in self.frame.pixels().iter_mut().zip(mask.iter()) {
+            if coverage > 0 {
+                let alpha = (coverage as f32 * opacity).round() as u32;
+                *dst = blend_over(color & 0xFFFFFF, alpha, *dst);
+            }
+        }
+    }
+    
+    /// Blends frame number `frame` of `image`, centered on `center_x`,
+    /// `center_y` and resized by `scale` with nearest-neighbour sampling.
+    pub fn image(&mut self, image: &CrosshairImage, frame: usize, center_x: i32, center_y: i32, scale: f32, opacity: f32) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        let scale = scale.max(0.01);
+        let width = (image.width as f32 * scale).round() as i32;
+        let height = (image.height as f32 * scale).round() as i32;
+        let left = center_x - width / 2;
+        let top = center_y - height / 2;
+        let frame_width = self.width;
+        let frame_height = self.height;
+        let pixels = self.frame.pixels();
+        
+        for y in top.max(0)..(top + height).min(frame_height) {
+            let src_y = (((y - top) as f32 / scale) as u32).min(image.height - 1);
+            for x in left.max(0)..(left + width).min(frame_width) {
+                let src_x = (((x - left) as f32 / scale) as u32).min(image.width - 1);
+                let [r, g, b, a] = image.pixel(frame, src_x, src_y);
+                let alpha = (a as f32 * opacity).round() as u32;
+                if alpha == 0 {
+                    continue;
+                }
+                
+                let src = (r as u32) << 16 | (g as u32) << 8 | b as u32;
+                let dst = &mut pixels[(y * frame_width + x) as usize];
+                *dst = blend_over(src, alpha, *dst);
+            }
+        }
+    }
+    
+    /// Bounding box of the pixels that differ from `previous`, which then
+    /// becomes this frame. Covers everything when the size or opacity changed
+    /// and is None when nothing did.
+    pub fn dirty_rect(&mut self, previous: &mut PresentedFrame, opacity: f32) -> Option<RECT> {
+        let (width, height) = (self.width, self.height);
+        let pixels = self.frame.pixels();
+        let resized = previous.width != width || previous.height != height || previous.pixels.len() != pixels.len();
+        
+        let dirty = if resized || previous.opacity != opacity {
+            Some(RECT {
+                left: 0,
+                top: 0,
+                right: width,
+                bottom: height,
+            })
+        } else {
+            changed_bounds(&previous.pixels, pixels, width as usize)
+        };
+        
+        if dirty.is_some() {
+            previous.width = width;
+            previous.height = height;
+            previous.opacity = opacity;
+            previous.pixels.clear();
+            previous.pixels.extend_from_slice(pixels);
+        }
+        dirty
+    }
+    
+    /// Shows the frame on the layered window `hwnd`, scaled by `opacity`.
+    /// Only the `dirty` part of the window is updated.
+    pub fn present(&self, hwnd: HWND, opacity: f32, dirty: &RECT) -> windows::core::Result<()> {
+        let blend = BLENDFUNCTION {
+            BlendOp: AC_SRC_OVER as u8,
+            BlendFlags: 0,
+            SourceConstantAlpha: (opacity.clamp(0.0, 1.0) * 255.0).round() as u8,
+            AlphaFormat: AC_SRC_ALPHA as u8,
+        };
+        let size = SIZE {
+            cx: self.width,
+            cy: self.height,
+        };
+        let origin = POINT::default();
+        let info = UPDATELAYEREDWINDOWINFO {
+            cbSize: std::mem::size_of::<UPDATELAYEREDWINDOWINFO>() as u32,
+            hdcDst: HDC::default(),
+            pptDst: std::ptr::null(),
+            psize: &size,
+            hdcSrc: self.frame.dc,
+            pptSrc: &origin,
+            crKey: COLORREF(0),
+            pblend: &blend,
+            dwFlags: ULW_ALPHA,
+            prcDirty: dirty,
+        };
+        
+        unsafe { UpdateLayeredWindowIndirect(hwnd, &info).ok() }
+    }
+    
+    /// Fallback for `present` where per-pixel alpha fails: pixels less than
+    /// half covered become `key` (0xRRGGBB) and turn transparent, the rest
+    /// are drawn opaque. The whole window is updated.
+    pub fn present_color_keyed(&mut self, hwnd: HWND, opacity: f32, key: u32) -> windows::core::Result<()> {
+        let frame = self.frame.pixels().to_vec();
+        for (dst, &src) in self.scratch.pixels().iter_mut().zip(frame.iter()) {
+            let alpha = src >> 24;
+            *dst = if alpha < 128 { key & 0xFFFFFF } else { unpremultiply(src, alpha) };
+        }
+        
+        let blend = BLENDFUNCTION {
+            BlendOp: AC_SRC_OVER as u8,
+            BlendFlags: 0,
+            SourceConstantAlpha: (opacity.clamp(0.0, 1.0) * 255.0).round() as u8,
+            AlphaFormat: 0,
+        };
+        let size = SIZE {
+            cx: self.width,
+            cy: self.height,
+        };
+        let origin = POINT::default();
+        // COLORREF is 0x00BBGGRR
+        let key_ref = (key & 0xFF) << 16 | (key & 0xFF00) | (key >> 16) & 0xFF;
+        let info = UPDATELAYEREDWINDOWINFO {
+            cbSize: std::mem::size_of::<UPDATELAYEREDWINDOWINFO>() as u32,
+            hdcDst: HDC::default(),
+            pptDst: std::ptr::null(),
+            psize: &size,
+            hdcSrc: self.scratch.dc,
+            pptSrc: &origin,
+            crKey: COLORREF(key_ref),
+            pblend: &blend,
+            dwFlags: UPDATE_LAYERED_WINDOW_FLAGS(ULW_COLORKEY.0 | ULW_ALPHA.0),
+            prcDirty: std::ptr::null(),
+        };
+        
+        unsafe { UpdateLayeredWindowIndirect(hwnd, &info).ok() }
+    }
+}
+
+// Bounding box of the pixels that differ between two frames `width` wide
+fn changed_bounds(old: &[u32], new: &[u32], width: usize) -> Option<RECT> {
+    let mut bounds: Option<RECT> = None;
+    for (y, (old_row, new_row)) in old.chunks(width).zip(new.chunks(width)).enumerate() {
+        if old_row == new_row {
+            continue;
+        }
+        let differs = |(a, b): (&u32, &u32)| a != b;
+        let left = old_row.iter().zip(new_row).position(differs).unwrap_or(0) as i32;
+        let right = old_row.iter().zip(new_row).rposition(differs).map_or(width, |x| x + 1) as i32;
+        let y = y as i32;
+        
+        let rect = bounds.get_or_insert(RECT {
+            left,
+            top: y,
+            right,
+            bottom: y + 1,
+        });
+        rect.left = rect.left.min(left);
+        rect.right = rect.right.max(right);
+        rect.bottom = y + 1;
+    }
+    bounds
+}
+
+// One box blur pass along rows of `len` values spaced `step` apart, with
+// `count` rows starting `stride` apart
+fn box_blur(src: &[u8], dst: &mut [u8], len: usize, count: usize, radius: usize, step: usize, stride: usize) {
+    let window = (radius * 2 + 1) as u32;
+    for row in 0..count {
+        let at = |i: usize| src[row * stride + i * step] as u32;
+        
+        // Running sum over the window, with the edges extended
+        let mut sum = at(0) * (radius as u32 + 1);
+        for i in 1..=radius {
+            sum += at(i.min(len - 1));
+        }
+        for i in 0..len {
+            dst[row * stride + i * step] = (sum / window) as u8;
+            sum += at((i + radius + 1).min(len - 1));
+            sum -= at(i.saturating_sub(radius));
+        }
+    }
+}
+
+// Opaque BGR color of a premultiplied BGRA pixel with `alpha` > 0
+fn unpremultiply(pixel: u32, alpha: u32) -> u32 {
+    let channel = |shift: u32| (((pixel >> shift) & 0xFF) * 255 / alpha).min(255) << shift;
+    channel(16) | channel(8) | channel(0)
+}
+
+// Blends an opaque BGR pixel at `alpha` over a premultiplied BGRA pixel
+fn blend_over(src: u32, alpha: u32, dst: u32) -> u32 {
+    let inverse = 255 - alpha;
+    let channel = |shift: u32| {
+        let s = (src >> shift) & 0xFF;
+        let d = (dst >> shift) & 0xFF;
+        ((s * alpha + d * inverse) / 255) << shift
+    };
+    let out_alpha = alpha + ((dst >> 24) * inverse) / 255;
+    
+    out_alpha << 24 | channel(16) | channel(8) | channel(0)
+}
\ No newline at end of file
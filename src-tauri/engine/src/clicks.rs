@@ -0,0 +1,119 @@
+//! Click visualizer for tutorials and streams: an expanding ring at the
+//! crosshair or cursor for every left and right click. Clicks come from the
+//! platform mouse hook while the visualizer is on; everything here is pure.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+// Clicks beyond this many rings at once are dropped, oldest first
+const LOG_CAPACITY: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ClickOrigin {
+    Crosshair,  // Rings grow from the crosshair center
+    Cursor,     // Rings grow where the mouse was clicked
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClickButton {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClickVisualizer {
+    pub enabled: bool,
+    pub origin: ClickOrigin,
+    pub radius: i32,       // Size of a ring when it has finished growing
+    pub thickness: i32,
+    pub duration_ms: u32,  // How long a ring takes to grow and fade out
+    #[serde(with = "crate::color::hex")]
+    pub left_color: u32,
+    #[serde(with = "crate::color::hex")]
+    pub right_color: u32,
+}
+
+impl Default for ClickVisualizer {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            origin: ClickOrigin::Crosshair,
+            radius: 24,
+            thickness: 2,
+            duration_ms: 400,
+            left_color: 0xFFFFFF,
+            right_color: 0x40A0FF,
+        }
+    }
+}
+
+struct Click {
+    button: ClickButton,
+    x: i32,
+    y: i32,
+    at: Instant,
+}
+
+/// Ring to draw for one click: where it was clicked, in screen coordinates,
+/// and how far along it is from 0.0 (just clicked) to 1.0 (gone).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ripple {
+    pub button: ClickButton,
+    pub x: i32,
+    pub y: i32,
+    pub progress: f32,
+}
+
+/// Recent clicks, oldest first.
+pub struct ClickLog {
+    clicks: Vec<Click>,
+}
+
+impl ClickLog {
+    pub const fn new() -> Self {
+        Self { clicks: Vec::new() }
+    }
+    
+    pub fn record(&mut self, button: ClickButton, x: i32, y: i32, now: Instant) {
+        self.clicks.push(Click { button, x, y, at: now });
+        if self.clicks.len() > LOG_CAPACITY {
+            self.clicks.remove(0);
+        }
+    }
+    
+    pub fn clear(&mut self) {
+        self.clicks.clear();
+    }
+    
+    /// Rings still growing at `now`. Finished ones are dropped.
+    pub fn ripples(&mut self, visualizer: &ClickVisualizer, now: Instant) -> Vec<Ripple> {
+        let duration = Duration::from_millis(visualizer.duration_ms.max(1) as u64);
+        let progress = |click: &Click| now.saturating_duration_since(click.at).as_secs_f32() / duration.as_secs_f32();
+        self.clicks.retain(|click| progress(click) < 1.0);
+        self.clicks
+            .iter()
+            .map(|click| Ripple {
+                button: click.button,
+                x: click.x,
+                y: click.y,
+                progress: progress(click),
+            })
+            .collect()
+    }
+}
+
+impl Default for ClickLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClickVisualizer {
+    pub fn color(&self, button: ClickButton) -> u32 {
+        match button {
+            ClickButton::Left => self.left_color,
+            ClickButton::Right => self.right_color,
+        }
+    }
+}
\ No newline at end of file
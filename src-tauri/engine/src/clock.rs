@@ -0,0 +1,102 @@
+//! Clock and session timer drawn in a screen corner, so fullscreen players
+//! keep track of time. The platform supplies the local time; formatting is
+//! pure.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::keystrokes::Corner;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClockDisplay {
+    pub enabled: bool,
+    pub show_time: bool,      // Current local time
+    pub show_session: bool,   // Time since the app started
+    pub time_format: String,  // See format_time, e.g. "HH:mm" or "h:mm tt"
+    pub corner: Corner,
+    pub margin: i32,          // Distance from the corner in pixels
+    pub font: String,
+    pub size: i32,            // Text height in pixels
+    pub bold: bool,
+    #[serde(with = "crate::color::hex")]
+    pub color: u32,
+}
+
+impl Default for ClockDisplay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            show_time: true,
+            show_session: false,
+            time_format: "HH:mm".to_string(),
+            corner: Corner::TopRight,
+            margin: 16,
+            font: "Segoe UI".to_string(),
+            size: 16,
+            bold: false,
+            color: 0xFFFFFF,
+        }
+    }
+}
+
+impl ClockDisplay {
+    /// The lines to show, top to bottom, for a local time of
+    /// `hour`:`minute`:`second` and a session `elapsed` long.
+    pub fn lines(&self, hour: u32, minute: u32, second: u32, elapsed: Duration) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.show_time {
+            lines.push(format_time(&self.time_format, hour, minute, second));
+        }
+        if self.show_session {
+            lines.push(format_elapsed(elapsed));
+        }
+        lines
+    }
+}
+
+/// Fills in a time format: `HH`/`H` are 24-hour hours with and without a
+/// leading zero, `hh`/`h` the same on a 12-hour clock, `mm` minutes, `ss`
+/// seconds and `tt` AM or PM. Anything else is copied as is.
+pub fn format_time(format: &str, hour: u32, minute: u32, second: u32) -> String {
+    let hour12 = match hour % 12 {
+        0 => 12,
+        h => h,
+    };
+    let tokens = [
+        ("HH", format!("{:02}", hour)),
+        ("H", hour.to_string()),
+        ("hh", format!("{:02}", hour12)),
+        ("h", hour12.to_string()),
+        ("mm", format!("{:02}", minute)),
+        ("ss", format!("{:02}", second)),
+        ("tt", if hour < 12 { "AM" } else { "PM" }.to_string()),
+    ];
+    
+    let mut out = String::new();
+    let mut rest = format;
+    'outer: while !rest.is_empty() {
+        for (token, value) in &tokens {
+            if let Some(after) = rest.strip_prefix(token) {
+                out.push_str(value);
+                rest = after;
+                continue 'outer;
+            }
+        }
+        let mut chars = rest.chars();
+        out.extend(chars.next());
+        rest = chars.as_str();
+    }
+    out
+}
+
+/// `m:ss` under an hour, `h:mm:ss` after that.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
\ No newline at end of file
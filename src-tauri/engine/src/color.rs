@@ -0,0 +1,139 @@
+//! Color representation and its JSON form.
+//!
+//! Colors are `u32`s laid out as `0xTTRRGGBB`, where `TT` is the transparency
+//! (255 - alpha). Legacy `0xRRGGBB` values therefore stay fully opaque. In JSON
+//! colors are written as `"#RRGGBB"` (opaque) or `"#RRGGBBAA"`; plain integers
+//! are still accepted when reading.
+
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serializer};
+use std::fmt;
+
+/// The `0xRRGGBB` part of `color`.
+pub fn rgb(color: u32) -> u32 {
+    color & 0xFFFFFF
+}
+
+/// Alpha of `color`, 255 = opaque.
+pub fn alpha(color: u32) -> u8 {
+    255 - (color >> 24) as u8
+}
+
+/// Builds a color from `0xRRGGBB` and an alpha.
+pub fn from_rgba(rgb: u32, alpha: u8) -> u32 {
+    ((255 - alpha) as u32) << 24 | rgb & 0xFFFFFF
+}
+
+/// Converts a hue in degrees (full saturation and value) to `0xRRGGBB`.
+pub fn hue_to_rgb(hue: f32) -> u32 {
+    let h = (hue.rem_euclid(360.0)) / 60.0;
+    let x = 1.0 - (h % 2.0 - 1.0).abs();
+    let (r, g, b) = match h as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    let channel = |v: f32| (v * 255.0).round() as u32;
+    channel(r) << 16 | channel(g) << 8 | channel(b)
+}
+
+/// Formats `color` as `#RRGGBB`, or `#RRGGBBAA` when it isn't opaque.
+pub fn to_hex(color: u32) -> String {
+    match alpha(color) {
+        255 => format!("#{:06X}", rgb(color)),
+        a => format!("#{:06X}{:02X}", rgb(color), a),
+    }
+}
+
+/// Parses `#RRGGBB` or `#RRGGBBAA` (the `#` is optional).
+pub fn parse_hex(value: &str) -> Option<u32> {
+    let hex = value.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    
+    match hex.len() {
+        6 => u32::from_str_radix(hex, 16).ok(),
+        8 => {
+            let rgba = u32::from_str_radix(hex, 16).ok()?;
+            Some(from_rgba(rgba >> 8, (rgba & 0xFF) as u8))
+        }
+        _ => None,
+    }
+}
+
+struct ColorVisitor;
+
+impl<'de> Visitor<'de> for ColorVisitor {
+    type Value = u32;
+    
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a \"#RRGGBB\" / \"#RRGGBBAA\" string or an integer color")
+    }
+    
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<u32, E> {
+        u32::try_from(value).map_err(|_| E::custom(format!("color {} is out of range", value)))
+    }
+    
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<u32, E> {
+        u32::try_from(value).map_err(|_| E::custom(format!("color {} is out of range", value)))
+    }
+    
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<u32, E> {
+        parse_hex(value).ok_or_else(|| E::custom(format!("invalid color \"{}\"", value)))
+    }
+}
+
+/// `#[serde(with = "crate::color::hex")]` for `u32` color fields.
+pub mod hex {
+    use super::*;
+    
+    pub fn serialize<S: Serializer>(color: &u32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(*color))
+    }
+    
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+        deserializer.deserialize_any(ColorVisitor)
+    }
+}
+
+/// `#[serde(with = "crate::color::hex_option")]` for `Option<u32>` color fields.
+pub mod hex_option {
+    use super::*;
+    
+    pub fn serialize<S: Serializer>(color: &Option<u32>, serializer: S) -> Result<S::Ok, S::Error> {
+        match color {
+            Some(color) => serializer.serialize_some(&to_hex(*color)),
+            None => serializer.serialize_none(),
+        }
+    }
+    
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u32>, D::Error> {
+        struct OptionVisitor;
+        
+        impl<'de> Visitor<'de> for OptionVisitor {
+            type Value = Option<u32>;
+            
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("null or a color")
+            }
+            
+            fn visit_none<E: de::Error>(self) -> Result<Option<u32>, E> {
+                Ok(None)
+            }
+            
+            fn visit_unit<E: de::Error>(self) -> Result<Option<u32>, E> {
+                Ok(None)
+            }
+            
+            fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<u32>, D::Error> {
+                deserializer.deserialize_any(ColorVisitor).map(Some)
+            }
+        }
+        
+        deserializer.deserialize_option(OptionVisitor)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+//! Color key for drawing without per-pixel alpha. When the overlay has to
+//! fall back to a color-keyed window, every pixel of the key color turns
+//! transparent, so the key must be a color the crosshair never draws.
+
+use crate::animation::ColorMode;
+use crate::color;
+use crate::config::{BlendMode, CrosshairConfig, CrosshairStyle, Element};
+
+/// Key to use when `pick_color_key` finds none.
+pub const DEFAULT_COLOR_KEY: u32 = 0xFF00FF;
+
+// Tried in order; unusual colors first, since black is a common outline
+const CANDIDATES: [u32; 6] = [DEFAULT_COLOR_KEY, 0x010203, 0x000000, 0xFEFEFD, 0x00FF01, 0x7F007F];
+
+/// A key none of `config`'s colors (0xRRGGBB) collide with. Fails when the
+/// config draws colors that aren't known up front, like rainbow cycling, the
+/// accent color, auto contrast, inverted lines or an image.
+pub fn pick_color_key(config: &CrosshairConfig) -> Result<u32, String> {
+    let mut colors = Vec::new();
+    collect_colors(config, &mut colors)?;
+    CANDIDATES
+        .into_iter()
+        .find(|key| !colors.contains(key))
+        .ok_or_else(|| "No color key is free: every candidate is used by the crosshair".to_string())
+}
+
+// Adds the colors `config` and its secondary config draw
+fn collect_colors(config: &CrosshairConfig, colors: &mut Vec<u32>) -> Result<(), String> {
+    match config.color_mode {
+        ColorMode::Static => {}
+        ColorMode::Rainbow { .. } => return Err("Rainbow colors can collide with any color key".to_string()),
+        ColorMode::Accent => return Err("The accent color can collide with any color key".to_string()),
+    }
+    if config.auto_contrast.enabled {
+        return Err("Auto contrast colors can collide with any color key".to_string());
+    }
+    if config.blend_mode == BlendMode::Invert {
+        return Err("Inverted lines can collide with any color key".to_string());
+    }
+    if let CrosshairStyle::Image { .. } = config.style {
+        return Err("Image colors can collide with any color key".to_string());
+    }
+    
+    colors.push(config.color);
+    if config.show_outline {
+        colors.push(config.outline_color);
+    }
+    if config.shadow_enabled {
+        colors.push(config.shadow_color);
+    }
+    colors.extend(config.dot_color);
+    let arms = &config.arms;
+    let arms = [&arms.top, &arms.bottom, &arms.left, &arms.right];
+    colors.extend(arms.into_iter().filter_map(|arm| arm.color));
+    colors.extend(config.texts.iter().map(|text| text.color));
+    for element in &config.elements {
+        match element {
+            Element::Line(line) => colors.push(line.color),
+            Element::Arc { color, .. } => colors.push(*color),
+            Element::Circle { color, fill, .. }
+            | Element::Rect { color, fill, .. }
+            | Element::Polygon { color, fill, .. } => {
+                colors.push(*color);
+                colors.extend(*fill);
+            }
+        }
+    }
+    if config.spray_pattern.enabled {
+        colors.push(config.spray_pattern.color);
+    }
+    
+    // The key is compared without the transparency byte
+    for value in colors.iter_mut() {
+        *value = color::rgb(*value);
+    }
+    
+    match config.secondary_config.as_deref() {
+        Some(secondary) => collect_colors(secondary, colors),
+        None => Ok(()),
+    }
+}
\ No newline at end of file
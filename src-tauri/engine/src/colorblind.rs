@@ -0,0 +1,115 @@
+//! Colorblind-friendly palettes and color vision deficiency simulation.
+//!
+//! Simulation uses the Machado, Oliveira & Fernandes (2009) matrices at full
+//! severity, applied in linear RGB.
+
+use serde::{Deserialize, Serialize};
+
+use crate::color::{alpha, from_rgba};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Deficiency {
+    Protanopia,    // No red cones
+    Deuteranopia,  // No green cones
+    Tritanopia,    // No blue cones
+}
+
+impl Deficiency {
+    pub const ALL: [Deficiency; 3] = [Deficiency::Protanopia, Deficiency::Deuteranopia, Deficiency::Tritanopia];
+    
+    fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            Deficiency::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Deficiency::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Deficiency::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PaletteColor {
+    pub name: &'static str,
+    #[serde(serialize_with = "crate::color::hex::serialize")]
+    pub color: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Palette {
+    pub deficiency: Deficiency,
+    pub colors: Vec<PaletteColor>,
+}
+
+const fn swatch(name: &'static str, color: u32) -> PaletteColor {
+    PaletteColor { name, color }
+}
+
+// Each color keeps its hue family and stands out from the others after
+// simulation, and stays bright enough to read over typical game scenes
+const RED_GREEN_SAFE: [PaletteColor; 5] = [
+    swatch("Yellow", 0xFFFF00),
+    swatch("Cyan", 0x00FFFF),
+    swatch("Blue", 0x3D7BFF),
+    swatch("White", 0xFFFFFF),
+    swatch("Magenta", 0xFF00FF),
+];
+
+const BLUE_YELLOW_SAFE: [PaletteColor; 5] = [
+    swatch("Red", 0xFF0000),
+    swatch("Pink", 0xFF5FA2),
+    swatch("Teal", 0x00C8B4),
+    swatch("White", 0xFFFFFF),
+    swatch("Black", 0x000000),
+];
+
+/// The built-in palettes, one per deficiency.
+pub fn palettes() -> Vec<Palette> {
+    Deficiency::ALL
+        .into_iter()
+        .map(|deficiency| Palette {
+            deficiency,
+            colors: match deficiency {
+                Deficiency::Protanopia | Deficiency::Deuteranopia => RED_GREEN_SAFE.to_vec(),
+                Deficiency::Tritanopia => BLUE_YELLOW_SAFE.to_vec(),
+            },
+        })
+        .collect()
+}
+
+/// How `color` looks to someone with `deficiency`, alpha kept.
+pub fn simulate(color: u32, deficiency: Deficiency) -> u32 {
+    let to_linear = |shift: u32| {
+        let c = ((color >> shift) & 0xFF) as f32 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let to_srgb = |c: f32| {
+        let c = c.clamp(0.0, 1.0);
+        let c = if c <= 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        };
+        (c * 255.0).round() as u32
+    };
+    
+    let linear = [to_linear(16), to_linear(8), to_linear(0)];
+    let [r, g, b] = deficiency
+        .matrix()
+        .map(|row| row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2]);
+    from_rgba(to_srgb(r) << 16 | to_srgb(g) << 8 | to_srgb(b), alpha(color))
+}
\ No newline at end of file
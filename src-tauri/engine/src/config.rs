@@ -0,0 +1,701 @@
+//! Crosshair configuration model.
+//!
+//! Everything in here is platform independent and serializable, so configs can
+//! be stored, shared and edited without a running overlay.
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::animation::{Animation, ColorMode};
+use crate::contrast::AutoContrast;
+use crate::spray::SprayPattern;
+use crate::spread::{ClickSpread, MovementSpread};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrosshairConfig {
+    #[serde(default = "current_schema")]
+    pub schema: u32,      // Format revision, see CONFIG_SCHEMA
+    pub enabled: bool,
+    pub size: f32,        // Default arm length
+    pub thickness: f32,
+    #[serde(default)]
+    pub cap_style: CapStyle,  // Line ends, most visible on thick crosshairs
+    pub gap: f32,
+    #[serde(with = "crate::color::hex")]
+    pub color: u32,
+    #[serde(with = "crate::color::hex")]
+    pub outline_color: u32,
+    pub outline_thickness: i32,
+    pub show_dot: bool,
+    pub dot_size: f32,
+    pub show_outline: bool,
+    pub opacity: f32,               // Applies to the whole crosshair, on top of element_opacity
+    #[serde(default)]
+    pub element_opacity: ElementOpacity,
+    // New advanced features
+    pub style: CrosshairStyle,
+    pub position_x: f32,  // Offset from the anchor point, fractional to sit between pixels
+    pub position_y: f32,  // Offset from the anchor point, fractional to sit between pixels
+    pub rotation: f32,    // Rotation in degrees
+    pub t_length: i32,    // Length for T-shaped crosshairs
+    #[serde(default)]
+    pub chevron: Chevron,  // Shape of the Chevron style
+    #[serde(default)]
+    pub diamond: Diamond,  // Shape of the Diamond style
+    #[serde(default)]
+    pub brackets: Brackets,  // Shape of the Brackets style, drawn at `thickness`
+    #[serde(default)]
+    pub double_circle: DoubleCircle,  // Shape of the DoubleCircle style
+    #[serde(default)]
+    pub mil_dot: MilDot,  // Shape of the MilDot style
+    #[serde(default)]
+    pub triangle: Triangle,  // Shape of the Triangle style
+    #[serde(default)]
+    pub ruler: Ruler,  // Scale of the Ruler style
+    pub shadow_enabled: bool,
+    #[serde(with = "crate::color::hex")]
+    pub shadow_color: u32,
+    pub shadow_offset: i32,
+    #[serde(default)]
+    pub shadow_blur: i32,  // Blur radius in pixels for a soft shadow, 0 = hard copy
+    #[serde(default, alias = "lines", deserialize_with = "deserialize_elements")]
+    pub elements: Vec<Element>,  // Shapes making up the Custom style; older configs call them `lines`
+    #[serde(default)]
+    pub texts: Vec<OverlayText>,  // Text snippets drawn with the crosshair, whatever the style
+    #[serde(default)]
+    pub arms: CrosshairArms,        // Per-arm enable flags, length and color overrides
+    #[serde(default, with = "crate::color::hex_option")]
+    pub dot_color: Option<u32>,     // `None` uses `color`
+    #[serde(default)]
+    pub dead_zone_radius: i32,      // Radius around the center that lines/shapes never cover
+    #[serde(default)]
+    pub animation: Animation,
+    #[serde(default = "default_animation_fps")]
+    pub animation_fps: u32,         // Frame rate cap for animations
+    #[serde(default)]
+    pub color_mode: ColorMode,
+    #[serde(default)]
+    pub auto_contrast: AutoContrast,
+    #[serde(default)]
+    pub blend_mode: BlendMode,
+    #[serde(default)]
+    pub click_spread: ClickSpread,  // Gap expansion while the left mouse button is held
+    #[serde(default)]
+    pub movement_spread: MovementSpread,  // Gap expansion while moving with WASD
+    #[serde(default)]
+    pub secondary_config: Option<Box<CrosshairConfig>>,  // Drawn at the same spot while the right mouse button is held (ADS)
+    #[serde(default)]
+    pub spray_pattern: SprayPattern,  // Recoil practice trace drawn under the crosshair
+    #[serde(default)]
+    pub anchor: Anchor,             // Screen-relative base position, only used on the primary config
+    #[serde(default)]
+    pub sizing_mode: SizingMode,
+    #[serde(default)]
+    pub angular: AngularSizing,     // Used when sizing_mode is Angular
+    #[serde(default)]
+    pub resolution_overrides: Vec<ResolutionOverride>,  // Applied when the display has a matching resolution
+}
+
+/// Dimensions that replace the config's own on a display of exactly
+/// `width`x`height`. Unset fields keep the config's value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolutionOverride {
+    pub width: i32,
+    pub height: i32,
+    #[serde(default)]
+    pub size: Option<f32>,
+    #[serde(default)]
+    pub gap: Option<f32>,
+    #[serde(default)]
+    pub thickness: Option<f32>,
+    #[serde(default)]
+    pub dot_size: Option<f32>,
+    #[serde(default)]
+    pub outline_thickness: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CrosshairStyle {
+    Classic,      // Standard + crosshair
+    Dot,          // Just a dot
+    Circle,       // Circle crosshair
+    Square,       // Square crosshair
+    TShape,       // T-shaped crosshair
+    Custom,       // Custom shape built from `elements`
+    Guides,       // Thin lines spanning the whole screen through the center (sniper mode)
+    Chevron,      // Angled ^ below or V above the center, shaped by `chevron`
+    X,            // Classic arms turned 45 degrees
+    Diamond,      // Square outline standing on a corner, shaped by `diamond`
+    Brackets,     // Four viewfinder-style corner brackets, shaped by `brackets`
+    DoubleCircle, // Two concentric circles like a red-dot sight, shaped by `double_circle`
+    MilDot,       // Scope reticle with evenly spaced marks along both lines, shaped by `mil_dot`
+    Triangle,     // Downward-pointing triangle above the aim point, shaped by `triangle`
+    Ruler,        // Lines with pixel ticks and distance labels for measuring, shaped by `ruler`
+    Image {       // PNG/APNG/GIF with alpha, centered and drawn at `scale`
+        path: String,
+        #[serde(default = "default_image_scale")]
+        scale: f32,
+        #[serde(default = "default_image_fps")]
+        fps_limit: u32,  // Caps the frame timer while an animated image plays
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum BlendMode {
+    #[default]
+    Normal,  // Lines and dot in their own colors
+    Invert,  // Lines and dot invert whatever is behind them, ignoring their colors
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum CapStyle {
+    Flat,         // Lines end exactly at their endpoints
+    #[default]
+    Round,        // Half-circle past each endpoint (GDI's default)
+    Square,       // Half a thickness past each endpoint
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum SizingMode {
+    #[default]
+    Pixels,       // size/gap/thickness/dot_size are raw pixels
+    Angular,      // Dimensions come from `angular` and are converted to pixels
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AngleReference {
+    Game,         // Degrees of in-game view angle, based on the game's FOV
+    Eye,          // Degrees of visual angle at the eye, based on monitor size and distance
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AngularSizing {
+    pub reference: AngleReference,
+    pub game_fov: f32,              // Horizontal FOV in degrees
+    pub monitor_width_cm: f32,
+    pub viewing_distance_cm: f32,
+    pub size: f32,                  // All dimensions below are in degrees
+    pub gap: f32,
+    pub thickness: f32,
+    pub dot_size: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Arm {
+    pub enabled: bool,
+    pub length: Option<f32>,  // Pixels, `None` uses the config's `size`
+    #[serde(default, with = "crate::color::hex_option")]
+    pub color: Option<u32>,   // `None` uses the config's `color`
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CrosshairArms {
+    pub top: Arm,
+    pub bottom: Arm,
+    pub left: Arm,
+    pub right: Arm,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chevron {
+    pub angle: f32,   // Opening angle between the two arms in degrees
+    pub length: i32,  // Arm length in pixels
+    pub below: bool,  // ^ with its tip just below the center, otherwise V above it
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Brackets {
+    pub corner_length: i32,  // Length of each leg of a bracket
+    pub spread: i32,         // Distance from the center to each corner, per axis
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diamond {
+    pub size: i32,          // Distance from the center to each corner
+    pub inner_lines: bool,  // Also draw the classic arms inside the outline
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoubleCircle {
+    pub inner_radius: i32,
+    pub outer_radius: i32,
+    pub cross_lines: bool,  // Also draw the classic arms
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MilDot {
+    pub spacing: i32,      // Distance between marks
+    pub count: i32,        // Marks on each side of the center; the lines run one spacing past the last
+    pub marker_size: i32,  // Dot radius, or half the tick length
+    pub ticks: bool,       // Short cross ticks instead of dots
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Triangle {
+    pub size: i32,     // Height from the tip (at the gap above center) to the base
+    pub filled: bool,  // Fill with the line color, otherwise only the edges are drawn
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ruler {
+    pub length: i32,      // Distance from the center to the end of each arm
+    pub minor_step: i32,  // Short tick every this many pixels, 0 = none
+    pub major_step: i32,  // Long tick every this many pixels, 0 = none
+    pub label_step: i32,  // Distance label every this many pixels, 0 = no labels
+    pub tick_size: i32,   // Half the length of a short tick; long ticks are twice that
+    pub label_size: i32,  // Label text height in pixels
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElementOpacity {
+    pub lines: f32,    // 0.0-1.0, multiplied with the alpha of each element's color
+    pub dot: f32,
+    pub outline: f32,
+    pub shadow: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Anchor {
+    pub x: f32,  // Percent of the screen (or attached window) width, 50 = center
+    pub y: f32,  // Percent of the screen (or attached window) height, 50 = center
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrosshairLine {
+    pub start_x: i32,
+    pub start_y: i32,
+    pub end_x: i32,
+    pub end_y: i32,
+    pub thickness: i32,
+    #[serde(with = "crate::color::hex")]
+    pub color: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ElementPoint {
+    pub x: i32,  // Offset from the crosshair center
+    pub y: i32,
+}
+
+/// A primitive of the Custom style. Coordinates are offsets from the crosshair
+/// center; a `thickness` of 0 leaves out the stroke and `fill` is optional.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Element {
+    Line(CrosshairLine),
+    Circle {
+        x: i32,
+        y: i32,
+        radius: i32,
+        thickness: i32,
+        #[serde(with = "crate::color::hex")]
+        color: u32,
+        #[serde(default, with = "crate::color::hex_option")]
+        fill: Option<u32>,
+    },
+    Rect {
+        x: i32,  // Top-left corner
+        y: i32,
+        width: i32,
+        height: i32,
+        thickness: i32,
+        #[serde(with = "crate::color::hex")]
+        color: u32,
+        #[serde(default, with = "crate::color::hex_option")]
+        fill: Option<u32>,
+    },
+    Polygon {
+        points: Vec<ElementPoint>,  // Closed automatically
+        thickness: i32,
+        #[serde(with = "crate::color::hex")]
+        color: u32,
+        #[serde(default, with = "crate::color::hex_option")]
+        fill: Option<u32>,
+    },
+    Arc {
+        x: i32,
+        y: i32,
+        radius: i32,
+        start_angle: f32,  // Degrees, counterclockwise from the positive x axis
+        sweep_angle: f32,  // Degrees, positive is counterclockwise
+        thickness: i32,
+        #[serde(with = "crate::color::hex")]
+        color: u32,
+    },
+}
+
+// Configs from before `elements` hold bare lines without a "type"
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredElement {
+    Element(Element),
+    Line(CrosshairLine),
+}
+
+fn deserialize_elements<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Element>, D::Error> {
+    let stored = Vec::<StoredElement>::deserialize(deserializer)?;
+    Ok(stored
+        .into_iter()
+        .map(|element| match element {
+            StoredElement::Element(element) => element,
+            StoredElement::Line(line) => Element::Line(line),
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OverlayText {
+    pub text: String,
+    pub font: String,   // Font family, e.g. "Segoe UI"
+    pub size: i32,      // Height in pixels
+    pub bold: bool,
+    #[serde(with = "crate::color::hex")]
+    pub color: u32,
+    pub offset_x: i32,  // Center of the text relative to the crosshair center
+    pub offset_y: i32,
+}
+
+impl OverlayText {
+    /// Rough distance from the crosshair center to the far edge of the text,
+    /// used to size the overlay window before the font is measured.
+    pub fn extent(&self) -> i32 {
+        let half_width = (self.size * self.text.chars().count() as i32 + 1) / 2;
+        (self.offset_x.abs() + half_width).max(self.offset_y.abs() + self.size)
+    }
+}
+
+impl Element {
+    /// Distance from the crosshair center to the farthest point of the
+    /// element, half its stroke included. Holds at any rotation.
+    pub fn extent(&self) -> f32 {
+        let distance = |x: i32, y: i32| (x as f32).hypot(y as f32);
+        let (reach, thickness) = match self {
+            Element::Line(line) => (
+                distance(line.start_x, line.start_y).max(distance(line.end_x, line.end_y)),
+                line.thickness,
+            ),
+            Element::Circle { x, y, radius, thickness, .. } | Element::Arc { x, y, radius, thickness, .. } => {
+                (distance(*x, *y) + radius.abs() as f32, *thickness)
+            }
+            Element::Rect { x, y, width, height, thickness, .. } => {
+                let reach = [(*x, *y), (x + width, *y), (*x, y + height), (x + width, y + height)]
+                    .into_iter()
+                    .map(|(x, y)| distance(x, y))
+                    .fold(0.0, f32::max);
+                (reach, *thickness)
+            }
+            Element::Polygon { points, thickness, .. } => (
+                points.iter().map(|point| distance(point.x, point.y)).fold(0.0, f32::max),
+                *thickness,
+            ),
+        };
+        reach + thickness.max(0) as f32 / 2.0
+    }
+}
+
+/// Revision of the config format written by this version. Revision 2 made
+/// `position_x`/`position_y` and the crosshair sizes fractional; older files
+/// still read, since whole numbers parse as either.
+pub const CONFIG_SCHEMA: u32 = 2;
+
+fn current_schema() -> u32 {
+    CONFIG_SCHEMA
+}
+
+fn default_animation_fps() -> u32 {
+    30
+}
+
+fn default_image_scale() -> f32 {
+    1.0
+}
+
+fn default_image_fps() -> u32 {
+    30
+}
+
+impl Default for CrosshairConfig {
+    fn default() -> Self {
+        Self {
+            schema: CONFIG_SCHEMA,
+            enabled: true,
+            size: 10.0,
+            thickness: 2.0,
+            cap_style: CapStyle::default(),
+            gap: 5.0,
+            color: 0x00FF00, // Green
+            outline_color: 0x000000,
+            outline_thickness: 1,
+            show_dot: true,
+            dot_size: 2.0,
+            show_outline: true,
+            opacity: 1.0,
+            element_opacity: ElementOpacity::default(),
+            style: CrosshairStyle::Classic,
+            position_x: 0.0,
+            position_y: 0.0,
+            rotation: 0.0,
+            t_length: 15,
+            chevron: Chevron::default(),
+            diamond: Diamond::default(),
+            brackets: Brackets::default(),
+            double_circle: DoubleCircle::default(),
+            mil_dot: MilDot::default(),
+            triangle: Triangle::default(),
+            ruler: Ruler::default(),
+            shadow_enabled: false,
+            shadow_color: 0x000000,
+            shadow_offset: 2,
+            shadow_blur: 0,
+            elements: Vec::new(),
+            texts: Vec::new(),
+            arms: CrosshairArms::default(),
+            dot_color: None,
+            dead_zone_radius: 0,
+            animation: Animation::default(),
+            animation_fps: default_animation_fps(),
+            color_mode: ColorMode::default(),
+            auto_contrast: AutoContrast::default(),
+            blend_mode: BlendMode::default(),
+            click_spread: ClickSpread::default(),
+            movement_spread: MovementSpread::default(),
+            secondary_config: None,
+            spray_pattern: SprayPattern::default(),
+            anchor: Anchor::default(),
+            sizing_mode: SizingMode::default(),
+            angular: AngularSizing::default(),
+            resolution_overrides: Vec::new(),
+        }
+    }
+}
+
+impl Default for AngularSizing {
+    fn default() -> Self {
+        Self {
+            reference: AngleReference::Game,
+            game_fov: 103.0,
+            monitor_width_cm: 53.0,  // 24" 16:9 monitor
+            viewing_distance_cm: 60.0,
+            size: 0.6,
+            gap: 0.3,
+            thickness: 0.1,
+            dot_size: 0.1,
+        }
+    }
+}
+
+impl Default for Arm {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            length: None,
+            color: None,
+        }
+    }
+}
+
+impl Arm {
+    /// Length to draw this arm with, or `None` when it's turned off.
+    pub fn resolved_length(&self, size: f32) -> Option<f32> {
+        self.enabled.then(|| self.length.unwrap_or(size))
+    }
+}
+
+impl CrosshairArms {
+    /// Longest enabled arm, used to size the overlay window.
+    pub fn max_length(&self, size: f32) -> f32 {
+        [&self.top, &self.bottom, &self.left, &self.right]
+            .iter()
+            .filter_map(|arm| arm.resolved_length(size))
+            .fold(0.0, f32::max)
+    }
+}
+
+impl Default for Chevron {
+    fn default() -> Self {
+        Self {
+            angle: 90.0,
+            length: 10,
+            below: true,
+        }
+    }
+}
+
+impl Default for Brackets {
+    fn default() -> Self {
+        Self {
+            corner_length: 6,
+            spread: 14,
+        }
+    }
+}
+
+impl Default for Diamond {
+    fn default() -> Self {
+        Self {
+            size: 12,
+            inner_lines: false,
+        }
+    }
+}
+
+impl Default for DoubleCircle {
+    fn default() -> Self {
+        Self {
+            inner_radius: 4,
+            outer_radius: 14,
+            cross_lines: false,
+        }
+    }
+}
+
+impl Default for MilDot {
+    fn default() -> Self {
+        Self {
+            spacing: 12,
+            count: 4,
+            marker_size: 2,
+            ticks: false,
+        }
+    }
+}
+
+impl Default for Ruler {
+    fn default() -> Self {
+        Self {
+            length: 200,
+            minor_step: 10,
+            major_step: 50,
+            label_step: 100,
+            tick_size: 3,
+            label_size: 11,
+        }
+    }
+}
+
+impl Ruler {
+    /// Distances from the center that get a tick, each with the tick's half
+    /// length and whether it is labeled.
+    pub fn ticks(&self) -> Vec<(i32, i32, bool)> {
+        let multiple = |distance: i32, step: i32| step > 0 && distance % step == 0;
+        (1..=self.length)
+            .filter(|&distance| {
+                multiple(distance, self.minor_step)
+                    || multiple(distance, self.major_step)
+                    || multiple(distance, self.label_step)
+            })
+            .map(|distance| {
+                let major = multiple(distance, self.major_step) || multiple(distance, self.label_step);
+                let half = if major { self.tick_size * 2 } else { self.tick_size };
+                (distance, half, multiple(distance, self.label_step))
+            })
+            .collect()
+    }
+}
+
+impl Default for Triangle {
+    fn default() -> Self {
+        Self {
+            size: 8,
+            filled: true,
+        }
+    }
+}
+
+impl Default for ElementOpacity {
+    fn default() -> Self {
+        Self {
+            lines: 1.0,
+            dot: 1.0,
+            outline: 1.0,
+            shadow: 1.0,
+        }
+    }
+}
+
+impl Default for Anchor {
+    fn default() -> Self {
+        Self { x: 50.0, y: 50.0 }
+    }
+}
+
+impl Anchor {
+    /// Pixel position of the anchor inside an area of `width` x `height`.
+    pub fn point(&self, width: i32, height: i32) -> (i32, i32) {
+        let x = width as f32 * self.x.clamp(0.0, 100.0) / 100.0;
+        let y = height as f32 * self.y.clamp(0.0, 100.0) / 100.0;
+        (x.round() as i32, y.round() as i32)
+    }
+}
+
+impl AngularSizing {
+    /// Converts an angle (measured from the screen center) into pixels for a
+    /// screen `screen_width` pixels wide.
+    pub fn angle_to_pixels(&self, degrees: f32, screen_width: i32) -> f32 {
+        let half_width = screen_width as f32 / 2.0;
+        let angle = degrees.to_radians();
+        
+        match self.reference {
+            AngleReference::Game => {
+                let half_fov = (self.game_fov.clamp(1.0, 179.0) / 2.0).to_radians();
+                half_width * angle.tan() / half_fov.tan()
+            }
+            AngleReference::Eye => {
+                if self.monitor_width_cm <= 0.0 {
+                    return 0.0;
+                }
+                let pixels_per_cm = screen_width as f32 / self.monitor_width_cm;
+                self.viewing_distance_cm * angle.tan() * pixels_per_cm
+            }
+        }
+    }
+}
+
+/// Fills the pixel dimensions of an Angular config from its angular values.
+/// Pixel-sized configs are left untouched.
+pub fn resolve_sizing(config: &mut CrosshairConfig, screen_width: i32) {
+    if let Some(secondary) = config.secondary_config.as_deref_mut() {
+        resolve_sizing(secondary, screen_width);
+    }
+    
+    if let SizingMode::Pixels = config.sizing_mode {
+        return;
+    }
+    
+    let angular = &config.angular;
+    let to_px = |degrees: f32| angular.angle_to_pixels(degrees, screen_width);
+    
+    config.size = to_px(angular.size).max(0.0);
+    config.gap = to_px(angular.gap).max(0.0);
+    config.thickness = to_px(angular.thickness).max(1.0);
+    config.dot_size = to_px(angular.dot_size).max(1.0);
+}
+
+/// `config` with its override for a `width`x`height` display applied, and
+/// the secondary config's too. `None` when neither has one, so callers can
+/// keep using the config as is.
+pub fn resolve_resolution(config: &CrosshairConfig, width: i32, height: i32) -> Option<CrosshairConfig> {
+    let matching = config
+        .resolution_overrides
+        .iter()
+        .find(|o| o.width == width && o.height == height);
+    let secondary = config
+        .secondary_config
+        .as_deref()
+        .and_then(|secondary| resolve_resolution(secondary, width, height));
+    if matching.is_none() && secondary.is_none() {
+        return None;
+    }
+    
+    let mut resolved = config.clone();
+    if let Some(secondary) = secondary {
+        resolved.secondary_config = Some(Box::new(secondary));
+    }
+    if let Some(o) = matching {
+        resolved.size = o.size.unwrap_or(resolved.size);
+        resolved.gap = o.gap.unwrap_or(resolved.gap);
+        resolved.thickness = o.thickness.unwrap_or(resolved.thickness);
+        resolved.dot_size = o.dot_size.unwrap_or(resolved.dot_size);
+        resolved.outline_thickness = o.outline_thickness.unwrap_or(resolved.outline_thickness);
+    }
+    Some(resolved)
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+//! Auto-contrast: swaps the crosshair color when it blends in with what is
+//! behind it. The overlay samples the screen around the crosshair and asks
+//! [`contrast_color`] which color to draw with.
+
+use serde::{Deserialize, Serialize};
+
+use crate::color::{alpha, from_rgba, rgb};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoContrast {
+    pub enabled: bool,
+    #[serde(default, with = "crate::color::hex_option")]
+    pub alternate_color: Option<u32>,  // Color to switch to, None = the main color inverted
+    pub threshold: f32,                // WCAG contrast ratio (1-21) below which the color switches
+    pub interval_ms: u32,              // How often the screen is sampled
+    pub sample_radius: i32,            // Half the side of the sampled square around the center
+}
+
+impl Default for AutoContrast {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            alternate_color: None,
+            threshold: 2.0,
+            interval_ms: 250,
+            sample_radius: 12,
+        }
+    }
+}
+
+/// Relative luminance of 0xRRGGBB, 0.0 (black) to 1.0 (white).
+pub fn luminance(color: u32) -> f32 {
+    let linear = |shift: u32| {
+        let c = ((color >> shift) & 0xFF) as f32 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(16) + 0.7152 * linear(8) + 0.0722 * linear(0)
+}
+
+/// WCAG contrast ratio between two 0xRRGGBB colors, from 1.0 to 21.0.
+pub fn contrast_ratio(a: u32, b: u32) -> f32 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// `color` with its RGB channels inverted (complementary hue, opposite
+/// brightness), alpha kept.
+pub fn invert(color: u32) -> u32 {
+    from_rgba(!color & 0xFFFFFF, alpha(color))
+}
+
+/// The color to draw with over `background` (0xRRGGBB): `color` while it
+/// stands out enough, otherwise whichever of it and the alternate contrasts more.
+pub fn contrast_color(settings: &AutoContrast, color: u32, background: u32) -> u32 {
+    if contrast_ratio(rgb(color), background) >= settings.threshold {
+        return color;
+    }
+    
+    let alternate = settings.alternate_color.unwrap_or_else(|| invert(color));
+    if contrast_ratio(rgb(alternate), background) > contrast_ratio(rgb(color), background) {
+        alternate
+    } else {
+        color
+    }
+}
+
+/// Average of 0x??RRGGBB pixels as 0xRRGGBB.
+pub fn average_rgb(pixels: &[u32]) -> u32 {
+    if pixels.is_empty() {
+        return 0;
+    }
+    
+    let channel = |shift: u32| {
+        let sum: u64 = pixels.iter().map(|&p| ((p >> shift) & 0xFF) as u64).sum();
+        (sum / pixels.len() as u64) as u32
+    };
+    channel(16) << 16 | channel(8) << 8 | channel(0)
+}
\ No newline at end of file
@@ -0,0 +1,759 @@
+//! Platform-independent crosshair geometry and a small software rasterizer.
+//!
+//! [`shapes`] turns a config into strokes and fills in offsets from the
+//! crosshair center, the same ones the overlay draws, and [`render`] paints
+//! them into a [`Raster`]. Nothing here touches the OS, so the output is the
+//! same on every machine and can be checked in tests.
+
+use crate::config::{CapStyle, CrosshairArms, CrosshairConfig, CrosshairStyle, Element};
+
+/// Guide lines are open-ended; they stop this far out, past any screen edge.
+pub const GUIDE_REACH: f32 = 16384.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+    
+    fn distance(self, other: Point) -> f32 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+/// Turns `point` around the origin. Positive degrees are clockwise on screen,
+/// like `rotation`.
+pub fn rotate(point: Point, degrees: f32) -> Point {
+    let (sin_a, cos_a) = degrees.to_radians().sin_cos();
+    Point {
+        x: point.x * cos_a - point.y * sin_a,
+        y: point.x * sin_a + point.y * cos_a,
+    }
+}
+
+/// Axis-aligned box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl Bounds {
+    fn around(point: Point, radius: f32) -> Self {
+        Self {
+            left: point.x - radius,
+            top: point.y - radius,
+            right: point.x + radius,
+            bottom: point.y + radius,
+        }
+    }
+    
+    pub fn union(self, other: Bounds) -> Bounds {
+        Bounds {
+            left: self.left.min(other.left),
+            top: self.top.min(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.max(other.bottom),
+        }
+    }
+    
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+    
+    pub fn height(&self) -> f32 {
+        self.bottom - self.top
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Path {
+    Polyline { points: Vec<Point>, closed: bool },
+    Circle { center: Point, radius: f32 },
+}
+
+/// A path stroked `width` pixels wide in `color` and optionally filled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    pub path: Path,
+    pub width: f32,  // 0 leaves out the stroke
+    pub color: u32,
+    pub fill: Option<u32>,
+    pub cap: CapStyle,
+}
+
+impl Shape {
+    /// Everything the shape can cover, caps and joins included.
+    pub fn bounds(&self) -> Option<Bounds> {
+        // Square caps and miter joins reach past the endpoint diagonally
+        let margin = self.width / 2.0 * std::f32::consts::SQRT_2;
+        match &self.path {
+            Path::Polyline { points, .. } => points
+                .iter()
+                .map(|&point| Bounds::around(point, margin))
+                .reduce(Bounds::union),
+            Path::Circle { center, radius } => Some(Bounds::around(*center, radius + self.width / 2.0)),
+        }
+    }
+    
+    // Redrawn in one color and `widen` pixels wider, for outline and shadow
+    // passes. Shapes without a stroke or fill stay invisible.
+    fn plain(&self, color: u32, widen: f32) -> Option<Shape> {
+        if self.width <= 0.0 && self.fill.is_none() {
+            return None;
+        }
+        Some(Shape {
+            width: self.width + widen,
+            color,
+            fill: self.fill.map(|_| color),
+            ..self.clone()
+        })
+    }
+}
+
+/// Union of the bounds of `shapes`, `None` when nothing is drawn.
+pub fn bounds(shapes: &[Shape]) -> Option<Bounds> {
+    shapes.iter().filter_map(Shape::bounds).reduce(Bounds::union)
+}
+
+/// Start and end of each enabled arm, pointing away from the center, with its
+/// color override. `rotation` is in degrees.
+pub fn arm_segments(config: &CrosshairConfig, rotation: f32) -> Vec<(Point, Point, Option<u32>)> {
+    let CrosshairArms { top, bottom, left, right } = &config.arms;
+    let gap = config.gap;
+    
+    [(top, 0.0, -1.0), (bottom, 0.0, 1.0), (left, -1.0, 0.0), (right, 1.0, 0.0)]
+        .into_iter()
+        .filter_map(|(arm, dir_x, dir_y)| {
+            let length = arm.resolved_length(config.size)?;
+            let start = rotate(Point::new(dir_x * gap, dir_y * gap), rotation);
+            let end = rotate(Point::new(dir_x * (gap + length), dir_y * (gap + length)), rotation);
+            Some((start, end, arm.color))
+        })
+        .collect()
+}
+
+/// The style's lines and shapes in drawing order, without the center dot.
+/// Images have no geometry and give an empty list.
+pub fn shapes(config: &CrosshairConfig) -> Vec<Shape> {
+    let width = config.thickness.max(0.0);
+    let color = config.color;
+    let stroke = |path: Path| Shape {
+        path,
+        width,
+        color,
+        fill: None,
+        cap: config.cap_style.clone(),
+    };
+    let line = |points: Vec<Point>| stroke(Path::Polyline { points, closed: false });
+    let ring = |radius: f32| stroke(Path::Circle { center: Point::default(), radius });
+    let turn = |x: f32, y: f32| rotate(Point::new(x, y), config.rotation);
+    let gap = config.gap;
+    let size = config.size;
+    
+    let arms = |rotation: f32| -> Vec<Shape> {
+        arm_segments(config, rotation)
+            .into_iter()
+            .map(|(start, end, arm_color)| Shape {
+                color: arm_color.unwrap_or(color),
+                ..line(vec![start, end])
+            })
+            .collect()
+    };
+    
+    let mut shapes = Vec::new();
+    match &config.style {
+        CrosshairStyle::Classic => shapes.extend(arms(config.rotation)),
+        CrosshairStyle::X => shapes.extend(arms(config.rotation + 45.0)),
+        CrosshairStyle::Dot | CrosshairStyle::Image { .. } => {}
+        CrosshairStyle::Circle => {
+            shapes.push(ring(size + gap));
+            if gap > 0.0 {
+                shapes.extend(arms(config.rotation));
+            }
+        }
+        CrosshairStyle::Square => {
+            let half = size + gap;
+            let corners = vec![
+                Point::new(-half, -half),
+                Point::new(half, -half),
+                Point::new(half, half),
+                Point::new(-half, half),
+            ];
+            shapes.push(stroke(Path::Polyline { points: corners, closed: true }));
+            if gap > 0.0 {
+                shapes.extend(arms(config.rotation));
+            }
+        }
+        CrosshairStyle::TShape => {
+            let top = -gap - size;
+            let t_length = config.t_length as f32;
+            shapes.push(line(vec![Point::new(-t_length, top), Point::new(t_length, top)]));
+            shapes.push(line(vec![Point::new(0.0, top), Point::new(0.0, -gap)]));
+            if gap > 0.0 {
+                shapes.push(line(vec![Point::new(0.0, gap), Point::new(0.0, gap + size)]));
+                shapes.push(line(vec![Point::new(-gap - size, 0.0), Point::new(-gap, 0.0)]));
+                shapes.push(line(vec![Point::new(gap, 0.0), Point::new(gap + size, 0.0)]));
+            }
+        }
+        CrosshairStyle::Custom => shapes.extend(config.elements.iter().map(|element| element_shape(element, &config.cap_style))),
+        CrosshairStyle::Guides => {
+            shapes.push(line(vec![Point::new(-GUIDE_REACH, 0.0), Point::new(-gap, 0.0)]));
+            shapes.push(line(vec![Point::new(gap, 0.0), Point::new(GUIDE_REACH, 0.0)]));
+            shapes.push(line(vec![Point::new(0.0, -GUIDE_REACH), Point::new(0.0, -gap)]));
+            shapes.push(line(vec![Point::new(0.0, gap), Point::new(0.0, GUIDE_REACH)]));
+        }
+        CrosshairStyle::Chevron => {
+            let chevron = &config.chevron;
+            let direction = if chevron.below { 1.0 } else { -1.0 };
+            let half_angle = (chevron.angle.clamp(1.0, 179.0) / 2.0).to_radians();
+            let tip = direction * gap;
+            let arm_x = chevron.length as f32 * half_angle.sin();
+            let arm_y = direction * chevron.length as f32 * half_angle.cos();
+            shapes.push(line(vec![turn(-arm_x, tip + arm_y), turn(0.0, tip), turn(arm_x, tip + arm_y)]));
+        }
+        CrosshairStyle::Diamond => {
+            let size = config.diamond.size as f32;
+            let corners = vec![turn(0.0, -size), turn(size, 0.0), turn(0.0, size), turn(-size, 0.0)];
+            shapes.push(stroke(Path::Polyline { points: corners, closed: true }));
+            if config.diamond.inner_lines {
+                shapes.extend(arms(config.rotation));
+            }
+        }
+        CrosshairStyle::Brackets => {
+            let spread = config.brackets.spread as f32;
+            let leg = config.brackets.corner_length.min(config.brackets.spread) as f32;
+            for (sign_x, sign_y) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                let (corner_x, corner_y) = (sign_x * spread, sign_y * spread);
+                shapes.push(line(vec![
+                    turn(corner_x, corner_y - sign_y * leg),
+                    turn(corner_x, corner_y),
+                    turn(corner_x - sign_x * leg, corner_y),
+                ]));
+            }
+        }
+        CrosshairStyle::DoubleCircle => {
+            let circles = &config.double_circle;
+            shapes.push(ring(circles.inner_radius as f32));
+            shapes.push(ring(circles.outer_radius as f32));
+            if circles.cross_lines {
+                shapes.extend(arms(config.rotation));
+            }
+        }
+        CrosshairStyle::MilDot => {
+            let mil_dot = &config.mil_dot;
+            let count = mil_dot.count.max(0);
+            let reach = (mil_dot.spacing * (count + 1)) as f32;
+            let marker = mil_dot.marker_size as f32;
+            for (dir_x, dir_y) in [(1.0, 0.0), (0.0, 1.0)] {
+                shapes.push(line(vec![turn(-dir_x * reach, -dir_y * reach), turn(dir_x * reach, dir_y * reach)]));
+            }
+            for step in (-count..=count).filter(|&step| step != 0) {
+                let offset = (step * mil_dot.spacing) as f32;
+                for (along_x, along_y) in [(offset, 0.0), (0.0, offset)] {
+                    if mil_dot.ticks {
+                        let (tick_x, tick_y) = if along_y == 0.0 { (0.0, marker) } else { (marker, 0.0) };
+                        shapes.push(line(vec![
+                            turn(along_x - tick_x, along_y - tick_y),
+                            turn(along_x + tick_x, along_y + tick_y),
+                        ]));
+                    } else {
+                        shapes.push(stroke(Path::Circle { center: turn(along_x, along_y), radius: marker }));
+                    }
+                }
+            }
+        }
+        CrosshairStyle::Triangle => {
+            let tip = -gap;
+            let height = config.triangle.size as f32;
+            let half_base = height / 3f32.sqrt(); // Equilateral
+            let corners = vec![turn(0.0, tip), turn(-half_base, tip - height), turn(half_base, tip - height)];
+            shapes.push(Shape {
+                fill: config.triangle.filled.then_some(color),
+                ..stroke(Path::Polyline { points: corners, closed: true })
+            });
+        }
+        // Distance labels are text and left to the renderer
+        CrosshairStyle::Ruler => {
+            let ruler = &config.ruler;
+            let gap = gap.max(0.0);
+            let length = ruler.length as f32;
+            let ticks = ruler.ticks();
+            for (dir_x, dir_y) in [(0.0f32, -1.0f32), (0.0, 1.0), (-1.0, 0.0), (1.0, 0.0)] {
+                if length > gap {
+                    shapes.push(line(vec![turn(dir_x * gap, dir_y * gap), turn(dir_x * length, dir_y * length)]));
+                }
+                let (across_x, across_y) = (dir_y.abs(), dir_x.abs());
+                for &(tick, half, _) in ticks.iter().filter(|&&(tick, _, _)| tick as f32 >= gap) {
+                    let (along_x, along_y) = (dir_x * tick as f32, dir_y * tick as f32);
+                    let half = half as f32;
+                    shapes.push(line(vec![
+                        turn(along_x - across_x * half, along_y - across_y * half),
+                        turn(along_x + across_x * half, along_y + across_y * half),
+                    ]));
+                }
+            }
+        }
+    }
+    shapes
+}
+
+/// The center dot, filled and stroked with the main pen, or `None` when off.
+pub fn dot_shape(config: &CrosshairConfig) -> Option<Shape> {
+    let color = config.dot_color.unwrap_or(config.color);
+    config.show_dot.then(|| Shape {
+        path: Path::Circle { center: Point::default(), radius: config.dot_size },
+        width: config.thickness.max(0.0),
+        color,
+        fill: Some(color),
+        cap: config.cap_style.clone(),
+    })
+}
+
+/// A Custom style element as a shape. Arcs become polylines.
+pub fn element_shape(element: &Element, cap: &CapStyle) -> Shape {
+    let point = |x: i32, y: i32| Point::new(x as f32, y as f32);
+    let (path, thickness, color, fill) = match element {
+        Element::Line(line) => (
+            Path::Polyline {
+                points: vec![point(line.start_x, line.start_y), point(line.end_x, line.end_y)],
+                closed: false,
+            },
+            line.thickness,
+            line.color,
+            None,
+        ),
+        Element::Circle { x, y, radius, thickness, color, fill } => (
+            Path::Circle { center: point(*x, *y), radius: *radius as f32 },
+            *thickness,
+            *color,
+            *fill,
+        ),
+        Element::Rect { x, y, width, height, thickness, color, fill } => (
+            Path::Polyline {
+                points: vec![point(*x, *y), point(x + width, *y), point(x + width, y + height), point(*x, y + height)],
+                closed: true,
+            },
+            *thickness,
+            *color,
+            *fill,
+        ),
+        Element::Polygon { points, thickness, color, fill } => (
+            Path::Polyline {
+                points: points.iter().map(|p| point(p.x, p.y)).collect(),
+                closed: true,
+            },
+            *thickness,
+            *color,
+            *fill,
+        ),
+        Element::Arc { x, y, radius, start_angle, sweep_angle, thickness, color } => {
+            // Counterclockwise with y pointing up, about one segment per 5 degrees
+            let steps = (sweep_angle.abs() / 5.0).ceil().max(1.0) as usize;
+            let points = (0..=steps)
+                .map(|step| {
+                    let angle = (start_angle + sweep_angle * step as f32 / steps as f32).to_radians();
+                    Point::new(*x as f32 + *radius as f32 * angle.cos(), *y as f32 - *radius as f32 * angle.sin())
+                })
+                .collect();
+            (Path::Polyline { points, closed: false }, *thickness, *color, None)
+        }
+    };
+    Shape {
+        path,
+        width: thickness.max(0) as f32,
+        color,
+        fill,
+        cap: cap.clone(),
+    }
+}
+
+/// Pixels in 0xAARRGGBB, row by row from the top left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raster {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u32>,
+}
+
+impl Raster {
+    /// A fully transparent raster.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height, pixels: vec![0; width * height] }
+    }
+    
+    pub fn pixel(&self, x: usize, y: usize) -> u32 {
+        self.pixels[y * self.width + x]
+    }
+    
+    /// Paints `shape` centered on `origin`, leaving pixels within `hole` of
+    /// the origin alone. Like GDI, whole coordinates are pixel centers, and
+    /// coverage is sampled there without antialiasing so results are exact
+    /// and repeatable.
+    pub fn draw(&mut self, shape: &Shape, origin: Point, alpha: f32, hole: f32) {
+        let Some(bounds) = shape.bounds() else {
+            return;
+        };
+        let alpha = ((alpha.clamp(0.0, 1.0) * 255.0).round() as u32) << 24;
+        
+        let left = (bounds.left + origin.x).floor().max(0.0) as usize;
+        let top = (bounds.top + origin.y).floor().max(0.0) as usize;
+        let right = ((bounds.right + origin.x).floor().max(-1.0) + 1.0) as usize;
+        let bottom = ((bounds.bottom + origin.y).floor().max(-1.0) + 1.0) as usize;
+        let (right, bottom) = (right.min(self.width), bottom.min(self.height));
+        
+        for y in top..bottom {
+            for x in left..right {
+                let sample = Point::new(x as f32 - origin.x, y as f32 - origin.y);
+                if hole > 0.0 && sample.distance(Point::default()) < hole {
+                    continue;
+                }
+                let color = if shape.width > 0.0 && on_stroke(shape, sample) {
+                    Some(shape.color)
+                } else if shape.fill.is_some() && inside(&shape.path, sample) {
+                    shape.fill
+                } else {
+                    None
+                };
+                if let Some(color) = color {
+                    self.pixels[y * self.width + x] = alpha | (color & 0x00FF_FFFF);
+                }
+            }
+        }
+    }
+}
+
+/// Draws `config` into a `width` x `height` raster the way the overlay does:
+/// shadow, outline, lines and dot, each pass over the last. The crosshair is
+/// centered in the raster and moved by its position offset.
+pub fn render(config: &CrosshairConfig, width: usize, height: usize) -> Raster {
+    let mut raster = Raster::new(width, height);
+    let center = Point::new(
+        (width / 2) as f32 + config.position_x,
+        (height / 2) as f32 + config.position_y,
+    );
+    let opacity = &config.element_opacity;
+    let hole = config.dead_zone_radius.max(0) as f32;
+    let lines = shapes(config);
+    let dot = dot_shape(config);
+    
+    if config.shadow_enabled {
+        let offset = config.shadow_offset as f32;
+        let origin = Point::new(center.x + offset, center.y + offset);
+        for shape in lines.iter().filter_map(|shape| shape.plain(config.shadow_color, 0.0)) {
+            raster.draw(&shape, origin, config.opacity * opacity.shadow, hole);
+        }
+    }
+    
+    if config.show_outline {
+        let widen = (config.outline_thickness * 2) as f32;
+        for shape in lines.iter().filter_map(|shape| shape.plain(config.outline_color, widen)) {
+            raster.draw(&shape, center, config.opacity * opacity.outline, hole);
+        }
+        if let Some(dot) = dot.as_ref().and_then(|dot| dot.plain(config.outline_color, widen)) {
+            raster.draw(&dot, center, config.opacity * opacity.outline, 0.0);
+        }
+    }
+    
+    for shape in &lines {
+        raster.draw(shape, center, config.opacity * opacity.lines, hole);
+    }
+    if let Some(dot) = &dot {
+        raster.draw(dot, center, config.opacity * opacity.dot, 0.0);
+    }
+    raster
+}
+
+fn on_stroke(shape: &Shape, point: Point) -> bool {
+    let half = shape.width / 2.0;
+    match &shape.path {
+        Path::Circle { center, radius } => (point.distance(*center) - radius).abs() <= half,
+        Path::Polyline { points, closed } => {
+            let closing = closed.then(|| (points[points.len() - 1], points[0]));
+            let mut segments = points.windows(2).map(|pair| (pair[0], pair[1])).chain(closing);
+            // Closed outlines have no ends, so their corners are always joined
+            segments.any(|(start, end)| {
+                let cap = if *closed { &CapStyle::Square } else { &shape.cap };
+                near_segment(point, start, end, half, cap)
+            })
+        }
+    }
+}
+
+// Within `half` of the segment, with the ends shaped by `cap`
+fn near_segment(point: Point, start: Point, end: Point, half: f32, cap: &CapStyle) -> bool {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let length = dx.hypot(dy);
+    if length == 0.0 {
+        return !matches!(cap, CapStyle::Flat) && point.distance(start) <= half;
+    }
+    
+    // Position along the segment and distance across it
+    let along = ((point.x - start.x) * dx + (point.y - start.y) * dy) / length;
+    let across = ((point.x - start.x) * dy - (point.y - start.y) * dx).abs() / length;
+    match cap {
+        // GDI leaves out the last pixel of a line
+        CapStyle::Flat => (0.0..length).contains(&along) && across <= half,
+        CapStyle::Square => (-half..=length + half).contains(&along) && across <= half,
+        CapStyle::Round => {
+            if along < 0.0 {
+                point.distance(start) <= half
+            } else if along > length {
+                point.distance(end) <= half
+            } else {
+                across <= half
+            }
+        }
+    }
+}
+
+// Even-odd fill test
+fn inside(path: &Path, point: Point) -> bool {
+    match path {
+        Path::Circle { center, radius } => point.distance(*center) <= *radius,
+        Path::Polyline { points, .. } => {
+            let mut inside = false;
+            let mut previous = match points.last() {
+                Some(&last) => last,
+                None => return false,
+            };
+            for &current in points {
+                if (current.y > point.y) != (previous.y > point.y) {
+                    let cross_x = previous.x + (point.y - previous.y) / (current.y - previous.y) * (current.x - previous.x);
+                    if point.x < cross_x {
+                        inside = !inside;
+                    }
+                }
+                previous = current;
+            }
+            inside
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn close(a: Point, b: Point) -> bool {
+        a.distance(b) < 1e-4
+    }
+    
+    // One character per pixel: '#' for `color`, 'o' for any other, '.' empty
+    fn ascii(raster: &Raster, color: u32) -> Vec<String> {
+        (0..raster.height)
+            .map(|y| {
+                (0..raster.width)
+                    .map(|x| match raster.pixel(x, y) {
+                        0 => '.',
+                        pixel if pixel & 0x00FF_FFFF == color => '#',
+                        _ => 'o',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+    
+    fn plain_classic() -> CrosshairConfig {
+        CrosshairConfig {
+            size: 3.0,
+            gap: 2.0,
+            thickness: 1.0,
+            cap_style: CapStyle::Flat,
+            show_dot: false,
+            show_outline: false,
+            color: 0xFF0000,
+            ..CrosshairConfig::default()
+        }
+    }
+    
+    #[test]
+    fn rotate_turns_clockwise_on_screen() {
+        assert!(close(rotate(Point::new(1.0, 0.0), 90.0), Point::new(0.0, 1.0)));
+        assert!(close(rotate(Point::new(0.0, -1.0), 90.0), Point::new(1.0, 0.0)));
+        assert!(close(rotate(Point::new(3.0, 4.0), 360.0), Point::new(3.0, 4.0)));
+    }
+    
+    #[test]
+    fn arms_start_at_the_gap() {
+        let config = CrosshairConfig { size: 10.0, gap: 5.0, ..CrosshairConfig::default() };
+        let arms = arm_segments(&config, 0.0);
+        assert_eq!(arms.len(), 4);
+        assert!(close(arms[0].0, Point::new(0.0, -5.0)));
+        assert!(close(arms[0].1, Point::new(0.0, -15.0)));
+        assert!(close(arms[3].0, Point::new(5.0, 0.0)));
+        assert!(close(arms[3].1, Point::new(15.0, 0.0)));
+    }
+    
+    #[test]
+    fn fractional_sizes_stay_exact() {
+        let config = CrosshairConfig { size: 2.5, gap: 1.5, ..CrosshairConfig::default() };
+        let arms = arm_segments(&config, 0.0);
+        assert!(close(arms[3].0, Point::new(1.5, 0.0)));
+        assert!(close(arms[3].1, Point::new(4.0, 0.0)));
+    }
+    
+    #[test]
+    fn arms_follow_overrides_and_rotation() {
+        let mut config = CrosshairConfig { size: 10.0, gap: 0.0, ..CrosshairConfig::default() };
+        config.arms.bottom.enabled = false;
+        config.arms.right.length = Some(4.0);
+        config.arms.right.color = Some(0x0000FF);
+        
+        let arms = arm_segments(&config, 90.0);
+        assert_eq!(arms.len(), 3);
+        // The right arm points down after a quarter turn
+        let (_, end, color) = arms[2];
+        assert!(close(end, Point::new(0.0, 4.0)));
+        assert_eq!(color, Some(0x0000FF));
+    }
+    
+    #[test]
+    fn x_style_is_classic_turned_45_degrees() {
+        let config = CrosshairConfig { style: CrosshairStyle::X, ..plain_classic() };
+        let Path::Polyline { points, .. } = &shapes(&config)[0].path else {
+            panic!("arms are polylines");
+        };
+        let expected = rotate(Point::new(0.0, -5.0), 45.0);
+        assert!(close(points[1], expected));
+    }
+    
+    #[test]
+    fn bounds_include_half_the_stroke() {
+        let config = CrosshairConfig { size: 10.0, gap: 5.0, thickness: 2.0, ..CrosshairConfig::default() };
+        let bounds = bounds(&shapes(&config)).unwrap();
+        let margin = std::f32::consts::SQRT_2;
+        assert!((bounds.left + 15.0 + margin).abs() < 1e-4);
+        assert!((bounds.bottom - 15.0 - margin).abs() < 1e-4);
+        assert!((bounds.width() - bounds.height()).abs() < 1e-4);
+    }
+    
+    #[test]
+    fn images_have_no_geometry() {
+        let config = CrosshairConfig {
+            style: CrosshairStyle::Image { path: String::new(), scale: 1.0, fps_limit: 30 },
+            show_dot: false,
+            ..CrosshairConfig::default()
+        };
+        assert!(shapes(&config).is_empty());
+        assert!(bounds(&shapes(&config)).is_none());
+    }
+    
+    #[test]
+    fn rect_elements_become_closed_outlines() {
+        let element = Element::Rect { x: -2, y: -1, width: 4, height: 2, thickness: 1, color: 0xFFFFFF, fill: Some(0x123456) };
+        let shape = element_shape(&element, &CapStyle::Flat);
+        assert_eq!(shape.fill, Some(0x123456));
+        let Path::Polyline { points, closed } = shape.path else {
+            panic!("rects are polylines");
+        };
+        assert!(closed);
+        assert!(close(points[2], Point::new(2.0, 1.0)));
+    }
+    
+    #[test]
+    fn arc_elements_run_counterclockwise_with_y_up() {
+        let element = Element::Arc { x: 0, y: 0, radius: 10, start_angle: 0.0, sweep_angle: 90.0, thickness: 1, color: 0 };
+        let Path::Polyline { points, .. } = element_shape(&element, &CapStyle::Round).path else {
+            panic!("arcs are polylines");
+        };
+        assert!(close(points[0], Point::new(10.0, 0.0)));
+        assert!(close(*points.last().unwrap(), Point::new(0.0, -10.0)));
+    }
+    
+    #[test]
+    fn renders_classic_golden() {
+        let raster = render(&plain_classic(), 13, 13);
+        let expected = [
+            ".............",
+            ".............",
+            "......#......",
+            "......#......",
+            "......#......",
+            ".............",
+            "..###...###..",
+            ".............",
+            "......#......",
+            "......#......",
+            "......#......",
+            ".............",
+            ".............",
+        ];
+        assert_eq!(ascii(&raster, 0xFF0000), expected);
+        assert_eq!(raster.pixel(6, 2) >> 24, 0xFF);
+    }
+    
+    #[test]
+    fn renders_outline_under_dot() {
+        let config = CrosshairConfig {
+            style: CrosshairStyle::Dot,
+            dot_size: 1.0,
+            thickness: 1.0,
+            show_dot: true,
+            show_outline: true,
+            outline_thickness: 1,
+            outline_color: 0x000001,
+            ..plain_classic()
+        };
+        let raster = render(&config, 9, 9);
+        let expected = [
+            ".........",
+            ".........",
+            "...ooo...",
+            "..o###o..",
+            "..o###o..",
+            "..o###o..",
+            "...ooo...",
+            ".........",
+            ".........",
+        ];
+        assert_eq!(ascii(&raster, 0xFF0000), expected);
+    }
+    
+    #[test]
+    fn dead_zone_masks_lines_but_not_the_dot() {
+        let config = CrosshairConfig {
+            gap: 0.0,
+            size: 6.0,
+            show_dot: true,
+            dot_size: 1.0,
+            dead_zone_radius: 3,
+            ..plain_classic()
+        };
+        let raster = render(&config, 15, 15);
+        assert_ne!(raster.pixel(7, 7), 0);
+        assert_eq!(raster.pixel(7, 5), 0);
+        assert_ne!(raster.pixel(7, 2), 0);
+    }
+    
+    #[test]
+    fn position_moves_the_render() {
+        let config = CrosshairConfig { position_x: 2.0, position_y: -1.0, ..plain_classic() };
+        let moved = render(&config, 13, 13);
+        let centered = render(&plain_classic(), 13, 13);
+        assert_ne!(moved.pixel(8, 1), 0);
+        assert_eq!(moved.pixel(8, 1), centered.pixel(6, 2));
+        assert_eq!(moved.pixel(6 + 2 + 3, 5), 0xFFFF0000);
+    }
+    
+    #[test]
+    fn half_pixel_position_centers_on_even_sizes() {
+        let config = CrosshairConfig { thickness: 2.0, position_x: -0.5, position_y: -0.5, ..plain_classic() };
+        let raster = render(&config, 12, 12);
+        for y in 0..12 {
+            for x in 0..12 {
+                assert_eq!(raster.pixel(x, y), raster.pixel(11 - x, y), "({}, {})", x, y);
+                assert_eq!(raster.pixel(x, y), raster.pixel(x, 11 - y), "({}, {})", x, y);
+            }
+        }
+        assert_ne!(raster.pixel(5, 1), 0);
+        assert_ne!(raster.pixel(6, 1), 0);
+    }
+}
\ No newline at end of file
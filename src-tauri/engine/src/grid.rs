@@ -0,0 +1,55 @@
+//! Full-screen alignment grid for composing scenes and lining up screenshots.
+//! It is drawn by the overlay window but shown and hidden apart from the
+//! crosshair, so it isn't part of [`CrosshairConfig`](crate::CrosshairConfig).
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GridLayout {
+    Thirds,       // Rule of thirds
+    GoldenRatio,  // Lines at 0.382 and 0.618 of each side
+    Cells,        // `cells` equal columns and rows
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Grid {
+    pub layout: GridLayout,
+    pub cells: i32,  // Columns and rows of the Cells layout
+    #[serde(with = "crate::color::hex")]
+    pub color: u32,
+    pub thickness: i32,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            layout: GridLayout::Thirds,
+            cells: 4,
+            color: 0x80FFFFFF,  // Half-transparent white
+            thickness: 1,
+        }
+    }
+}
+
+impl Grid {
+    /// Offsets of the lines dividing a side `extent` pixels long, without
+    /// the edges themselves.
+    pub fn divisions(&self, extent: i32) -> Vec<i32> {
+        let fractions: Vec<f32> = match self.layout {
+            GridLayout::Thirds => vec![1.0 / 3.0, 2.0 / 3.0],
+            GridLayout::GoldenRatio => {
+                let minor = 1.0 - 1.0 / 1.618_034;
+                vec![minor, 1.0 - minor]
+            }
+            GridLayout::Cells => {
+                let cells = self.cells.clamp(1, 64);
+                (1..cells).map(|i| i as f32 / cells as f32).collect()
+            }
+        };
+        fractions
+            .into_iter()
+            .map(|fraction| (extent as f32 * fraction).round() as i32)
+            .collect()
+    }
+}
\ No newline at end of file
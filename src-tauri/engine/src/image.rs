@@ -0,0 +1,375 @@
+//! Image crosshairs (PNG, APNG and GIF). Images are decoded once into fully
+//! composited frames and kept in a small cache keyed by path and modification
+//! time, so a redraw never touches the disk unless the file was replaced.
+
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Files larger than this are rejected before decoding.
+pub const MAX_IMAGE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Largest accepted width or height in pixels.
+pub const MAX_IMAGE_SIZE: u32 = 1024;
+
+/// Animations with more frames than this are cut off.
+pub const MAX_IMAGE_FRAMES: usize = 256;
+
+const MAX_CACHED_IMAGES: usize = 8;
+
+// Browsers treat near-zero frame delays as "as fast as the encoder allowed",
+// which in practice means 100 ms
+const MIN_FRAME_DELAY_MS: u32 = 20;
+const DEFAULT_FRAME_DELAY_MS: u32 = 100;
+
+/// One fully composited frame with straight (non-premultiplied) RGBA pixels.
+#[derive(Debug)]
+struct ImageFrame {
+    pixels: Vec<u8>,
+    delay_ms: u32,
+}
+
+/// A decoded still or animated image.
+#[derive(Debug)]
+pub struct CrosshairImage {
+    pub width: u32,
+    pub height: u32,
+    frames: Vec<ImageFrame>,
+    duration_ms: u32,
+}
+
+impl CrosshairImage {
+    pub fn is_animated(&self) -> bool {
+        self.frames.len() > 1
+    }
+    
+    /// Index of the frame shown `elapsed` seconds into the (looping) animation.
+    pub fn frame_at(&self, elapsed: f32) -> usize {
+        if !self.is_animated() {
+            return 0;
+        }
+        
+        let mut time = (elapsed.max(0.0) * 1000.0) as u64 % self.duration_ms as u64;
+        for (index, frame) in self.frames.iter().enumerate() {
+            if time < frame.delay_ms as u64 {
+                return index;
+            }
+            time -= frame.delay_ms as u64;
+        }
+        self.frames.len() - 1
+    }
+    
+    /// `[r, g, b, a]` of the pixel at `x`, `y` in `frame`.
+    pub fn pixel(&self, frame: usize, x: u32, y: u32) -> [u8; 4] {
+        let pixels = &self.frames[frame].pixels;
+        let i = ((y * self.width + x) * 4) as usize;
+        [pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3]]
+    }
+}
+
+struct CachedImage {
+    path: PathBuf,
+    modified: SystemTime,
+    image: Arc<CrosshairImage>,
+}
+
+// Most recently used last
+static IMAGE_CACHE: Mutex<Vec<CachedImage>> = Mutex::new(Vec::new());
+
+/// Loads the PNG, APNG or GIF at `path`, validating its size, or returns the
+/// cached copy when the file hasn't changed since it was last decoded.
+pub fn load_image(path: &str) -> Result<Arc<CrosshairImage>, String> {
+    let path = Path::new(path);
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+    let decode = match extension.as_deref() {
+        Some("png") | Some("apng") => decode_png,
+        Some("gif") => decode_gif,
+        _ => return Err("Crosshair images must be PNG, APNG or GIF files".to_string()),
+    };
+    
+    let metadata = std::fs::metadata(path)
+        .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("{} is not a file", path.display()));
+    }
+    if metadata.len() > MAX_IMAGE_BYTES {
+        return Err(format!("Image is larger than {} MB", MAX_IMAGE_BYTES / (1024 * 1024)));
+    }
+    let modified = metadata.modified().map_err(|e| e.to_string())?;
+    
+    let mut cache = IMAGE_CACHE.lock().map_err(|e| e.to_string())?;
+    if let Some(index) = cache.iter().position(|c| c.path == path && c.modified == modified) {
+        let cached = cache.remove(index);
+        let image = cached.image.clone();
+        cache.push(cached);
+        return Ok(image);
+    }
+    
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let image = Arc::new(decode(BufReader::new(file))?);
+    cache.retain(|c| c.path != path);
+    if cache.len() >= MAX_CACHED_IMAGES {
+        cache.remove(0);
+    }
+    cache.push(CachedImage {
+        path: path.to_path_buf(),
+        modified,
+        image: image.clone(),
+    });
+    
+    Ok(image)
+}
+
+fn check_size(width: u32, height: u32) -> Result<(), String> {
+    if width == 0 || height == 0 || width > MAX_IMAGE_SIZE || height > MAX_IMAGE_SIZE {
+        return Err(format!(
+            "Image must be between 1x1 and {0}x{0} pixels, got {1}x{2}",
+            MAX_IMAGE_SIZE, width, height
+        ));
+    }
+    Ok(())
+}
+
+fn frame_delay(delay_ms: u32) -> u32 {
+    if delay_ms < MIN_FRAME_DELAY_MS {
+        DEFAULT_FRAME_DELAY_MS
+    } else {
+        delay_ms
+    }
+}
+
+// What happens to a frame's area before the next one is drawn
+#[derive(Clone, Copy, PartialEq)]
+enum Dispose {
+    Keep,
+    Clear,
+    Restore,
+}
+
+// A part of the image drawn by one frame, as RGBA
+struct FrameRegion<'a> {
+    pixels: &'a [u8],
+    left: u32,
+    top: u32,
+    width: u32,
+    height: u32,
+}
+
+// Builds full frames out of the partial updates GIF and APNG store
+struct Compositor {
+    width: u32,
+    height: u32,
+    canvas: Vec<u8>,
+    frames: Vec<ImageFrame>,
+}
+
+impl Compositor {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            canvas: vec![0; (width * height * 4) as usize],
+            frames: Vec::new(),
+        }
+    }
+    
+    // `over` alpha-blends the region onto the canvas instead of replacing it
+    fn add(&mut self, region: FrameRegion, over: bool, dispose: Dispose, delay_ms: u32) {
+        let previous = (dispose == Dispose::Restore).then(|| self.canvas.clone());
+        
+        let rows = region.height.min(self.height.saturating_sub(region.top));
+        let columns = region.width.min(self.width.saturating_sub(region.left));
+        for y in 0..rows {
+            for x in 0..columns {
+                let src = ((y * region.width + x) * 4) as usize;
+                let dst = (((region.top + y) * self.width + region.left + x) * 4) as usize;
+                let pixel = &region.pixels[src..src + 4];
+                let target = &mut self.canvas[dst..dst + 4];
+                if over {
+                    blend_straight(pixel, target);
+                } else {
+                    target.copy_from_slice(pixel);
+                }
+            }
+        }
+        
+        self.frames.push(ImageFrame {
+            pixels: self.canvas.clone(),
+            delay_ms: frame_delay(delay_ms),
+        });
+        
+        match dispose {
+            Dispose::Keep => {}
+            Dispose::Clear => {
+                for y in 0..rows {
+                    let start = (((region.top + y) * self.width + region.left) * 4) as usize;
+                    self.canvas[start..start + (columns * 4) as usize].fill(0);
+                }
+            }
+            Dispose::Restore => {
+                if let Some(previous) = previous {
+                    self.canvas = previous;
+                }
+            }
+        }
+    }
+    
+    fn finish(self) -> Result<CrosshairImage, String> {
+        if self.frames.is_empty() {
+            return Err("Image has no frames".to_string());
+        }
+        
+        let duration_ms = self.frames.iter().map(|f| f.delay_ms).sum();
+        Ok(CrosshairImage {
+            width: self.width,
+            height: self.height,
+            frames: self.frames,
+            duration_ms,
+        })
+    }
+}
+
+// Source-over for straight alpha RGBA
+fn blend_straight(src: &[u8], dst: &mut [u8]) {
+    let src_alpha = src[3] as u32;
+    if src_alpha == 255 {
+        dst.copy_from_slice(src);
+        return;
+    }
+    if src_alpha == 0 {
+        return;
+    }
+    
+    let dst_alpha = dst[3] as u32 * (255 - src_alpha) / 255;
+    let out_alpha = src_alpha + dst_alpha;
+    for channel in 0..3 {
+        dst[channel] = ((src[channel] as u32 * src_alpha + dst[channel] as u32 * dst_alpha) / out_alpha) as u8;
+    }
+    dst[3] = out_alpha as u8;
+}
+
+fn decode_png(file: BufReader<std::fs::File>) -> Result<CrosshairImage, String> {
+    let mut decoder = png::Decoder::new(file);
+    // Palettes, transparency chunks and 16-bit channels all become 8-bit
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|e| format!("Invalid PNG: {}", e))?;
+    
+    let (width, height) = (reader.info().width, reader.info().height);
+    check_size(width, height)?;
+    
+    // APNGs list their frame count up front; plain PNGs are one frame
+    let frame_count = reader
+        .info()
+        .animation_control
+        .map_or(1, |control| control.num_frames as usize)
+        .clamp(1, MAX_IMAGE_FRAMES);
+    let animated = reader.info().animation_control.is_some();
+    
+    let mut compositor = Compositor::new(width, height);
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    while compositor.frames.len() < frame_count {
+        let output = match reader.next_frame(&mut buffer) {
+            Ok(output) => output,
+            // Keep what decoded so far from truncated animations
+            Err(_) if !compositor.frames.is_empty() => break,
+            Err(e) => return Err(format!("Invalid PNG: {}", e)),
+        };
+        let pixels = to_rgba(&buffer[..output.buffer_size()], output.color_type)?;
+        
+        let control = reader.info().frame_control;
+        if animated && control.is_none() {
+            // The default image isn't part of the animation
+            continue;
+        }
+        
+        let (left, top, over, dispose, delay_ms) = match control {
+            Some(control) => {
+                let delay_den = if control.delay_den == 0 { 100 } else { control.delay_den as u32 };
+                let dispose = match control.dispose_op {
+                    png::DisposeOp::None => Dispose::Keep,
+                    png::DisposeOp::Background => Dispose::Clear,
+                    png::DisposeOp::Previous => Dispose::Restore,
+                };
+                (
+                    control.x_offset,
+                    control.y_offset,
+                    control.blend_op == png::BlendOp::Over,
+                    dispose,
+                    control.delay_num as u32 * 1000 / delay_den,
+                )
+            }
+            None => (0, 0, false, Dispose::Keep, 0),
+        };
+        
+        compositor.add(
+            FrameRegion {
+                pixels: &pixels,
+                left,
+                top,
+                width: output.width,
+                height: output.height,
+            },
+            over,
+            dispose,
+            delay_ms,
+        );
+    }
+    
+    compositor.finish()
+}
+
+fn to_rgba(data: &[u8], color_type: png::ColorType) -> Result<Vec<u8>, String> {
+    Ok(match color_type {
+        png::ColorType::Rgba => data.to_vec(),
+        png::ColorType::Rgb => data.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => data.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => data.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        png::ColorType::Indexed => return Err("Unsupported PNG color type".to_string()),
+    })
+}
+
+fn decode_gif(file: BufReader<std::fs::File>) -> Result<CrosshairImage, String> {
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(file).map_err(|e| format!("Invalid GIF: {}", e))?;
+    
+    let (width, height) = (decoder.width() as u32, decoder.height() as u32);
+    check_size(width, height)?;
+    
+    let mut compositor = Compositor::new(width, height);
+    while compositor.frames.len() < MAX_IMAGE_FRAMES {
+        let frame = match decoder.read_next_frame() {
+            Ok(Some(frame)) => frame,
+            Ok(None) => break,
+            Err(_) if !compositor.frames.is_empty() => break,
+            Err(e) => return Err(format!("Invalid GIF: {}", e)),
+        };
+        
+        let dispose = match frame.dispose {
+            gif::DisposalMethod::Background => Dispose::Clear,
+            gif::DisposalMethod::Previous => Dispose::Restore,
+            _ => Dispose::Keep,
+        };
+        
+        // GIF delays are in hundredths of a second; transparent pixels let
+        // the previous frame show through
+        compositor.add(
+            FrameRegion {
+                pixels: &frame.buffer[..],
+                left: frame.left as u32,
+                top: frame.top as u32,
+                width: frame.width as u32,
+                height: frame.height as u32,
+            },
+            true,
+            dispose,
+            frame.delay as u32 * 10,
+        );
+    }
+    
+    compositor.finish()
+}
\ No newline at end of file
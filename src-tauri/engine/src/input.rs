@@ -0,0 +1,221 @@
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+use windows::Win32::{
+    Foundation::*,
+    Graphics::Gdi::InvalidateRect,
+    System::LibraryLoader::GetModuleHandleW,
+    UI::WindowsAndMessaging::*,
+};
+use tracing::error;
+
+use crate::clicks::{ClickButton, ClickLog, ClickVisualizer, Ripple};
+use crate::config::CrosshairConfig;
+use crate::keystrokes::{KeystrokeDisplay, KeystrokeLog};
+use crate::spread::InputState;
+
+// Virtual-key codes of the movement keys, indexed by their bit in MOVEMENT_KEYS
+const MOVEMENT_VKS: [u32; 4] = [0x57, 0x41, 0x53, 0x44]; // W, A, S, D
+
+static LEFT_BUTTON_DOWN: AtomicBool = AtomicBool::new(false);
+static RIGHT_BUTTON_DOWN: AtomicBool = AtomicBool::new(false);
+
+// Bitmask of the movement keys currently held
+static MOVEMENT_KEYS: AtomicU8 = AtomicU8::new(0);
+
+// Recent keys for the keystroke display, only recorded while it is on
+static LOG_KEYS: AtomicBool = AtomicBool::new(false);
+static KEY_LOG: Mutex<KeystrokeLog> = Mutex::new(KeystrokeLog::new());
+
+// Recent clicks for the click visualizer, only recorded while it is on
+static LOG_CLICKS: AtomicBool = AtomicBool::new(false);
+static CLICK_LOG: Mutex<ClickLog> = Mutex::new(ClickLog::new());
+
+// Raw HHOOKs of the low-level hooks, 0 when not installed
+static MOUSE_HOOK: AtomicIsize = AtomicIsize::new(0);
+static KEYBOARD_HOOK: AtomicIsize = AtomicIsize::new(0);
+
+// Raw HWND repainted when the aiming state flips, 0 when there is none
+static REPAINT_WINDOW: AtomicIsize = AtomicIsize::new(0);
+
+/// Returns the input state seen by the hooks.
+pub fn input_state() -> InputState {
+    InputState {
+        firing: LEFT_BUTTON_DOWN.load(Ordering::Relaxed),
+        moving: MOVEMENT_KEYS.load(Ordering::Relaxed) != 0,
+        aiming: RIGHT_BUTTON_DOWN.load(Ordering::Relaxed),
+    }
+}
+
+pub fn wants_mouse_hook(config: &CrosshairConfig) -> bool {
+    config.click_spread.enabled
+        || config.secondary_config.is_some()
+        || config.spray_pattern.is_animated()
+        || LOG_CLICKS.load(Ordering::Relaxed)
+}
+
+pub fn wants_keyboard_hook(config: &CrosshairConfig) -> bool {
+    config.movement_spread.enabled || LOG_KEYS.load(Ordering::Relaxed)
+}
+
+/// Starts or stops recording keys for the keystroke display. The hooks pick
+/// this up at the next [`sync_input_hooks`].
+pub fn set_key_logging(enabled: bool) {
+    LOG_KEYS.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        KEY_LOG.lock().unwrap().clear();
+    }
+}
+
+/// Starts or stops recording clicks for the click visualizer. The hooks pick
+/// this up at the next [`sync_input_hooks`].
+pub fn set_click_logging(enabled: bool) {
+    LOG_CLICKS.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        CLICK_LOG.lock().unwrap().clear();
+    }
+}
+
+/// Rings the click visualizer should draw right now.
+pub fn recent_clicks(visualizer: &ClickVisualizer) -> Vec<Ripple> {
+    CLICK_LOG.lock().unwrap().ripples(visualizer, Instant::now())
+}
+
+/// Keys to show on the keystroke display right now, with their opacity.
+pub fn recent_keys(display: &KeystrokeDisplay) -> Vec<(String, f32)> {
+    KEY_LOG.lock().unwrap().visible(display, Instant::now())
+}
+
+/// Installs or removes the low-level hooks needed by `config`. Low-level hooks
+/// are serviced by the installing thread, so this must run on the overlay thread.
+/// `hwnd` is repainted right away when the right mouse button changes state.
+pub fn sync_input_hooks(hwnd: HWND, config: &CrosshairConfig) {
+    REPAINT_WINDOW.store(hwnd.0 as isize, Ordering::Relaxed);
+    set_mouse_hook(wants_mouse_hook(config));
+    set_keyboard_hook(wants_keyboard_hook(config));
+}
+
+fn set_mouse_hook(enabled: bool) {
+    if set_hook(&MOUSE_HOOK, WH_MOUSE_LL, Some(mouse_hook_proc), enabled) {
+        LEFT_BUTTON_DOWN.store(false, Ordering::Relaxed);
+        RIGHT_BUTTON_DOWN.store(false, Ordering::Relaxed);
+    }
+}
+
+fn set_keyboard_hook(enabled: bool) {
+    if set_hook(&KEYBOARD_HOOK, WH_KEYBOARD_LL, Some(keyboard_hook_proc), enabled) {
+        MOVEMENT_KEYS.store(0, Ordering::Relaxed);
+    }
+}
+
+// Installs or removes the hook stored in `slot`. Returns true when a hook was
+// removed so the caller can reset the state it tracked.
+fn set_hook(slot: &AtomicIsize, id: WINDOWS_HOOK_ID, proc: HOOKPROC, enabled: bool) -> bool {
+    let installed = slot.load(Ordering::Relaxed);
+    
+    unsafe {
+        if enabled && installed == 0 {
+            let Ok(instance) = GetModuleHandleW(None) else {
+                return false;
+            };
+            match SetWindowsHookExW(id, proc, instance, 0) {
+                Ok(hook) => slot.store(hook.0 as isize, Ordering::Relaxed),
+                Err(e) => error!("Failed to install input hook: {}", e),
+            }
+        } else if !enabled && installed != 0 {
+            let _ = UnhookWindowsHookEx(HHOOK(installed as *mut _));
+            slot.store(0, Ordering::Relaxed);
+            return true;
+        }
+    }
+    
+    false
+}
+
+/// Removes all hooks, e.g. when the overlay window is destroyed.
+pub fn remove_input_hooks() {
+    REPAINT_WINDOW.store(0, Ordering::Relaxed);
+    set_mouse_hook(false);
+    set_keyboard_hook(false);
+}
+
+// Keep this fast: Windows drops low-level hooks that stall mouse input
+unsafe extern "system" fn mouse_hook_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code >= 0 {
+        match wparam.0 as u32 {
+            WM_LBUTTONDOWN => LEFT_BUTTON_DOWN.store(true, Ordering::Relaxed),
+            WM_LBUTTONUP => LEFT_BUTTON_DOWN.store(false, Ordering::Relaxed),
+            WM_RBUTTONDOWN => set_aiming(true),
+            WM_RBUTTONUP => set_aiming(false),
+            _ => {}
+        }
+        if LOG_CLICKS.load(Ordering::Relaxed) {
+            let button = match wparam.0 as u32 {
+                WM_LBUTTONDOWN => Some(ClickButton::Left),
+                WM_RBUTTONDOWN => Some(ClickButton::Right),
+                _ => None,
+            };
+            if let Some(button) = button {
+                let info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+                log_click(button, info.pt);
+            }
+        }
+    }
+    
+    CallNextHookEx(None, code, wparam, lparam)
+}
+
+// Swapping to the ADS crosshair must not wait for the next animation frame
+unsafe fn set_aiming(aiming: bool) {
+    if RIGHT_BUTTON_DOWN.swap(aiming, Ordering::Relaxed) != aiming {
+        repaint();
+    }
+}
+
+unsafe extern "system" fn keyboard_hook_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code >= 0 {
+        let info = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+        if let Some(index) = MOVEMENT_VKS.iter().position(|&vk| vk == info.vkCode) {
+            let bit = 1u8 << index;
+            match wparam.0 as u32 {
+                WM_KEYDOWN | WM_SYSKEYDOWN => {
+                    MOVEMENT_KEYS.fetch_or(bit, Ordering::Relaxed);
+                }
+                WM_KEYUP | WM_SYSKEYUP => {
+                    MOVEMENT_KEYS.fetch_and(!bit, Ordering::Relaxed);
+                }
+                _ => {}
+            }
+        }
+        if LOG_KEYS.load(Ordering::Relaxed) {
+            log_key(info.vkCode, wparam.0 as u32);
+        }
+    }
+    
+    CallNextHookEx(None, code, wparam, lparam)
+}
+
+// Hooks run on the overlay thread between messages, never while it paints,
+// so the log locks are always free here
+unsafe fn log_click(button: ClickButton, point: POINT) {
+    CLICK_LOG.lock().unwrap().record(button, point.x, point.y, Instant::now());
+    repaint();
+}
+
+unsafe fn log_key(vk: u32, message: u32) {
+    let mut log = KEY_LOG.lock().unwrap();
+    match message {
+        WM_KEYDOWN | WM_SYSKEYDOWN => log.press(vk),
+        WM_KEYUP | WM_SYSKEYUP => log.release(vk, Instant::now()),
+        _ => return,
+    }
+    drop(log);
+    repaint();
+}
+
+unsafe fn repaint() {
+    let hwnd = REPAINT_WINDOW.load(Ordering::Relaxed);
+    if hwnd != 0 {
+        let _ = InvalidateRect(HWND(hwnd as *mut _), None, false);
+    }
+}
\ No newline at end of file
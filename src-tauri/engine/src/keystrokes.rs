@@ -0,0 +1,154 @@
+//! Keystroke display for streamers: recently pressed keys drawn in a corner of
+//! the overlay. Keys come from the platform keyboard hook, only while the
+//! display is on, and are never stored; everything here is pure.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+// Oldest keys are dropped past this, whatever `max_keys` says
+const LOG_CAPACITY: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum KeystrokeLayout {
+    Row,     // Side by side, newest on the right
+    Column,  // Stacked, newest at the bottom
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeystrokeDisplay {
+    pub enabled: bool,
+    pub corner: Corner,
+    pub layout: KeystrokeLayout,
+    pub max_keys: usize,  // Most keys shown at once
+    pub fade_ms: u32,     // How long a released key takes to fade out
+    pub font_size: i32,
+    #[serde(with = "crate::color::hex")]
+    pub color: u32,       // Key names
+    #[serde(with = "crate::color::hex")]
+    pub background: u32,  // Box behind each key
+    pub margin: i32,      // Distance from the corner in pixels
+}
+
+impl Default for KeystrokeDisplay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            corner: Corner::BottomLeft,
+            layout: KeystrokeLayout::Row,
+            max_keys: 6,
+            fade_ms: 800,
+            font_size: 18,
+            color: 0xFFFFFF,
+            background: 0x60000000,  // Black at 62% opacity
+            margin: 24,
+        }
+    }
+}
+
+struct Keystroke {
+    vk: u32,
+    released: Option<Instant>,
+}
+
+/// Keys pressed recently, oldest first.
+pub struct KeystrokeLog {
+    keys: Vec<Keystroke>,
+}
+
+impl KeystrokeLog {
+    pub const fn new() -> Self {
+        Self { keys: Vec::new() }
+    }
+    
+    /// Records a key going down. Auto-repeat of a held key changes nothing.
+    pub fn press(&mut self, vk: u32) {
+        if self.keys.iter().any(|key| key.vk == vk && key.released.is_none()) {
+            return;
+        }
+        self.keys.push(Keystroke { vk, released: None });
+        if self.keys.len() > LOG_CAPACITY {
+            self.keys.remove(0);
+        }
+    }
+    
+    pub fn release(&mut self, vk: u32, now: Instant) {
+        for key in self.keys.iter_mut().filter(|key| key.vk == vk && key.released.is_none()) {
+            key.released = Some(now);
+        }
+    }
+    
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+    
+    /// The newest `display.max_keys` keys with their opacity: 1.0 while held,
+    /// then fading to nothing over `display.fade_ms`. Faded keys are dropped.
+    pub fn visible(&mut self, display: &KeystrokeDisplay, now: Instant) -> Vec<(String, f32)> {
+        let fade = Duration::from_millis(display.fade_ms as u64);
+        let opacity = |key: &Keystroke| match key.released {
+            None => 1.0,
+            Some(_) if fade.is_zero() => 0.0,
+            Some(released) => 1.0 - now.saturating_duration_since(released).as_secs_f32() / fade.as_secs_f32(),
+        };
+        self.keys.retain(|key| opacity(key) > 0.0);
+        
+        let skip = self.keys.len().saturating_sub(display.max_keys);
+        self.keys[skip..]
+            .iter()
+            .map(|key| (key_name(key.vk), opacity(key)))
+            .collect()
+    }
+}
+
+impl Default for KeystrokeLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Short label for a Windows virtual-key code.
+pub fn key_name(vk: u32) -> String {
+    let name = match vk {
+        0x08 => "Back",
+        0x09 => "Tab",
+        0x0D => "Enter",
+        0x10 | 0xA0 | 0xA1 => "Shift",
+        0x11 | 0xA2 | 0xA3 => "Ctrl",
+        0x12 | 0xA4 | 0xA5 => "Alt",
+        0x14 => "Caps",
+        0x1B => "Esc",
+        0x20 => "Space",
+        0x25 => "←",
+        0x26 => "↑",
+        0x27 => "→",
+        0x28 => "↓",
+        0x2D => "Ins",
+        0x2E => "Del",
+        0xC0 => "`",
+        0xBD => "-",
+        0xBB => "=",
+        0xDB => "[",
+        0xDD => "]",
+        0xBA => ";",
+        0xDE => "'",
+        0xBC => ",",
+        0xBE => ".",
+        0xBF => "/",
+        0xDC => "\\",
+        // Digits and letters share their ASCII codes
+        0x30..=0x39 | 0x41..=0x5A => return char::from(vk as u8).to_string(),
+        0x60..=0x69 => return format!("Num{}", vk - 0x60),
+        0x70..=0x87 => return format!("F{}", vk - 0x6F),
+        _ => return format!("#{:02X}", vk),
+    };
+    name.to_string()
+}
\ No newline at end of file
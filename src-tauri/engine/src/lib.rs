@@ -0,0 +1,93 @@
+//! Crosshair overlay engine.
+//!
+//! This crate contains everything needed to draw a crosshair on top of other
+//! applications, independent of the Tauri settings app:
+//!
+//! - [`CrosshairConfig`] and friends describe what the crosshair looks like and
+//!   (de)serialize to the same JSON used by `config.json` and presets.
+//! - On Windows, [`create_overlay_window`] spawns a click-through, always-on-top
+//!   layered window on its own thread, and [`update_config`] / [`toggle_overlay`]
+//!   change it live.
+//! - [`backend`] wraps that behind the [`OverlayBackend`] trait, so hosts can
+//!   run the same code on platforms or setups with a different overlay.
+//! - [`geometry`] holds the shapes behind each style and [`render`] rasterizes
+//!   them in memory, for tests and previews without a window.
+//!
+//! ```no_run
+//! # // The overlay only exists on Windows; elsewhere this compiles to nothing
+//! # #[cfg(windows)]
+//! # {
+//! use crosshair_engine::{create_overlay_window, update_config, CrosshairConfig};
+//!
+//! create_overlay_window().unwrap();
+//! update_config(CrosshairConfig { color: 0xFF0000, ..Default::default() }).unwrap();
+//! # }
+//! ```
+
+pub mod animation;
+pub mod backend;
+pub mod clicks;
+pub mod clock;
+pub mod color;
+pub mod color_key;
+pub mod colorblind;
+pub mod config;
+pub mod contrast;
+pub mod geometry;
+pub mod grid;
+pub mod keystrokes;
+pub mod image;
+pub mod spray;
+pub mod spread;
+pub mod symmetry;
+#[cfg(windows)]
+mod accent;
+#[cfg(windows)]
+mod canvas;
+#[cfg(windows)]
+mod input;
+mod metrics;
+mod monitor;
+#[cfg(windows)]
+mod overlay;
+#[cfg(windows)]
+mod picker;
+
+pub use animation::{animate, frame_rate, needs_frames, Animation, ColorMode};
+pub use clicks::{ClickButton, ClickOrigin, ClickVisualizer};
+pub use clock::ClockDisplay;
+pub use backend::{backend, set_backend, HeadlessBackend, OverlayBackend, OverlayStatus};
+#[cfg(windows)]
+pub use backend::GdiBackend;
+pub use config::{
+    resolve_sizing, Anchor, AngleReference, AngularSizing, Arm, BlendMode, Brackets, CapStyle, Chevron,
+    CrosshairArms, CrosshairConfig, CrosshairLine, CrosshairStyle, Diamond, DoubleCircle, Element,
+    ElementOpacity, ElementPoint, MilDot, OverlayText, ResolutionOverride, Ruler, SizingMode, Triangle,
+    resolve_resolution, CONFIG_SCHEMA,
+};
+pub use color_key::{pick_color_key, DEFAULT_COLOR_KEY};
+pub use colorblind::{palettes, simulate, Deficiency, Palette, PaletteColor};
+pub use contrast::AutoContrast;
+pub use geometry::{render, Raster};
+pub use grid::{Grid, GridLayout};
+pub use keystrokes::{Corner, KeystrokeDisplay, KeystrokeLayout};
+pub use image::{load_image, CrosshairImage};
+pub use spray::{SprayPattern, SprayPoint, SprayState};
+pub use spread::{ClickSpread, InputState, MovementSpread, SpreadState};
+pub use symmetry::{mirror_elements, rotate_elements, MirrorAxis};
+
+#[cfg(windows)]
+pub use overlay::{
+    attach_to_window, begin_config_transaction, capture_anchor_area, commit_config_transaction,
+    create_overlay_window, destroy_overlay_window, flash_osd, get_attached_window, get_config,
+    get_overlay_status, is_edit_mode, restart_overlay, set_capture_visible, set_click_visualizer,
+    set_clock_display, set_edit_mode, set_grid, set_keystroke_display, set_monitor,
+    set_position_changed_handler, set_suppressed, set_taskbar_created_handler, set_topmost_interval,
+    shutdown_overlay, start_timer, toggle_overlay, update_config, RENDERER,
+};
+pub use metrics::{render_metrics, RenderMetrics};
+pub use monitor::MonitorInfo;
+#[cfg(windows)]
+pub use monitor::list_monitors;
+#[cfg(windows)]
+pub use picker::pick_screen_color;
\ No newline at end of file
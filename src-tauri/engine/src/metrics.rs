@@ -0,0 +1,86 @@
+//! Paint timing for the overlay window, to put numbers on "the overlay causes
+//! stutter" reports.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Weight of the newest paint in the running average
+const AVERAGE_WEIGHT: f64 = 0.1;
+
+// Repaints are counted over windows of this length
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderMetrics {
+    pub last_paint_us: u64,         // Duration of the most recent paint, in microseconds
+    pub average_paint_us: u64,      // Running average, weighted towards recent paints
+    pub max_paint_us: u64,          // Slowest paint since the overlay started
+    pub repaints_per_second: u32,   // Over the last full second, 0 when idle
+    pub total_repaints: u64,
+}
+
+struct Tracker {
+    metrics: RenderMetrics,
+    average_us: f64,
+    window_start: Option<Instant>,
+    window_count: u32,
+}
+
+static TRACKER: Mutex<Tracker> = Mutex::new(Tracker {
+    metrics: RenderMetrics {
+        last_paint_us: 0,
+        average_paint_us: 0,
+        max_paint_us: 0,
+        repaints_per_second: 0,
+        total_repaints: 0,
+    },
+    average_us: 0.0,
+    window_start: None,
+    window_count: 0,
+});
+
+/// Records one finished paint that took `duration`. Only the GDI overlay
+/// paints, so elsewhere nothing calls it.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) fn record_paint(duration: Duration) {
+    let mut tracker = TRACKER.lock().unwrap();
+    let micros = duration.as_micros() as u64;
+    
+    tracker.average_us = if tracker.metrics.total_repaints == 0 {
+        micros as f64
+    } else {
+        tracker.average_us + (micros as f64 - tracker.average_us) * AVERAGE_WEIGHT
+    };
+    tracker.metrics.last_paint_us = micros;
+    tracker.metrics.average_paint_us = tracker.average_us.round() as u64;
+    tracker.metrics.max_paint_us = tracker.metrics.max_paint_us.max(micros);
+    tracker.metrics.total_repaints += 1;
+    
+    let now = Instant::now();
+    match tracker.window_start {
+        Some(start) if now - start < RATE_WINDOW => tracker.window_count += 1,
+        Some(start) => {
+            let rate = tracker.window_count as f64 / (now - start).as_secs_f64();
+            tracker.metrics.repaints_per_second = rate.round() as u32;
+            tracker.window_start = Some(now);
+            tracker.window_count = 1;
+        }
+        None => {
+            tracker.window_start = Some(now);
+            tracker.window_count = 1;
+        }
+    }
+}
+
+/// Paint timings of the overlay window since it started.
+pub fn render_metrics() -> RenderMetrics {
+    let tracker = TRACKER.lock().unwrap();
+    let mut metrics = tracker.metrics.clone();
+    
+    // A static crosshair only repaints on changes; the last rate would be stale
+    if tracker.window_start.is_none_or(|start| start.elapsed() >= RATE_WINDOW * 2) {
+        metrics.repaints_per_second = 0;
+    }
+    metrics
+}
\ No newline at end of file
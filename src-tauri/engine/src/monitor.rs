@@ -0,0 +1,68 @@
+//! Connected monitors, for centering the crosshair on one other than the primary.
+
+use serde::Serialize;
+#[cfg(windows)]
+use windows::Win32::{
+    Foundation::{BOOL, LPARAM, RECT},
+    Graphics::Gdi::{EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW},
+    UI::WindowsAndMessaging::MONITORINFOF_PRIMARY,
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorInfo {
+    pub index: usize,  // Pass to set_monitor; follows Windows' enumeration order
+    pub name: String,  // Device name, e.g. \\.\DISPLAY1
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub primary: bool,
+}
+
+/// Lists the connected monitors with their virtual-screen rectangles.
+#[cfg(windows)]
+pub fn list_monitors() -> Vec<MonitorInfo> {
+    let mut monitors: Vec<MonitorInfo> = Vec::new();
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            None,
+            None,
+            Some(collect_monitor),
+            LPARAM(&mut monitors as *mut Vec<MonitorInfo> as isize),
+        );
+    }
+    monitors
+}
+
+/// Rectangle (x, y, width, height) of monitor `index`, if it's still connected.
+#[cfg(windows)]
+pub(crate) fn monitor_area(index: usize) -> Option<(i32, i32, i32, i32)> {
+    list_monitors()
+        .into_iter()
+        .find(|m| m.index == index)
+        .map(|m| (m.x, m.y, m.width, m.height))
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn collect_monitor(monitor: HMONITOR, _hdc: HDC, _rect: *mut RECT, data: LPARAM) -> BOOL {
+    let monitors = &mut *(data.0 as *mut Vec<MonitorInfo>);
+    
+    let mut info = MONITORINFOEXW::default();
+    info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+    if GetMonitorInfoW(monitor, &mut info as *mut MONITORINFOEXW as *mut MONITORINFO).as_bool() {
+        let rect = info.monitorInfo.rcMonitor;
+        let name_len = info.szDevice.iter().position(|&c| c == 0).unwrap_or(info.szDevice.len());
+        monitors.push(MonitorInfo {
+            index: monitors.len(),
+            name: String::from_utf16_lossy(&info.szDevice[..name_len]),
+            x: rect.left,
+            y: rect.top,
+            width: rect.right - rect.left,
+            height: rect.bottom - rect.top,
+            primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+        });
+    }
+    
+    // Keep enumerating
+    BOOL(1)
+}
\ No newline at end of file
@@ -0,0 +1,2615 @@
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use windows::{
+    core::*,
+    Win32::{
+        Foundation::*,
+        Graphics::Gdi::*,
+        System::LibraryLoader::*,
+        System::RemoteDesktop::{WTSRegisterSessionNotification, WTSUnRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION},
+        System::SystemInformation::GetLocalTime,
+        System::Threading::GetCurrentThreadId,
+        UI::Accessibility::*,
+        UI::Input::KeyboardAndMouse::{ReleaseCapture, SetCapture},
+        UI::WindowsAndMessaging::*,
+    },
+};
+use once_cell::sync::Lazy;
+use tracing::{error, warn};
+
+use crate::accent::accent_color;
+use crate::animation::{animate, frame_rate, needs_frames, Animation, ColorMode, PULSE_AMPLITUDE};
+use crate::backend::OverlayStatus;
+use crate::canvas::{sample_screen, Canvas, PresentedFrame};
+use crate::metrics::record_paint;
+use crate::monitor::monitor_area;
+use crate::color;
+use crate::color_key::{pick_color_key, DEFAULT_COLOR_KEY};
+use crate::contrast::{average_rgb, contrast_color};
+use crate::config::{
+    resolve_resolution, resolve_sizing, BlendMode, CapStyle, CrosshairConfig, CrosshairStyle, Element, OverlayText,
+};
+use crate::geometry::{self, arm_segments};
+use crate::grid::Grid;
+use crate::image::load_image;
+use crate::input::{
+    input_state, recent_clicks, recent_keys, remove_input_hooks, set_click_logging, set_key_logging,
+    sync_input_hooks, wants_keyboard_hook, wants_mouse_hook,
+};
+use crate::clicks::{ClickOrigin, ClickVisualizer};
+use crate::clock::ClockDisplay;
+use crate::keystrokes::{Corner, KeystrokeDisplay, KeystrokeLayout};
+use crate::spray::{SprayPattern, SprayState};
+use crate::spread::{max_spread, SpreadState};
+
+static OVERLAY_STATE: Lazy<Arc<Mutex<OverlayState>>> = Lazy::new(|| {
+    Arc::new(Mutex::new(OverlayState::default()))
+});
+
+// Broadcast by Explorer when the taskbar is (re)created, e.g. after explorer.exe restarts
+static TASKBAR_CREATED_MSG: Lazy<u32> = Lazy::new(|| unsafe {
+    RegisterWindowMessageW(w!("TaskbarCreated"))
+});
+
+static TASKBAR_CREATED_HANDLER: Mutex<Option<Box<dyn Fn() + Send>>> = Mutex::new(None);
+
+// Called with the new position_x/position_y after the crosshair is dragged in edit mode
+type PositionHandler = Box<dyn Fn(f32, f32) + Send>;
+static POSITION_CHANGED_HANDLER: Mutex<Option<PositionHandler>> = Mutex::new(None);
+
+static OVERLAY_THREAD: Mutex<Option<JoinHandle<Result<()>>>> = Mutex::new(None);
+
+// Index into list_monitors() the crosshair is centered on, None = primary
+static SELECTED_MONITOR: Mutex<Option<usize>> = Mutex::new(None);
+
+// Resolution of the display the crosshair is on, picking the config's
+// resolution override. Read at window creation and on display changes.
+static RESOLUTION: Mutex<(i32, i32)> = Mutex::new((0, 0));
+
+// How often topmost is re-asserted, 0 disables the watchdog. An app setting,
+// so presets and shared configs don't carry it.
+static TOPMOST_INTERVAL_MS: AtomicU32 = AtomicU32::new(1000);
+
+// Posted to the overlay thread to (re)install the WinEvent hooks for the attached window
+const WM_OVERLAY_ATTACH: u32 = WM_APP + 1;
+
+// Posted to the overlay thread to re-arm the topmost watchdog and animation timers
+const WM_OVERLAY_TIMERS: u32 = WM_APP + 2;
+
+// Posted to the overlay thread to enter or leave edit mode
+const WM_OVERLAY_EDIT: u32 = WM_APP + 3;
+
+// Posted to the overlay thread to start showing the OSD message
+const WM_OVERLAY_OSD: u32 = WM_APP + 4;
+
+// Posted to the overlay thread to start ticking a countdown
+const WM_OVERLAY_COUNTDOWN: u32 = WM_APP + 5;
+
+// Edit mode gives the window a visible, clickable background and room for coordinates
+const EDIT_MODE_MIN_SIZE: i32 = 160;
+const EDIT_MODE_BACKGROUND: u32 = 0x202020;
+const EDIT_MODE_BACKDROP_OPACITY: f32 = 0.6;
+
+const TOPMOST_TIMER_ID: usize = 1;
+const ANIMATION_TIMER_ID: usize = 2;
+const CONTRAST_TIMER_ID: usize = 3;
+const OSD_TIMER_ID: usize = 4;
+const WAKE_TIMER_ID: usize = 5;
+const COUNTDOWN_TIMER_ID: usize = 6;
+const KEYSTROKE_TIMER_ID: usize = 7;
+const CLICK_TIMER_ID: usize = 8;
+const CLOCK_TIMER_ID: usize = 9;
+
+// Displays can take a few seconds to come back after resume or unlock, so the
+// window is refreshed once right away and again after this delay
+const WAKE_RECHECK_MS: u32 = 3000;
+
+// The OSD message stays fully visible, then fades out over the rest of its time
+const OSD_DURATION: Duration = Duration::from_millis(1500);
+const OSD_FADE: Duration = Duration::from_millis(500);
+const OSD_FRAME_MS: u32 = 33;
+// Room for the message below the crosshair
+const OSD_MIN_SIZE: i32 = 360;
+const OSD_OFFSET_Y: i32 = 60;
+const OSD_FONT_SIZE: i32 = 18;
+
+// Message flashed near the crosshair and when it started, see [`flash_osd`]
+static OSD: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
+// A countdown counts whole seconds above the crosshair, then blinks its zero
+// for a while before it goes away
+const COUNTDOWN_FLASH: Duration = Duration::from_millis(2000);
+const COUNTDOWN_BLINK_MS: u128 = 250;
+const COUNTDOWN_FRAME_MS: u32 = 50;
+const COUNTDOWN_MIN_SIZE: i32 = 240;
+const COUNTDOWN_OFFSET_Y: i32 = -50;
+const COUNTDOWN_FONT_SIZE: i32 = 22;
+const COUNTDOWN_COLOR: u32 = 0xFFFFFF;
+const COUNTDOWN_ZERO_COLOR: u32 = 0xFF4040;
+const MAX_COUNTDOWN_SECS: f32 = 24.0 * 60.0 * 60.0;
+
+// Running countdown, see [`start_timer`]
+static COUNTDOWN: Mutex<Option<Countdown>> = Mutex::new(None);
+
+struct Countdown {
+    label: String,
+    duration: Duration,
+    started: Instant,
+}
+
+impl Countdown {
+    // Done blinking and ready to be removed
+    fn finished(&self) -> bool {
+        self.started.elapsed() >= self.duration + COUNTDOWN_FLASH
+    }
+}
+
+// Alignment grid drawn across the target area, None = hidden
+static GRID: Mutex<Option<Grid>> = Mutex::new(None);
+
+// Keystroke display drawn in a corner of the target area, None = off
+static KEYSTROKES: Mutex<Option<KeystrokeDisplay>> = Mutex::new(None);
+
+// Released keys fade out at this frame rate; held keys need no frames
+const KEYSTROKE_FRAME_MS: u32 = 33;
+// Space around a key name inside its box, and between boxes
+const KEYSTROKE_PADDING: i32 = 6;
+
+// Click visualizer rings, None = off
+static CLICKS: Mutex<Option<ClickVisualizer>> = Mutex::new(None);
+const CLICK_FRAME_MS: u32 = 16;
+
+// Clock and session timer in a corner, None = off
+static CLOCK: Mutex<Option<ClockDisplay>> = Mutex::new(None);
+// Often enough that the seconds never visibly lag
+const CLOCK_FRAME_MS: u32 = 250;
+// The session timer counts from the first overlay window
+static SESSION_START: Lazy<Instant> = Lazy::new(Instant::now);
+
+// Reference point for animation time
+static ANIMATION_START: Lazy<Instant> = Lazy::new(Instant::now);
+
+const OVERLAY_CLASS_NAME: PCWSTR = w!("CrosshairOverlayClass");
+
+/// Name of the renderer drawing the overlay: GDI layers blended into a
+/// per-pixel alpha frame, see `canvas`.
+pub const RENDERER: &str = "gdi";
+
+struct OverlayState {
+    hwnd: Option<HWND>,
+    config: CrosshairConfig,
+    suppressed: bool,  // Hidden by the process watcher, independent of config.enabled
+    attached: Option<HWND>,  // Window the crosshair is centered on instead of the screen
+    attach_hooks: Vec<HWINEVENTHOOK>,
+    thread_id: u32,  // Thread running the overlay message loop, 0 when not running
+    editing: bool,   // Clickable so the crosshair can be dragged into place
+    drag_start: Option<(POINT, f32, f32)>,  // Cursor and position_x/position_y when the drag began
+    spread: SpreadState,
+    spray: SprayState,
+    last_frame: Option<Instant>,
+    contrast_color: Option<u32>,  // Replaces config.color while auto-contrast says so
+    accent_color: Option<u32>,    // Cached system accent color, cleared when it changes
+    capture_visible: bool,        // false keeps the window out of recordings and screenshots
+    presented: PresentedFrame,    // Last frame shown, so unchanged pixels aren't sent again
+    alpha_works: bool,            // A per-pixel alpha frame has been shown
+    color_keyed: bool,            // Per-pixel alpha failed, frames go out with a color key
+    color_key_error: Option<String>,  // Why no color key fits the config, drawn with holes meanwhile
+    transaction: Option<Transaction>,  // Open begin_config_transaction, if any
+}
+
+// Window update held back while a config transaction is open
+struct Transaction {
+    depth: u32,            // Nested begins not yet committed
+    changed: bool,         // update_config changed the config meanwhile
+    timers_changed: bool,
+    started: Instant,
+}
+
+// A transaction left open this long was never going to be committed
+const MAX_TRANSACTION_AGE: Duration = Duration::from_secs(10);
+
+impl Default for OverlayState {
+    fn default() -> Self {
+        Self {
+            hwnd: None,
+            config: CrosshairConfig::default(),
+            suppressed: false,
+            attached: None,
+            attach_hooks: Vec::new(),
+            thread_id: 0,
+            editing: false,
+            drag_start: None,
+            spread: SpreadState::default(),
+            spray: SprayState::default(),
+            last_frame: None,
+            contrast_color: None,
+            accent_color: None,
+            capture_visible: true,
+            presented: PresentedFrame::default(),
+            alpha_works: false,
+            color_keyed: false,
+            color_key_error: None,
+            transaction: None,
+        }
+    }
+}
+
+unsafe impl Send for OverlayState {}
+unsafe impl Sync for OverlayState {}
+
+/// Creates the overlay window on a dedicated thread that runs its message loop.
+/// Returns immediately; the window shows up once the thread has started.
+pub fn create_overlay_window() -> Result<()> {
+    Lazy::force(&SESSION_START);
+    let thread = std::thread::spawn(|| {
+        unsafe {
+            OVERLAY_STATE.lock().unwrap().thread_id = GetCurrentThreadId();
+            
+            let instance = GetModuleHandleW(None)?;
+            let class_name = OVERLAY_CLASS_NAME;
+            
+            let wc = WNDCLASSEXW {
+                cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+                style: CS_HREDRAW | CS_VREDRAW,
+                lpfnWndProc: Some(window_proc),
+                hInstance: instance.into(),
+                hCursor: LoadCursorW(None, IDC_ARROW)?,
+                lpszClassName: class_name,
+                hbrBackground: HBRUSH(std::ptr::null_mut()), // No background brush for transparency
+                ..Default::default()
+            };
+            
+            RegisterClassExW(&wc);
+            
+            detect_resolution();
+            
+            // Calculate window size based on the current crosshair config
+            // (the defaults on first start, the live config after a restart)
+            let (config, attached, editing, capture_visible) = {
+                let state = OVERLAY_STATE.lock().unwrap();
+                (state.config.clone(), state.attached, state.editing, state.capture_visible)
+            };
+            // Center the window on screen (or the attached window)
+            let (x, y, width, height) = window_bounds(&config, attached, editing);
+            
+            let hwnd = CreateWindowExW(
+                overlay_ex_style(editing),
+                class_name,
+                w!("Crosshair Overlay"),
+                WS_POPUP | WS_VISIBLE,
+                x,
+                y,
+                width,
+                height,
+                None,
+                None,
+                instance,
+                None,
+            )?;
+            
+            // Allow the TaskbarCreated broadcast through even when running elevated
+            let _ = ChangeWindowMessageFilterEx(hwnd, *TASKBAR_CREATED_MSG, MSGFLT_ALLOW, None);
+            // Lock, unlock and fast user switching arrive as WM_WTSSESSION_CHANGE
+            let _ = WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION);
+            
+            {
+                let mut state = OVERLAY_STATE.lock().unwrap();
+                state.hwnd = Some(hwnd);
+                install_attach_hooks(&mut state);
+            }
+            
+            // Layered windows stay invisible until their first UpdateLayeredWindow
+            render_overlay(hwnd);
+            let _ = ShowWindow(hwnd, SW_SHOW);
+            
+            apply_capture_visibility(hwnd, capture_visible);
+            arm_timers(hwnd, &config);
+            sync_input_hooks(hwnd, &config);
+            
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+            
+            OVERLAY_STATE.lock().unwrap().thread_id = 0;
+            Ok::<(), windows::core::Error>(())
+        }
+    });
+    
+    *OVERLAY_THREAD.lock().unwrap() = Some(thread);
+    
+    Ok(())
+}
+
+/// Closes the overlay window and waits for its thread to exit.
+pub fn destroy_overlay_window() -> Result<()> {
+    let (hwnd, thread_id) = {
+        let state = OVERLAY_STATE.lock().unwrap();
+        (state.hwnd, state.thread_id)
+    };
+    
+    unsafe {
+        match hwnd {
+            Some(hwnd) if IsWindow(hwnd).as_bool() => {
+                PostMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0))?;
+            }
+            // No live window, but the message loop may still be running
+            _ if thread_id != 0 => {
+                let _ = PostThreadMessageW(thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+            }
+            _ => {}
+        }
+    }
+    
+    let thread = OVERLAY_THREAD.lock().unwrap().take();
+    if let Some(thread) = thread {
+        let _ = thread.join();
+    }
+    
+    OVERLAY_STATE.lock().unwrap().hwnd = None;
+    Ok(())
+}
+
+/// Destroys the overlay window, joins its thread and unregisters the window
+/// class. Call once when the host application exits; safe to call repeatedly.
+pub fn shutdown_overlay() -> Result<()> {
+    destroy_overlay_window()?;
+    
+    unsafe {
+        let instance = GetModuleHandleW(None)?;
+        // Fails harmlessly if the class was never registered or is already gone
+        let _ = UnregisterClassW(OVERLAY_CLASS_NAME, instance);
+    }
+    
+    Ok(())
+}
+
+/// Tears down the overlay window and thread and creates fresh ones, keeping the
+/// current config. Used to recover when the window died or stopped drawing.
+pub fn restart_overlay() -> Result<()> {
+    destroy_overlay_window()?;
+    create_overlay_window()
+}
+
+/// Reports whether the overlay window and its thread are alive.
+pub fn get_overlay_status() -> OverlayStatus {
+    let state = OVERLAY_STATE.lock().unwrap();
+    let thread_running = OVERLAY_THREAD
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|thread| !thread.is_finished());
+    
+    let hwnd_alive = state.hwnd.is_some_and(|hwnd| unsafe { IsWindow(hwnd).as_bool() });
+    let window_visible = hwnd_alive
+        && state.hwnd.is_some_and(|hwnd| unsafe { IsWindowVisible(hwnd).as_bool() });
+    
+    OverlayStatus {
+        exists: state.hwnd.is_some(),
+        hwnd_alive,
+        visible: window_visible
+            && state.config.enabled
+            && !state.suppressed
+            && !attached_minimized(&state),
+        thread_running,
+        hwnd: state.hwnd.map(|hwnd| hwnd.0 as isize),
+        color_keyed: state.color_keyed,
+        color_key_error: state.color_key_error.clone(),
+    }
+}
+
+unsafe extern "system" fn window_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_PAINT => {
+            // Nothing is drawn through the paint DC; the frame goes out via UpdateLayeredWindow
+            let mut ps = PAINTSTRUCT::default();
+            let _ = BeginPaint(hwnd, &mut ps);
+            let _ = EndPaint(hwnd, &ps);
+            
+            render_overlay(hwnd);
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            let mut state = OVERLAY_STATE.lock().unwrap();
+            state.hwnd = None;
+            state.presented = PresentedFrame::default();
+            for hook in state.attach_hooks.drain(..) {
+                let _ = UnhookWinEvent(hook);
+            }
+            drop(state);
+            
+            let _ = KillTimer(hwnd, TOPMOST_TIMER_ID);
+            let _ = KillTimer(hwnd, ANIMATION_TIMER_ID);
+            let _ = KillTimer(hwnd, CONTRAST_TIMER_ID);
+            let _ = KillTimer(hwnd, OSD_TIMER_ID);
+            let _ = KillTimer(hwnd, WAKE_TIMER_ID);
+            let _ = KillTimer(hwnd, COUNTDOWN_TIMER_ID);
+            let _ = KillTimer(hwnd, KEYSTROKE_TIMER_ID);
+            let _ = KillTimer(hwnd, CLICK_TIMER_ID);
+            let _ = KillTimer(hwnd, CLOCK_TIMER_ID);
+            let _ = WTSUnRegisterSessionNotification(hwnd);
+            remove_input_hooks();
+            PostQuitMessage(0);
+            LRESULT(0)
+        }
+        WM_TIMER if wparam.0 == TOPMOST_TIMER_ID => {
+            // Other overlays (Discord, GeForce) can push us down the topmost band
+            let _ = SetWindowPos(
+                hwnd,
+                HWND_TOPMOST,
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+            );
+            LRESULT(0)
+        }
+        WM_TIMER if wparam.0 == ANIMATION_TIMER_ID => {
+            let _ = InvalidateRect(hwnd, None, false);
+            LRESULT(0)
+        }
+        WM_DISPLAYCHANGE => {
+            // A new resolution may switch to another override, and moves the center
+            detect_resolution();
+            refresh_window(hwnd);
+            DefWindowProcW(hwnd, msg, wparam, lparam)
+        }
+        WM_DWMCOLORIZATIONCOLORCHANGED | WM_SETTINGCHANGE => {
+            // Personalization changes arrive as one or both of these
+            OVERLAY_STATE.lock().unwrap().accent_color = None;
+            let _ = InvalidateRect(hwnd, None, false);
+            DefWindowProcW(hwnd, msg, wparam, lparam)
+        }
+        WM_TIMER if wparam.0 == CONTRAST_TIMER_ID => {
+            update_contrast_color(hwnd);
+            LRESULT(0)
+        }
+        WM_TIMER if wparam.0 == OSD_TIMER_ID => {
+            let mut osd = OSD.lock().unwrap();
+            let expired = osd.as_ref().is_none_or(|(_, started)| started.elapsed() >= OSD_DURATION);
+            if expired {
+                *osd = None;
+                drop(osd);
+                
+                // Shrink the window back to the crosshair
+                let _ = KillTimer(hwnd, OSD_TIMER_ID);
+                let state = OVERLAY_STATE.lock().unwrap();
+                let (x, y, width, height) = window_bounds(&state.config, state.attached, state.editing);
+                drop(state);
+                let _ = SetWindowPos(hwnd, HWND_TOPMOST, x, y, width, height, SWP_NOACTIVATE);
+            }
+            let _ = InvalidateRect(hwnd, None, false);
+            LRESULT(0)
+        }
+        WM_POWERBROADCAST
+            if matches!(wparam.0 as u32, PBT_APMRESUMEAUTOMATIC | PBT_APMRESUMESUSPEND) =>
+        {
+            refresh_after_wake(hwnd);
+            LRESULT(1)
+        }
+        WM_WTSSESSION_CHANGE
+            if matches!(wparam.0 as u32, WTS_SESSION_UNLOCK | WTS_CONSOLE_CONNECT | WTS_REMOTE_CONNECT) =>
+        {
+            refresh_after_wake(hwnd);
+            LRESULT(0)
+        }
+        WM_TIMER if wparam.0 == WAKE_TIMER_ID => {
+            let _ = KillTimer(hwnd, WAKE_TIMER_ID);
+            refresh_window(hwnd);
+            LRESULT(0)
+        }
+        WM_TIMER if matches!(wparam.0, KEYSTROKE_TIMER_ID | CLICK_TIMER_ID | CLOCK_TIMER_ID) => {
+            let _ = InvalidateRect(hwnd, None, false);
+            LRESULT(0)
+        }
+        WM_TIMER if wparam.0 == COUNTDOWN_TIMER_ID => {
+            let mut countdown = COUNTDOWN.lock().unwrap();
+            if countdown.as_ref().is_none_or(Countdown::finished) {
+                *countdown = None;
+                drop(countdown);
+                
+                let _ = KillTimer(hwnd, COUNTDOWN_TIMER_ID);
+                let state = OVERLAY_STATE.lock().unwrap();
+                let (x, y, width, height) = window_bounds(&state.config, state.attached, state.editing);
+                drop(state);
+                let _ = SetWindowPos(hwnd, HWND_TOPMOST, x, y, width, height, SWP_NOACTIVATE);
+            }
+            let _ = InvalidateRect(hwnd, None, false);
+            LRESULT(0)
+        }
+        WM_OVERLAY_COUNTDOWN => {
+            let state = OVERLAY_STATE.lock().unwrap();
+            let (x, y, width, height) = window_bounds(&state.config, state.attached, state.editing);
+            drop(state);
+            
+            let _ = SetWindowPos(hwnd, HWND_TOPMOST, x, y, width, height, SWP_NOACTIVATE);
+            SetTimer(hwnd, COUNTDOWN_TIMER_ID, COUNTDOWN_FRAME_MS, None);
+            let _ = InvalidateRect(hwnd, None, false);
+            LRESULT(0)
+        }
+        WM_OVERLAY_OSD => {
+            let state = OVERLAY_STATE.lock().unwrap();
+            let (x, y, width, height) = window_bounds(&state.config, state.attached, state.editing);
+            drop(state);
+            
+            let _ = SetWindowPos(hwnd, HWND_TOPMOST, x, y, width, height, SWP_NOACTIVATE);
+            SetTimer(hwnd, OSD_TIMER_ID, OSD_FRAME_MS, None);
+            let _ = InvalidateRect(hwnd, None, false);
+            LRESULT(0)
+        }
+        WM_OVERLAY_TIMERS => {
+            let config = OVERLAY_STATE.lock().unwrap().config.clone();
+            arm_timers(hwnd, &config);
+            sync_input_hooks(hwnd, &config);
+            LRESULT(0)
+        }
+        WM_OVERLAY_ATTACH => {
+            let mut state = OVERLAY_STATE.lock().unwrap();
+            install_attach_hooks(&mut state);
+            let (x, y, width, height) = window_bounds(&state.config, state.attached, state.editing);
+            drop(state);
+            
+            let _ = SetWindowPos(hwnd, HWND_TOPMOST, x, y, width, height, SWP_NOACTIVATE);
+            let _ = InvalidateRect(hwnd, None, false);
+            LRESULT(0)
+        }
+        WM_OVERLAY_EDIT => {
+            let mut state = OVERLAY_STATE.lock().unwrap();
+            let editing = state.editing;
+            if !editing && state.drag_start.take().is_some() {
+                let _ = ReleaseCapture();
+            }
+            let (x, y, width, height) = window_bounds(&state.config, state.attached, editing);
+            drop(state);
+            
+            SetWindowLongW(hwnd, GWL_EXSTYLE, overlay_ex_style(editing).0 as i32);
+            let _ = SetWindowPos(
+                hwnd,
+                HWND_TOPMOST,
+                x,
+                y,
+                width,
+                height,
+                SWP_NOACTIVATE | SWP_FRAMECHANGED,
+            );
+            let _ = InvalidateRect(hwnd, None, false);
+            LRESULT(0)
+        }
+        WM_SETCURSOR if OVERLAY_STATE.lock().unwrap().editing => {
+            if let Ok(cursor) = LoadCursorW(None, IDC_SIZEALL) {
+                SetCursor(cursor);
+            }
+            LRESULT(1)
+        }
+        WM_LBUTTONDOWN => {
+            let mut state = OVERLAY_STATE.lock().unwrap();
+            let mut cursor = POINT::default();
+            if state.editing && GetCursorPos(&mut cursor).is_ok() {
+                let position = (state.config.position_x, state.config.position_y);
+                state.drag_start = Some((cursor, position.0, position.1));
+                drop(state);
+                SetCapture(hwnd);
+            }
+            LRESULT(0)
+        }
+        WM_MOUSEMOVE => {
+            let mut state = OVERLAY_STATE.lock().unwrap();
+            let mut cursor = POINT::default();
+            if let Some((start, start_x, start_y)) = state.drag_start {
+                if GetCursorPos(&mut cursor).is_ok() {
+                    state.config.position_x = start_x + (cursor.x - start.x) as f32;
+                    state.config.position_y = start_y + (cursor.y - start.y) as f32;
+                    let (x, y, width, height) = window_bounds(&state.config, state.attached, true);
+                    drop(state);
+                    
+                    let _ = SetWindowPos(hwnd, HWND_TOPMOST, x, y, width, height, SWP_NOACTIVATE);
+                    let _ = InvalidateRect(hwnd, None, false);
+                }
+            }
+            LRESULT(0)
+        }
+        WM_LBUTTONUP => {
+            let mut state = OVERLAY_STATE.lock().unwrap();
+            if state.drag_start.take().is_some() {
+                let (position_x, position_y) = (state.config.position_x, state.config.position_y);
+                drop(state);
+                let _ = ReleaseCapture();
+                
+                if let Some(handler) = POSITION_CHANGED_HANDLER.lock().unwrap().as_ref() {
+                    handler(position_x, position_y);
+                }
+            }
+            LRESULT(0)
+        }
+        _ if msg != 0 && msg == *TASKBAR_CREATED_MSG => {
+            if let Some(handler) = TASKBAR_CREATED_HANDLER.lock().unwrap().as_ref() {
+                handler();
+            }
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+// After sleep, unlock or a session switch the window can come back black or
+// below other topmost windows
+fn refresh_after_wake(hwnd: HWND) {
+    refresh_window(hwnd);
+    unsafe {
+        SetTimer(hwnd, WAKE_TIMER_ID, WAKE_RECHECK_MS, None);
+    }
+}
+
+// Re-asserts topmost, position and capture visibility and sends a full frame
+// instead of a diff against what may no longer be on screen
+fn refresh_window(hwnd: HWND) {
+    let mut state = OVERLAY_STATE.lock().unwrap();
+    state.presented = PresentedFrame::default();
+    let (x, y, width, height) = window_bounds(&state.config, state.attached, state.editing);
+    let capture_visible = state.capture_visible;
+    drop(state);
+    
+    unsafe {
+        let _ = SetWindowPos(hwnd, HWND_TOPMOST, x, y, width, height, SWP_NOACTIVATE);
+    }
+    apply_capture_visibility(hwnd, capture_visible);
+    unsafe {
+        let _ = InvalidateRect(hwnd, None, false);
+    }
+}
+
+// Hides the window from screen capture (OBS, screenshots, Game Bar) unless
+// `visible`. Needs Windows 10 2004; older versions keep it visible.
+fn apply_capture_visibility(hwnd: HWND, visible: bool) {
+    let affinity = if visible {
+        WDA_NONE
+    } else {
+        WDA_EXCLUDEFROMCAPTURE
+    };
+    unsafe {
+        let _ = SetWindowDisplayAffinity(hwnd, affinity);
+    }
+}
+
+// Starts, restarts or stops the topmost watchdog and the animation frame timer
+// to match TOPMOST_INTERVAL_MS and `config`. Must run on the overlay thread, like sync_input_hooks.
+fn arm_timers(hwnd: HWND, config: &CrosshairConfig) {
+    unsafe {
+        let _ = KillTimer(hwnd, TOPMOST_TIMER_ID);
+        let topmost_interval_ms = TOPMOST_INTERVAL_MS.load(Ordering::Relaxed);
+        if topmost_interval_ms > 0 {
+            SetTimer(hwnd, TOPMOST_TIMER_ID, topmost_interval_ms, None);
+        }
+        
+        let _ = KillTimer(hwnd, CONTRAST_TIMER_ID);
+        if config.auto_contrast.enabled {
+            SetTimer(hwnd, CONTRAST_TIMER_ID, config.auto_contrast.interval_ms.max(50), None);
+        }
+        
+        let _ = KillTimer(hwnd, ANIMATION_TIMER_ID);
+        if needs_frames(config) {
+            SetTimer(hwnd, ANIMATION_TIMER_ID, 1000 / frame_rate(config), None);
+        }
+        
+        let _ = KillTimer(hwnd, CLOCK_TIMER_ID);
+        if CLOCK.lock().unwrap().is_some() {
+            SetTimer(hwnd, CLOCK_TIMER_ID, CLOCK_FRAME_MS, None);
+        }
+    }
+}
+
+// True when the overlay thread has to re-arm its timers or input hooks after
+// switching configs
+fn timers_changed(old: &CrosshairConfig, new: &CrosshairConfig) -> bool {
+    frame_rate(old) != frame_rate(new)
+        || old.auto_contrast.enabled != new.auto_contrast.enabled
+        || old.auto_contrast.interval_ms != new.auto_contrast.interval_ms
+        || needs_frames(old) != needs_frames(new)
+        || wants_mouse_hook(old) != wants_mouse_hook(new)
+        || wants_keyboard_hook(old) != wants_keyboard_hook(new)
+}
+
+// Side length of the square overlay window for `config`, large enough for the
+// ADS crosshair and the widest dynamic spread too
+fn window_size(config: &CrosshairConfig) -> i32 {
+    let config = &*for_current_resolution(config);
+    let mut size = crosshair_size(config);
+    if let Some(secondary) = config.secondary_config.as_deref() {
+        size = size.max(crosshair_size(secondary));
+    }
+    let spray = &config.spray_pattern;
+    size = size.max(spray.extent() * 2 + spray.thickness * 2 + 20);
+    size + max_spread(config) * 2
+}
+
+// Twice the distance from the center to the farthest pixel the crosshair can
+// draw, plus padding. Distances from the center don't change under rotation,
+// so the window never has to grow when the crosshair turns.
+fn crosshair_size(config: &CrosshairConfig) -> i32 {
+    // Leave room for a pulsing crosshair at its largest
+    let scale = match config.animation {
+        Animation::Pulse { .. } => 1.0 + PULSE_AMPLITUDE,
+        _ => 1.0,
+    };
+    
+    // Strokes stick out by half their pen width, and outlines widen the pen
+    let outline = if config.show_outline { config.outline_thickness.max(0) * 2 } else { 0 };
+    let stroke = (config.thickness.max(1.0) + outline as f32) / 2.0;
+    let mut reach = shape_reach(config) + stroke;
+    if config.show_dot {
+        reach = reach.max(config.dot_size + stroke);
+    }
+    
+    let extent = (reach * scale).ceil() as i32;
+    let text_extent = config.texts.iter().map(OverlayText::extent).max().unwrap_or(0);
+    let shadow_extent = if config.shadow_enabled {
+        config.shadow_offset.abs() + config.shadow_blur.max(0)
+    } else {
+        0
+    };
+    (extent.max(text_extent) + shadow_extent) * 2 + 20 // Add padding
+}
+
+// Distance from the center to the farthest point of the style's lines, not
+// counting their width
+fn shape_reach(config: &CrosshairConfig) -> f32 {
+    let distance = |x: f32, y: f32| x.hypot(y);
+    let gap = config.gap;
+    let size = config.size;
+    let arms = gap + config.arms.max_length(size);
+    
+    match config.style {
+        CrosshairStyle::Classic | CrosshairStyle::X => arms,
+        CrosshairStyle::Dot => 0.0,
+        CrosshairStyle::Circle => (gap + size).max(arms),
+        // The corners of the outline are the farthest points
+        CrosshairStyle::Square => (gap + size) * std::f32::consts::SQRT_2,
+        CrosshairStyle::TShape => {
+            let bar = distance(config.t_length as f32, gap + size);
+            bar.max(gap + size)
+        }
+        CrosshairStyle::Custom => config.elements.iter().map(Element::extent).fold(0.0, f32::max),
+        // Sized by is_full_screen instead
+        CrosshairStyle::Guides => arms,
+        CrosshairStyle::Chevron => gap + config.chevron.length as f32,
+        CrosshairStyle::Diamond => {
+            let outline = config.diamond.size as f32;
+            if config.diamond.inner_lines {
+                outline.max(arms)
+            } else {
+                outline
+            }
+        }
+        CrosshairStyle::Brackets => config.brackets.spread as f32 * std::f32::consts::SQRT_2,
+        CrosshairStyle::DoubleCircle => {
+            let circles = config.double_circle.inner_radius.max(config.double_circle.outer_radius) as f32;
+            if config.double_circle.cross_lines {
+                circles.max(arms)
+            } else {
+                circles
+            }
+        }
+        CrosshairStyle::MilDot => {
+            let mil_dot = &config.mil_dot;
+            let lines = (mil_dot.spacing * (mil_dot.count.max(0) + 1)) as f32;
+            let last_mark = (mil_dot.spacing * mil_dot.count.max(0)) as f32;
+            lines.max(distance(last_mark, mil_dot.marker_size as f32))
+        }
+        CrosshairStyle::Triangle => {
+            let height = config.triangle.size as f32;
+            distance(height / 3f32.sqrt(), gap + height)
+        }
+        // Labels sit past the tick ends and can be a few digits wide
+        CrosshairStyle::Ruler => {
+            let ruler = &config.ruler;
+            let label = (ruler.label_size * 3) as f32;
+            distance(ruler.length as f32 + label, (ruler.tick_size * 2) as f32 + label)
+        }
+        // Images aren't rotated, so half their longer side is enough
+        CrosshairStyle::Image { ref path, scale, .. } => match load_image(path) {
+            Ok(image) => image.width.max(image.height) as f32 * scale / 2.0,
+            Err(_) => arms,
+        },
+    }
+}
+
+// Guide lines, the grid, the keystroke display, the clock and rings at the
+// cursor need a window covering the whole target area
+fn is_full_screen(config: &CrosshairConfig) -> bool {
+    GRID.lock().unwrap().is_some()
+        || KEYSTROKES.lock().unwrap().is_some()
+        || CLOCK.lock().unwrap().is_some()
+        || CLICKS
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|clicks| clicks.origin == ClickOrigin::Cursor)
+        || matches!(config.style, CrosshairStyle::Guides)
+        || config
+            .secondary_config
+            .as_deref()
+            .is_some_and(|secondary| matches!(secondary.style, CrosshairStyle::Guides))
+}
+
+// Screen area (x, y, width, height) the crosshair is centered on: the attached
+// window's client area when there is one, otherwise the selected monitor
+fn target_area(attached: Option<HWND>) -> (i32, i32, i32, i32) {
+    unsafe {
+        if let Some(target) = attached {
+            let mut client = RECT::default();
+            if IsWindow(target).as_bool() && GetClientRect(target, &mut client).is_ok() {
+                let mut origin = POINT::default();
+                let _ = ClientToScreen(target, &mut origin);
+                return (origin.x, origin.y, client.right - client.left, client.bottom - client.top);
+            }
+        }
+        
+        // A monitor that was unplugged falls back to the primary one
+        let monitor = *SELECTED_MONITOR.lock().unwrap();
+        if let Some(area) = monitor.and_then(monitor_area) {
+            return area;
+        }
+        
+        (0, 0, GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN))
+    }
+}
+
+fn detect_resolution() {
+    let (_, _, width, height) = target_area(None);
+    *RESOLUTION.lock().unwrap() = (width, height);
+}
+
+// `config` as drawn on the current display, with its resolution override applied
+fn for_current_resolution(config: &CrosshairConfig) -> Cow<'_, CrosshairConfig> {
+    let (width, height) = *RESOLUTION.lock().unwrap();
+    match resolve_resolution(config, width, height) {
+        Some(resolved) => Cow::Owned(resolved),
+        None => Cow::Borrowed(config),
+    }
+}
+
+// Screen rectangle (x, y, width, height) of the overlay window: a square
+// centered on the crosshair, or the whole target area in sniper mode. Edit
+// mode always uses a square so the rest of the screen stays usable.
+fn window_bounds(
+    config: &CrosshairConfig,
+    attached: Option<HWND>,
+    editing: bool,
+) -> (i32, i32, i32, i32) {
+    let (x, y, width, height) = target_area(attached);
+    if is_full_screen(config) && !editing {
+        return (x, y, width, height);
+    }
+    
+    let mut size = window_size(config);
+    if editing {
+        size = size.max(EDIT_MODE_MIN_SIZE);
+    }
+    if OSD.lock().unwrap().is_some() {
+        size = size.max(OSD_MIN_SIZE);
+    }
+    if COUNTDOWN.lock().unwrap().is_some() {
+        size = size.max(COUNTDOWN_MIN_SIZE);
+    }
+    if let Some(clicks) = CLICKS.lock().unwrap().as_ref() {
+        size = size.max((clicks.radius + clicks.thickness) * 2 + 4);
+    }
+    let (anchor_x, anchor_y) = config.anchor.point(width, height);
+    let (offset_x, offset_y) = pixel_offset(config);
+    let center_x = x + anchor_x + offset_x;
+    let center_y = y + anchor_y + offset_y;
+    (center_x - size / 2, center_y - size / 2, size, size)
+}
+
+// Offset from the window center to the crosshair center. Only a full-screen
+// window needs it; square windows are already placed around the crosshair.
+fn center_shift(config: &CrosshairConfig, editing: bool, client: &RECT) -> (i32, i32) {
+    if !is_full_screen(config) || editing {
+        return (0, 0);
+    }
+    
+    let (width, height) = (client.right - client.left, client.bottom - client.top);
+    let (anchor_x, anchor_y) = config.anchor.point(width, height);
+    let (offset_x, offset_y) = pixel_offset(config);
+    (
+        anchor_x + offset_x - width / 2,
+        anchor_y + offset_y - height / 2,
+    )
+}
+
+// GDI only draws on whole pixels, so fractional positions are rounded here;
+// the geometry module keeps them exact
+fn pixel_offset(config: &CrosshairConfig) -> (i32, i32) {
+    (pixels(config.position_x), pixels(config.position_y))
+}
+
+// A fractional size rounded to whole pixels for GDI
+fn pixels(value: f32) -> i32 {
+    value.round() as i32
+}
+
+// Click-through everywhere, except in edit mode where the crosshair can be dragged
+fn overlay_ex_style(editing: bool) -> WINDOW_EX_STYLE {
+    let style = WS_EX_TOPMOST | WS_EX_LAYERED | WS_EX_TOOLWINDOW;
+    if editing {
+        style
+    } else {
+        style | WS_EX_TRANSPARENT
+    }
+}
+
+// Samples the screen behind the crosshair and repaints when auto-contrast
+// picks a different color
+fn update_contrast_color(hwnd: HWND) {
+    let state = OVERLAY_STATE.lock().unwrap();
+    let config = state.config.clone();
+    let editing = state.editing;
+    drop(state);
+    
+    let settings = &config.auto_contrast;
+    let swapped = if settings.enabled {
+        let mut window = RECT::default();
+        unsafe {
+            let _ = GetWindowRect(hwnd, &mut window);
+        }
+        let client = RECT {
+            left: 0,
+            top: 0,
+            right: window.right - window.left,
+            bottom: window.bottom - window.top,
+        };
+        let (shift_x, shift_y) = center_shift(&config, editing, &client);
+        let center_x = window.left + client.right / 2 + shift_x;
+        let center_y = window.top + client.bottom / 2 + shift_y;
+        let radius = settings.sample_radius.clamp(1, 64);
+        
+        let Some(pixels) = sample_screen(center_x - radius, center_y - radius, radius * 2, radius * 2) else {
+            return;
+        };
+        let color = contrast_color(settings, config.color, average_rgb(&pixels));
+        (color != config.color).then_some(color)
+    } else {
+        None
+    };
+    
+    let mut state = OVERLAY_STATE.lock().unwrap();
+    if state.contrast_color != swapped {
+        state.contrast_color = swapped;
+        drop(state);
+        unsafe {
+            let _ = InvalidateRect(hwnd, None, false);
+        }
+    }
+}
+
+fn attached_minimized(state: &OverlayState) -> bool {
+    state.attached.is_some_and(|target| unsafe { IsIconic(target).as_bool() })
+}
+
+// Replaces the WinEvent hooks with ones watching the currently attached window
+fn install_attach_hooks(state: &mut OverlayState) {
+    unsafe {
+        for hook in state.attach_hooks.drain(..) {
+            let _ = UnhookWinEvent(hook);
+        }
+        
+        let Some(target) = state.attached else {
+            return;
+        };
+        
+        let mut pid = 0u32;
+        let thread_id = GetWindowThreadProcessId(target, Some(&mut pid as *mut u32));
+        
+        for (event_min, event_max) in [
+            (EVENT_OBJECT_LOCATIONCHANGE, EVENT_OBJECT_LOCATIONCHANGE),
+            (EVENT_SYSTEM_MINIMIZESTART, EVENT_SYSTEM_MINIMIZEEND),
+            (EVENT_OBJECT_DESTROY, EVENT_OBJECT_DESTROY),
+        ] {
+            let hook = SetWinEventHook(
+                event_min,
+                event_max,
+                None,
+                Some(attached_window_event),
+                pid,
+                thread_id,
+                WINEVENT_OUTOFCONTEXT,
+            );
+            if !hook.0.is_null() {
+                state.attach_hooks.push(hook);
+            }
+        }
+    }
+}
+
+unsafe extern "system" fn attached_window_event(
+    _hook: HWINEVENTHOOK,
+    event: u32,
+    hwnd: HWND,
+    id_object: i32,
+    _id_child: i32,
+    _thread_id: u32,
+    _time: u32,
+) {
+    let mut state = OVERLAY_STATE.lock().unwrap();
+    if id_object != OBJID_WINDOW.0 || state.attached != Some(hwnd) {
+        return;
+    }
+    let Some(overlay_hwnd) = state.hwnd else {
+        return;
+    };
+    
+    // The game closed, so fall back to centering on the screen
+    if event == EVENT_OBJECT_DESTROY {
+        state.attached = None;
+        install_attach_hooks(&mut state);
+    }
+    
+    let (x, y, width, height) = window_bounds(&state.config, state.attached, state.editing);
+    drop(state);
+    
+    let _ = SetWindowPos(overlay_hwnd, HWND_TOPMOST, x, y, width, height, SWP_NOACTIVATE);
+    let _ = InvalidateRect(overlay_hwnd, None, false);
+}
+
+// Draws the current frame and pushes it to the layered window
+fn render_overlay(hwnd: HWND) {
+    let started = Instant::now();
+    unsafe {
+        // Get window client area
+        let mut rect = RECT::default();
+        let _ = GetClientRect(hwnd, &mut rect);
+        let Some(mut canvas) = Canvas::new(rect.right - rect.left, rect.bottom - rect.top) else {
+            return;
+        };
+        
+        let mut guard = OVERLAY_STATE.lock().unwrap();
+        let state = &mut *guard;
+        
+        if state.editing {
+            // Only pixels with some alpha take clicks, so give edit mode a backdrop
+            canvas.layer(EDIT_MODE_BACKDROP_OPACITY, |hdc| {
+                let backdrop = CreateSolidBrush(COLORREF(rgb_to_colorref(EDIT_MODE_BACKGROUND)));
+                FillRect(hdc, &rect, backdrop);
+                let _ = DeleteObject(backdrop);
+            });
+        }
+        
+        let now = Instant::now();
+        let dt = state
+            .last_frame
+            .map_or(0.0, |last| (now - last).as_secs_f32().min(0.1));
+        state.last_frame = Some(now);
+        let input = input_state();
+        let extra_gap = state.spread.update(&state.config, input, dt);
+        let shot = state.spray.update(&state.config.spray_pattern, input, dt);
+        let (shift_x, shift_y) = center_shift(&state.config, state.editing, &rect);
+        
+        // While aiming the secondary config takes over; a disabled one hides the crosshair
+        let active = match state.config.secondary_config.as_deref() {
+            Some(secondary) if input.aiming => secondary,
+            _ => &state.config,
+        };
+        let active = for_current_resolution(active);
+        
+        // Always show the crosshair being positioned, even if it's hidden otherwise
+        let visible = state.config.enabled
+            && active.enabled
+            && !state.suppressed
+            && !attached_minimized(state);
+        
+        // The grid is left out of edit mode, whose window only covers the crosshair
+        if let Some(grid) = GRID.lock().unwrap().as_ref().filter(|_| !state.editing) {
+            canvas.layer(color_alpha(grid.color), |hdc| draw_grid(hdc, grid, &rect));
+        }
+        
+        if visible || state.editing {
+            // The trace sits under the crosshair and ignores blinking
+            let config = &state.config;
+            let center_x = (rect.right - rect.left) / 2 + shift_x;
+            let center_y = (rect.bottom - rect.top) / 2 + shift_y;
+            canvas.layer(color_alpha(config.spray_pattern.color), |hdc| {
+                draw_spray_pattern(hdc, &config.spray_pattern, center_x, center_y, config.color, shot);
+            });
+            
+            let elapsed = ANIMATION_START.elapsed().as_secs_f32();
+            if let Some(mut frame) = animate(&active, elapsed) {
+                frame.gap += extra_gap as f32;
+                frame.position_x = shift_x as f32;
+                frame.position_y = shift_y as f32;
+                if frame.color_mode == ColorMode::Accent {
+                    if state.accent_color.is_none() {
+                        state.accent_color = accent_color();
+                    }
+                    if let Some(accent) = state.accent_color {
+                        frame.color = color::from_rgba(accent, color::alpha(frame.color));
+                    }
+                }
+                if let Some(color) = state.contrast_color.filter(|_| state.config.auto_contrast.enabled) {
+                    frame.color = color;
+                }
+                if frame.blend_mode == BlendMode::Invert {
+                    let mut window = RECT::default();
+                    let _ = GetWindowRect(hwnd, &mut window);
+                    canvas.capture_backdrop(window.left, window.top);
+                }
+                draw_crosshair(&mut canvas, &rect, &frame, elapsed);
+            }
+        }
+        
+        if state.editing {
+            let (position_x, position_y) = (state.config.position_x, state.config.position_y);
+            canvas.layer(1.0, |hdc| draw_position_label(hdc, position_x, position_y));
+        }
+        
+        if let Some((message, started)) = OSD.lock().unwrap().as_ref() {
+            let center_x = (rect.right - rect.left) / 2 + shift_x;
+            let center_y = (rect.bottom - rect.top) / 2 + shift_y;
+            draw_osd(&mut canvas, message, started.elapsed(), center_x, center_y);
+        }
+        
+        if let Some(countdown) = COUNTDOWN.lock().unwrap().as_ref() {
+            let center_x = (rect.right - rect.left) / 2 + shift_x;
+            let center_y = (rect.bottom - rect.top) / 2 + shift_y;
+            draw_countdown(&mut canvas, countdown, center_x, center_y);
+        }
+        
+        if let Some(clicks) = CLICKS.lock().unwrap().as_ref() {
+            let ripples = recent_clicks(clicks);
+            // Clicks are in screen coordinates
+            let mut window = RECT::default();
+            let _ = GetWindowRect(hwnd, &mut window);
+            let center_x = (rect.right - rect.left) / 2 + shift_x;
+            let center_y = (rect.bottom - rect.top) / 2 + shift_y;
+            for ripple in &ripples {
+                let (x, y) = match clicks.origin {
+                    ClickOrigin::Crosshair => (center_x, center_y),
+                    ClickOrigin::Cursor => (ripple.x - window.left, ripple.y - window.top),
+                };
+                let radius = (clicks.radius as f32 * ripple.progress).round() as i32;
+                let color = clicks.color(ripple.button);
+                canvas.layer((1.0 - ripple.progress) * color_alpha(color), |hdc| {
+                    with_pen(hdc, clicks.thickness, color, &CapStyle::Round, || draw_ring(hdc, x, y, radius));
+                });
+            }
+            if ripples.is_empty() {
+                let _ = KillTimer(hwnd, CLICK_TIMER_ID);
+            } else {
+                SetTimer(hwnd, CLICK_TIMER_ID, CLICK_FRAME_MS, None);
+            }
+        }
+        
+        if let Some(clock) = CLOCK.lock().unwrap().as_ref().filter(|_| !state.editing) {
+            let now = GetLocalTime();
+            let lines = clock.lines(
+                now.wHour as u32,
+                now.wMinute as u32,
+                now.wSecond as u32,
+                SESSION_START.elapsed(),
+            );
+            canvas.layer(color_alpha(clock.color), |hdc| draw_clock(hdc, clock, &lines, &rect));
+        }
+        
+        // Like the grid, keystrokes need the full-screen window edit mode doesn't have
+        if let Some(display) = KEYSTROKES.lock().unwrap().as_ref().filter(|_| !state.editing) {
+            let keys = recent_keys(display);
+            draw_keystrokes(&mut canvas, display, &keys, &rect);
+            if keys.iter().any(|&(_, opacity)| opacity < 1.0) {
+                SetTimer(hwnd, KEYSTROKE_TIMER_ID, KEYSTROKE_FRAME_MS, None);
+            } else {
+                let _ = KillTimer(hwnd, KEYSTROKE_TIMER_ID);
+            }
+        }
+        
+        // Frames often come out the same, e.g. animation ticks while hidden
+        let opacity = state.config.opacity;
+        let dirty = canvas.dirty_rect(&mut state.presented, opacity);
+        let color_key = if state.color_keyed {
+            // The config was accepted before the fallback, so it may not fit any key
+            let key = match pick_color_key(&state.config) {
+                Ok(key) => {
+                    state.color_key_error = None;
+                    key
+                }
+                Err(e) => {
+                    if state.color_key_error.as_ref() != Some(&e) {
+                        warn!("Crosshair drawn with holes: {}", e);
+                    }
+                    state.color_key_error = Some(e);
+                    DEFAULT_COLOR_KEY
+                }
+            };
+            Some(key)
+        } else {
+            None
+        };
+        drop(guard);
+        
+        if let Some(dirty) = dirty {
+            let presented = match color_key {
+                Some(key) => canvas.present_color_keyed(hwnd, opacity, key),
+                None => canvas.present(hwnd, opacity, &dirty),
+            };
+            let mut state = OVERLAY_STATE.lock().unwrap();
+            match presented {
+                Ok(()) => state.alpha_works |= color_key.is_none(),
+                Err(e) => {
+                    error!("Failed to update overlay window: {}", e);
+                    // The window didn't get this frame, so send all of the next one
+                    state.presented = PresentedFrame::default();
+                    // Only give up on per-pixel alpha if it never worked, not on a passing failure
+                    if color_key.is_none() && !state.alpha_works {
+                        warn!("Per-pixel alpha is unavailable, falling back to a color key");
+                        state.color_keyed = true;
+                        let _ = InvalidateRect(hwnd, None, false);
+                    }
+                }
+            }
+        }
+    }
+    record_paint(started.elapsed());
+}
+
+// Opacity carried in the high byte of a color, as 0.0-1.0
+fn color_alpha(color: u32) -> f32 {
+    color::alpha(color) as f32 / 255.0
+}
+
+// Solid geometric pen of `color` (0xRRGGBB) with the given line ends
+unsafe fn create_pen(width: i32, color: u32, cap: &CapStyle) -> HPEN {
+    let (end_cap, join) = match cap {
+        CapStyle::Flat => (PS_ENDCAP_FLAT, PS_JOIN_MITER),
+        CapStyle::Round => (PS_ENDCAP_ROUND, PS_JOIN_ROUND),
+        CapStyle::Square => (PS_ENDCAP_SQUARE, PS_JOIN_MITER),
+    };
+    let brush = LOGBRUSH {
+        lbStyle: BS_SOLID,
+        lbColor: COLORREF(rgb_to_colorref(color)),
+        lbHatch: 0,
+    };
+    
+    ExtCreatePen(
+        PS_GEOMETRIC | PS_SOLID | end_cap | join,
+        width.max(1) as u32,
+        &brush,
+        None,
+    )
+}
+
+// Runs `draw` with a solid pen of `color` (0xRRGGBB) selected
+unsafe fn with_pen(hdc: HDC, width: i32, color: u32, cap: &CapStyle, draw: impl FnOnce()) {
+    let pen = create_pen(width, color, cap);
+    let old_pen = SelectObject(hdc, pen);
+    
+    draw();
+    
+    SelectObject(hdc, old_pen);
+    let _ = DeleteObject(pen);
+}
+
+// Each element gets its own layer so it can have its own opacity. `elapsed`
+// picks the frame of animated images.
+fn draw_crosshair(canvas: &mut Canvas, rect: &RECT, config: &CrosshairConfig, elapsed: f32) {
+    let (offset_x, offset_y) = pixel_offset(config);
+    let center_x = (rect.right - rect.left) / 2 + offset_x;
+    let center_y = (rect.bottom - rect.top) / 2 + offset_y;
+    let opacity = &config.element_opacity;
+    
+    // Draw shadow if enabled
+    if config.shadow_enabled {
+        let shadow_x = center_x + config.shadow_offset;
+        let shadow_y = center_y + config.shadow_offset;
+        
+        let shadow_opacity = opacity.shadow * color_alpha(config.shadow_color);
+        let draw_shadow = |hdc: HDC| unsafe {
+            with_pen(hdc, pixels(config.thickness), config.shadow_color, &config.cap_style, || {
+                apply_dead_zone(hdc, rect, shadow_x, shadow_y, config.dead_zone_radius);
+                let pass = PlainPass {
+                    color: config.shadow_color,
+                    widen: 0,
+                };
+                draw_crosshair_shape(hdc, shadow_x, shadow_y, config, Some(pass));
+            });
+        };
+        
+        if config.shadow_blur > 0 {
+            canvas.blurred_layer(shadow_opacity, config.shadow_color, config.shadow_blur, draw_shadow);
+        } else {
+            canvas.layer(shadow_opacity, draw_shadow);
+        }
+    }
+    
+    // Draw outline if enabled. Every stroke is redrawn wider in the outline
+    // color underneath the main pass, the dot included.
+    if config.show_outline {
+        let widen = config.outline_thickness * 2;
+        let pass = PlainPass {
+            color: config.outline_color,
+            widen,
+        };
+        
+        canvas.layer(opacity.outline * color_alpha(config.outline_color), |hdc| unsafe {
+            with_pen(hdc, pixels(config.thickness) + widen, config.outline_color, &config.cap_style, || {
+                apply_dead_zone(hdc, rect, center_x, center_y, config.dead_zone_radius);
+                draw_crosshair_shape(hdc, center_x, center_y, config, Some(pass));
+                
+                if config.show_dot {
+                    SelectClipRgn(hdc, None);
+                    draw_dot(hdc, center_x, center_y, pixels(config.dot_size));
+                }
+            });
+        });
+    }
+    
+    // Draw main crosshair shape based on style. Images carry their own alpha
+    // and are blended straight into the frame.
+    if let CrosshairStyle::Image { path, scale, .. } = &config.style {
+        match load_image(path) {
+            Ok(image) => {
+                let frame = image.frame_at(elapsed);
+                canvas.image(&image, frame, center_x, center_y, *scale, opacity.lines);
+            }
+            Err(e) => warn!("Failed to load crosshair image: {}", e),
+        }
+    } else {
+        let draw_lines = |hdc: HDC| unsafe {
+            with_pen(hdc, pixels(config.thickness), config.color, &config.cap_style, || {
+                apply_dead_zone(hdc, rect, center_x, center_y, config.dead_zone_radius);
+                draw_crosshair_shape(hdc, center_x, center_y, config, None);
+            });
+        };
+        match config.blend_mode {
+            BlendMode::Normal => canvas.layer(opacity.lines * color_alpha(config.color), draw_lines),
+            BlendMode::Invert => canvas.inverted_layer(opacity.lines, draw_lines),
+        }
+    }
+    
+    // Draw center dot if enabled. The dead zone only masks lines and shapes,
+    // never the dot itself.
+    if config.show_dot {
+        let dot_color = config.dot_color.unwrap_or(config.color);
+        
+        let draw_center_dot = |hdc: HDC| unsafe {
+            with_pen(hdc, pixels(config.thickness), dot_color, &config.cap_style, || {
+                draw_dot(hdc, center_x, center_y, pixels(config.dot_size));
+            });
+        };
+        match config.blend_mode {
+            BlendMode::Normal => canvas.layer(opacity.dot * color_alpha(dot_color), draw_center_dot),
+            BlendMode::Invert => canvas.inverted_layer(opacity.dot, draw_center_dot),
+        }
+    }
+    
+    for text in config.texts.iter().filter(|text| !text.text.is_empty()) {
+        canvas.layer(opacity.lines * color_alpha(text.color), |hdc| {
+            draw_overlay_text(hdc, text, center_x, center_y);
+        });
+    }
+}
+
+// Filled circle in the selected pen's color; the pen width adds to the radius
+unsafe fn draw_dot(hdc: HDC, center_x: i32, center_y: i32, radius: i32) {
+    let brush = CreateSolidBrush(selected_pen_color(hdc));
+    let old_brush = SelectObject(hdc, brush);
+    
+    let _ = Ellipse(
+        hdc,
+        center_x - radius,
+        center_y - radius,
+        center_x + radius,
+        center_y + radius,
+    );
+    
+    SelectObject(hdc, old_brush);
+    let _ = DeleteObject(brush);
+}
+
+// Text is drawn without antialiasing: smoothed edges would be blended against
+// the layer's black background and leave a dark fringe
+fn draw_overlay_text(hdc: HDC, text: &OverlayText, center_x: i32, center_y: i32) {
+    let x = center_x + text.offset_x;
+    let y = center_y + text.offset_y;
+    draw_aligned_text(hdc, text, x, y, DT_CENTER | DT_VCENTER);
+}
+
+// Draws `text` (its offsets ignored) aligned to (x, y): `align` picks which
+// side or center of the text lands on the point
+fn draw_aligned_text(hdc: HDC, text: &OverlayText, x: i32, y: i32, align: DRAW_TEXT_FORMAT) {
+    unsafe {
+        let mut face_name = [0u16; 32];
+        for (slot, unit) in face_name.iter_mut().zip(text.font.encode_utf16().take(31)) {
+            *slot = unit;
+        }
+        let font = CreateFontIndirectW(&LOGFONTW {
+            lfHeight: -text.size.max(1),
+            lfWeight: if text.bold { FW_BOLD.0 as i32 } else { FW_NORMAL.0 as i32 },
+            lfCharSet: DEFAULT_CHARSET,
+            lfQuality: NONANTIALIASED_QUALITY,
+            lfFaceName: face_name,
+            ..Default::default()
+        });
+        let old_font = SelectObject(hdc, font);
+        
+        SetBkMode(hdc, TRANSPARENT);
+        SetTextColor(hdc, COLORREF(rgb_to_colorref(text.color)));
+        
+        // A zero-size rect with DT_NOCLIP puts the text's aligned edge on its point
+        let mut rect = RECT {
+            left: x,
+            top: y,
+            right: x,
+            bottom: y,
+        };
+        let mut label: Vec<u16> = text.text.encode_utf16().collect();
+        DrawTextW(
+            hdc,
+            &mut label,
+            &mut rect,
+            align | DT_SINGLELINE | DT_NOCLIP,
+        );
+        
+        SelectObject(hdc, old_font);
+        let _ = DeleteObject(font);
+    }
+}
+
+// One line of text per entry, stacked from `clock.corner` of `rect`
+fn draw_clock(hdc: HDC, clock: &ClockDisplay, lines: &[String], rect: &RECT) {
+    let line_height = clock.size.max(1) + 2;
+    let (x, align_x) = match clock.corner {
+        Corner::TopLeft | Corner::BottomLeft => (rect.left + clock.margin, DT_LEFT),
+        Corner::TopRight | Corner::BottomRight => (rect.right - clock.margin, DT_RIGHT),
+    };
+    let top = match clock.corner {
+        Corner::TopLeft | Corner::TopRight => rect.top + clock.margin,
+        Corner::BottomLeft | Corner::BottomRight => rect.bottom - clock.margin - line_height * lines.len() as i32,
+    };
+    
+    for (index, line) in lines.iter().enumerate() {
+        let text = OverlayText {
+            text: line.clone(),
+            font: clock.font.clone(),
+            size: clock.size,
+            bold: clock.bold,
+            color: clock.color,
+            offset_x: 0,
+            offset_y: 0,
+        };
+        draw_aligned_text(hdc, &text, x, top + line_height * index as i32, align_x | DT_TOP);
+    }
+}
+
+// Lines dividing the whole window, under everything else
+fn draw_grid(hdc: HDC, grid: &Grid, rect: &RECT) {
+    let (width, height) = (rect.right - rect.left, rect.bottom - rect.top);
+    unsafe {
+        with_pen(hdc, grid.thickness, grid.color, &CapStyle::Flat, || {
+            for x in grid.divisions(width) {
+                let _ = MoveToEx(hdc, rect.left + x, rect.top, None);
+                let _ = LineTo(hdc, rect.left + x, rect.bottom);
+            }
+            for y in grid.divisions(height) {
+                let _ = MoveToEx(hdc, rect.left, rect.top + y, None);
+                let _ = LineTo(hdc, rect.right, rect.top + y);
+            }
+        });
+    }
+}
+
+// One box per key, lined up from `display.corner` of `rect`. Text isn't
+// measured, so box widths are estimated from the font size.
+fn draw_keystrokes(canvas: &mut Canvas, display: &KeystrokeDisplay, keys: &[(String, f32)], rect: &RECT) {
+    let font_size = display.font_size.max(1);
+    let height = font_size + KEYSTROKE_PADDING * 2;
+    let widths: Vec<i32> = keys
+        .iter()
+        .map(|(name, _)| (font_size * 3 / 5 * name.chars().count() as i32).max(font_size) + KEYSTROKE_PADDING * 2)
+        .collect();
+    
+    let (strip_width, strip_height) = match display.layout {
+        KeystrokeLayout::Row => (
+            widths.iter().sum::<i32>() + KEYSTROKE_PADDING * (widths.len() as i32 - 1).max(0),
+            height,
+        ),
+        KeystrokeLayout::Column => (
+            widths.iter().copied().max().unwrap_or(0),
+            (height + KEYSTROKE_PADDING) * widths.len() as i32 - KEYSTROKE_PADDING,
+        ),
+    };
+    let left = match display.corner {
+        Corner::TopLeft | Corner::BottomLeft => rect.left + display.margin,
+        Corner::TopRight | Corner::BottomRight => rect.right - display.margin - strip_width,
+    };
+    let top = match display.corner {
+        Corner::TopLeft | Corner::TopRight => rect.top + display.margin,
+        Corner::BottomLeft | Corner::BottomRight => rect.bottom - display.margin - strip_height,
+    };
+    
+    let (mut x, mut y) = (left, top);
+    for ((name, opacity), width) in keys.iter().zip(widths) {
+        // Stacked boxes line up with the corner's side
+        let box_left = match (display.layout, display.corner) {
+            (KeystrokeLayout::Column, Corner::TopRight | Corner::BottomRight) => left + strip_width - width,
+            _ => x,
+        };
+        let key_rect = RECT {
+            left: box_left,
+            top: y,
+            right: box_left + width,
+            bottom: y + height,
+        };
+        canvas.layer(opacity * color_alpha(display.background), |hdc| unsafe {
+            let brush = CreateSolidBrush(COLORREF(rgb_to_colorref(display.background)));
+            FillRect(hdc, &key_rect, brush);
+            let _ = DeleteObject(brush);
+        });
+        let text = OverlayText {
+            text: name.clone(),
+            font: "Segoe UI".to_string(),
+            size: font_size,
+            bold: true,
+            color: display.color,
+            offset_x: 0,
+            offset_y: 0,
+        };
+        canvas.layer(opacity * color_alpha(display.color), |hdc| {
+            draw_overlay_text(hdc, &text, box_left + width / 2, y + height / 2);
+        });
+        
+        match display.layout {
+            KeystrokeLayout::Row => x += width + KEYSTROKE_PADDING,
+            KeystrokeLayout::Column => y += height + KEYSTROKE_PADDING,
+        }
+    }
+}
+
+// The OSD message in white, fading out at the end
+fn draw_osd(canvas: &mut Canvas, message: &str, elapsed: Duration, center_x: i32, center_y: i32) {
+    let remaining = OSD_DURATION.saturating_sub(elapsed);
+    let fade = (remaining.as_secs_f32() / OSD_FADE.as_secs_f32()).min(1.0);
+    if fade <= 0.0 {
+        return;
+    }
+    
+    let text = OverlayText {
+        text: message.to_string(),
+        font: "Segoe UI".to_string(),
+        size: OSD_FONT_SIZE,
+        bold: true,
+        color: 0xFFFFFF,
+        offset_x: 0,
+        offset_y: OSD_OFFSET_Y,
+    };
+    draw_shadowed_text(canvas, text, fade, center_x, center_y);
+}
+
+// Remaining whole seconds with the label in front, blinking once it hits zero
+fn draw_countdown(canvas: &mut Canvas, countdown: &Countdown, center_x: i32, center_y: i32) {
+    let elapsed = countdown.started.elapsed();
+    let remaining = countdown.duration.saturating_sub(elapsed);
+    let overtime = elapsed.saturating_sub(countdown.duration);
+    if remaining.is_zero() && (overtime.as_millis() / COUNTDOWN_BLINK_MS) % 2 == 1 {
+        return;
+    }
+    
+    let seconds = remaining.as_secs_f32().ceil() as u64;
+    let text = OverlayText {
+        text: if countdown.label.is_empty() {
+            seconds.to_string()
+        } else {
+            format!("{} {}", countdown.label, seconds)
+        },
+        font: "Segoe UI".to_string(),
+        size: COUNTDOWN_FONT_SIZE,
+        bold: true,
+        color: if remaining.is_zero() { COUNTDOWN_ZERO_COLOR } else { COUNTDOWN_COLOR },
+        offset_x: 0,
+        offset_y: COUNTDOWN_OFFSET_Y,
+    };
+    draw_shadowed_text(canvas, text, 1.0, center_x, center_y);
+}
+
+// `text` over a dark drop shadow so it reads on any background
+fn draw_shadowed_text(canvas: &mut Canvas, mut text: OverlayText, opacity: f32, center_x: i32, center_y: i32) {
+    let color = text.color;
+    text.color = 0x000000;
+    text.offset_x += 1;
+    text.offset_y += 1;
+    canvas.layer(opacity * 0.8, |hdc| draw_overlay_text(hdc, &text, center_x, center_y));
+    text.color = color;
+    text.offset_x -= 1;
+    text.offset_y -= 1;
+    canvas.layer(opacity, |hdc| draw_overlay_text(hdc, &text, center_x, center_y));
+}
+
+// Shows the current offset in the corner of the edit mode window
+fn draw_position_label(hdc: HDC, position_x: f32, position_y: f32) {
+    unsafe {
+        let label: Vec<u16> = format!("x: {}  y: {}", position_x, position_y)
+            .encode_utf16()
+            .collect();
+        
+        SetBkMode(hdc, TRANSPARENT);
+        SetTextColor(hdc, COLORREF(0xFFFFFF));
+        let _ = TextOutW(hdc, 6, 4, &label);
+    }
+}
+
+// Converts 0xRRGGBB to the 0x00BBGGRR layout GDI expects
+fn rgb_to_colorref(color: u32) -> u32 {
+    (color & 0xFF) << 16 | (color & 0xFF00) | (color >> 16) & 0xFF
+}
+
+// Connects the spray pattern's points with a thin line and marks the current
+// shot in `marker_color`
+fn draw_spray_pattern(
+    hdc: HDC,
+    pattern: &SprayPattern,
+    center_x: i32,
+    center_y: i32,
+    marker_color: u32,
+    shot: Option<usize>,
+) {
+    if !pattern.enabled || pattern.points.is_empty() {
+        return;
+    }
+    
+    unsafe {
+        let trace_pen = CreatePen(
+            PS_SOLID,
+            pattern.thickness.max(1),
+            COLORREF(rgb_to_colorref(pattern.color)),
+        );
+        let old_pen = SelectObject(hdc, trace_pen);
+        
+        let first = pattern.points[0];
+        let _ = MoveToEx(hdc, center_x + first.x, center_y + first.y, None);
+        for point in &pattern.points[1..] {
+            let _ = LineTo(hdc, center_x + point.x, center_y + point.y);
+        }
+        
+        SelectObject(hdc, old_pen);
+        let _ = DeleteObject(trace_pen);
+        
+        if let Some(point) = shot.and_then(|i| pattern.points.get(i)) {
+            let marker_brush = CreateSolidBrush(COLORREF(rgb_to_colorref(marker_color)));
+            let old_brush = SelectObject(hdc, marker_brush);
+            let old_pen = SelectObject(hdc, GetStockObject(NULL_PEN));
+            let radius = pattern.thickness.max(1) + 2;
+            
+            let _ = Ellipse(
+                hdc,
+                center_x + point.x - radius,
+                center_y + point.y - radius,
+                center_x + point.x + radius,
+                center_y + point.y + radius,
+            );
+            
+            SelectObject(hdc, old_pen);
+            SelectObject(hdc, old_brush);
+            let _ = DeleteObject(marker_brush);
+        }
+    }
+}
+
+// Clips all further drawing to the window minus a circle of `radius` around the center
+fn apply_dead_zone(hdc: HDC, rect: &RECT, center_x: i32, center_y: i32, radius: i32) {
+    unsafe {
+        if radius <= 0 {
+            SelectClipRgn(hdc, None);
+            return;
+        }
+        
+        let clip = CreateRectRgn(rect.left, rect.top, rect.right, rect.bottom);
+        let hole = CreateEllipticRgn(
+            center_x - radius,
+            center_y - radius,
+            center_x + radius + 1,
+            center_y + radius + 1,
+        );
+        CombineRgn(clip, clip, hole, RGN_DIFF);
+        SelectClipRgn(hdc, clip);
+        
+        let _ = DeleteObject(hole);
+        let _ = DeleteObject(clip);
+    }
+}
+
+// A shadow or outline pass: everything is drawn in `color` and `widen` pixels
+// wider than in the main pass, ignoring per-line and per-arm colors
+#[derive(Clone, Copy)]
+struct PlainPass {
+    color: u32,
+    widen: i32,
+}
+
+// With `plain` set everything is drawn with the selected pen, which the caller
+// sized for the pass; custom elements size their own pens from `widen`
+fn draw_crosshair_shape(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig, plain: Option<PlainPass>) {
+    match config.style {
+            CrosshairStyle::Classic => {
+                draw_classic_crosshair(hdc, center_x, center_y, config, plain);
+            }
+            CrosshairStyle::Dot => {
+                // Dot style is handled by the show_dot setting
+            }
+            CrosshairStyle::Circle => {
+                draw_circle_crosshair(hdc, center_x, center_y, config, plain);
+            }
+            CrosshairStyle::Square => {
+                draw_square_crosshair(hdc, center_x, center_y, config, plain);
+            }
+            CrosshairStyle::TShape => {
+                draw_t_crosshair(hdc, center_x, center_y, config);
+            }
+            CrosshairStyle::Custom => {
+                draw_custom_crosshair(hdc, center_x, center_y, config, plain);
+            }
+            CrosshairStyle::Guides => {
+                draw_guide_lines(hdc, center_x, center_y, config);
+            }
+            CrosshairStyle::Chevron => {
+                draw_chevron_crosshair(hdc, center_x, center_y, config);
+            }
+            CrosshairStyle::X => {
+                draw_x_crosshair(hdc, center_x, center_y, config, plain);
+            }
+            CrosshairStyle::Diamond => {
+                draw_diamond_crosshair(hdc, center_x, center_y, config, plain);
+            }
+            CrosshairStyle::Brackets => {
+                draw_brackets_crosshair(hdc, center_x, center_y, config);
+            }
+            CrosshairStyle::DoubleCircle => {
+                draw_double_circle_crosshair(hdc, center_x, center_y, config, plain);
+            }
+            CrosshairStyle::MilDot => {
+                draw_mil_dot_crosshair(hdc, center_x, center_y, config);
+            }
+            CrosshairStyle::Triangle => {
+                draw_triangle_crosshair(hdc, center_x, center_y, config);
+            }
+            CrosshairStyle::Ruler => {
+                draw_ruler_crosshair(hdc, center_x, center_y, config);
+            }
+            CrosshairStyle::Image { .. } => {
+                // Images are blended by draw_crosshair, GDI has no alpha
+            }
+        }
+}
+
+// Maps an offset from the center to an absolute point, rotated by `degrees`
+fn offset_rotator(center_x: i32, center_y: i32, degrees: f32) -> impl Fn(f32, f32) -> (i32, i32) {
+    move |dx, dy| to_screen(center_x, center_y, geometry::rotate(geometry::Point::new(dx, dy), degrees))
+}
+
+// Absolute pixel for an offset from the center
+fn to_screen(center_x: i32, center_y: i32, point: geometry::Point) -> (i32, i32) {
+    (center_x + point.x.round() as i32, center_y + point.y.round() as i32)
+}
+
+fn draw_classic_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig, plain: Option<PlainPass>) {
+    draw_arms(hdc, center_x, center_y, config, plain, config.rotation);
+}
+
+// The classic arms turned 45 degrees, so `arms.top` becomes the top-right arm
+fn draw_x_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig, plain: Option<PlainPass>) {
+    draw_arms(hdc, center_x, center_y, config, plain, config.rotation + 45.0);
+}
+
+fn draw_arms(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig, plain: Option<PlainPass>, rotation: f32) {
+    unsafe {
+        // Top, bottom, left and right lines, each pointing away from the center
+        for (start, end, color) in arm_segments(config, rotation) {
+            let (x1, y1) = to_screen(center_x, center_y, start);
+            let (x2, y2) = to_screen(center_x, center_y, end);
+            
+            let arm_pen = color
+                .filter(|_| plain.is_none())
+                .map(|color| create_pen(pixels(config.thickness), color, &config.cap_style));
+            let old_pen = arm_pen.map(|arm_pen| SelectObject(hdc, arm_pen));
+            
+            let _ = MoveToEx(hdc, x1, y1, None);
+            let _ = LineTo(hdc, x2, y2);
+            
+            if let (Some(arm_pen), Some(old_pen)) = (arm_pen, old_pen) {
+                SelectObject(hdc, old_pen);
+                let _ = DeleteObject(arm_pen);
+            }
+        }
+    }
+}
+
+fn draw_circle_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig, plain: Option<PlainPass>) {
+    draw_ring(hdc, center_x, center_y, pixels(config.size + config.gap));
+    
+    // Draw crosshair lines inside circle if gap > 0
+    if config.gap > 0.0 {
+        draw_classic_crosshair(hdc, center_x, center_y, config, plain);
+    }
+}
+
+fn draw_double_circle_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig, plain: Option<PlainPass>) {
+    let circles = &config.double_circle;
+    draw_ring(hdc, center_x, center_y, circles.inner_radius);
+    draw_ring(hdc, center_x, center_y, circles.outer_radius);
+    
+    if circles.cross_lines {
+        draw_classic_crosshair(hdc, center_x, center_y, config, plain);
+    }
+}
+
+fn draw_mil_dot_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig) {
+    unsafe {
+        let mil_dot = &config.mil_dot;
+        let point = offset_rotator(center_x, center_y, config.rotation);
+        let count = mil_dot.count.max(0);
+        let reach = (mil_dot.spacing * (count + 1)) as f32;
+        let marker = mil_dot.marker_size as f32;
+        
+        // The crossing lines
+        for (dir_x, dir_y) in [(1.0, 0.0), (0.0, 1.0)] {
+            let (x1, y1) = point(-dir_x * reach, -dir_y * reach);
+            let (x2, y2) = point(dir_x * reach, dir_y * reach);
+            let _ = MoveToEx(hdc, x1, y1, None);
+            let _ = LineTo(hdc, x2, y2);
+        }
+        
+        // Marks on both sides of the center along each line
+        for step in (-count..=count).filter(|&step| step != 0) {
+            let offset = (step * mil_dot.spacing) as f32;
+            for (along_x, along_y) in [(offset, 0.0), (0.0, offset)] {
+                if mil_dot.ticks {
+                    // Perpendicular to the line the mark sits on
+                    let (tick_x, tick_y) = if along_y == 0.0 { (0.0, marker) } else { (marker, 0.0) };
+                    let (x1, y1) = point(along_x - tick_x, along_y - tick_y);
+                    let (x2, y2) = point(along_x + tick_x, along_y + tick_y);
+                    let _ = MoveToEx(hdc, x1, y1, None);
+                    let _ = LineTo(hdc, x2, y2);
+                } else {
+                    let (x, y) = point(along_x, along_y);
+                    draw_ring(hdc, x, y, mil_dot.marker_size);
+                }
+            }
+        }
+    }
+}
+
+fn draw_triangle_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig) {
+    unsafe {
+        let point = offset_rotator(center_x, center_y, config.rotation);
+        let tip = -config.gap;
+        let height = config.triangle.size as f32;
+        let half_base = height / 3f32.sqrt(); // Equilateral
+        
+        let corners = [
+            point(0.0, tip),
+            point(-half_base, tip - height),
+            point(half_base, tip - height),
+        ]
+        .map(|(x, y)| POINT { x, y });
+        
+        // Filling with the pen's own color keeps the shadow and outline passes
+        // filled in their colors too
+        let brush = if config.triangle.filled {
+            CreateSolidBrush(selected_pen_color(hdc))
+        } else {
+            HBRUSH(GetStockObject(NULL_BRUSH).0)
+        };
+        let old_brush = SelectObject(hdc, brush);
+        
+        let _ = Polygon(hdc, &corners);
+        
+        SelectObject(hdc, old_brush);
+        if config.triangle.filled {
+            let _ = DeleteObject(brush);
+        }
+    }
+}
+
+// Both lines out to `ruler.length` with perpendicular ticks, labeled with
+// their distance below the horizontal arms and right of the vertical ones
+fn draw_ruler_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig) {
+    unsafe {
+        let ruler = &config.ruler;
+        let point = offset_rotator(center_x, center_y, config.rotation);
+        let gap = config.gap.max(0.0);
+        let length = ruler.length as f32;
+        let ticks = ruler.ticks();
+        // Labels follow the pen so the shadow and outline passes match too
+        let label_color = rgb_to_colorref(selected_pen_color(hdc).0);
+        
+        for (dir_x, dir_y) in [(0.0f32, -1.0f32), (0.0, 1.0), (-1.0, 0.0), (1.0, 0.0)] {
+            if length > gap {
+                let (x1, y1) = point(dir_x * gap, dir_y * gap);
+                let (x2, y2) = point(dir_x * length, dir_y * length);
+                let _ = MoveToEx(hdc, x1, y1, None);
+                let _ = LineTo(hdc, x2, y2);
+            }
+            
+            // Perpendicular to the arm, pointing down or right
+            let (across_x, across_y) = (dir_y.abs(), dir_x.abs());
+            for &(tick, half, labeled) in ticks.iter().filter(|&&(tick, _, _)| tick as f32 >= gap) {
+                let (along_x, along_y) = (dir_x * tick as f32, dir_y * tick as f32);
+                let half = half as f32;
+                let (x1, y1) = point(along_x - across_x * half, along_y - across_y * half);
+                let (x2, y2) = point(along_x + across_x * half, along_y + across_y * half);
+                let _ = MoveToEx(hdc, x1, y1, None);
+                let _ = LineTo(hdc, x2, y2);
+                
+                if labeled {
+                    let text = tick.to_string();
+                    // Half the label's extent along the offset, approximated
+                    // from its height since the font isn't measured
+                    let half_extent = if across_x > 0.0 {
+                        (ruler.label_size * text.len() as i32) as f32 * 0.3
+                    } else {
+                        ruler.label_size as f32 * 0.5
+                    };
+                    let offset = half + 2.0 + half_extent;
+                    let (x, y) = point(along_x + across_x * offset, along_y + across_y * offset);
+                    let label = OverlayText {
+                        text,
+                        font: "Segoe UI".to_string(),
+                        size: ruler.label_size,
+                        bold: false,
+                        color: label_color,
+                        offset_x: 0,
+                        offset_y: 0,
+                    };
+                    draw_overlay_text(hdc, &label, x, y);
+                }
+            }
+        }
+    }
+}
+
+// Color of the geometric pen selected into `hdc`
+unsafe fn selected_pen_color(hdc: HDC) -> COLORREF {
+    let mut pen = EXTLOGPEN::default();
+    GetObjectW(
+        GetCurrentObject(hdc, OBJ_PEN),
+        std::mem::size_of::<EXTLOGPEN>() as i32,
+        Some(&mut pen as *mut EXTLOGPEN as *mut std::ffi::c_void),
+    );
+    pen.elpColor
+}
+
+// Unfilled circle with the selected pen
+fn draw_ring(hdc: HDC, center_x: i32, center_y: i32, radius: i32) {
+    unsafe {
+        let null_brush = HBRUSH(GetStockObject(NULL_BRUSH).0);
+        let old_brush = SelectObject(hdc, null_brush);
+        
+        let _ = Ellipse(
+            hdc,
+            center_x - radius,
+            center_y - radius,
+            center_x + radius,
+            center_y + radius,
+        );
+        
+        SelectObject(hdc, old_brush);
+    }
+}
+
+fn draw_square_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig, plain: Option<PlainPass>) {
+    unsafe {
+        let half_size = pixels(config.size + config.gap);
+        
+        // Draw square
+        let _ = MoveToEx(hdc, center_x - half_size, center_y - half_size, None);
+        let _ = LineTo(hdc, center_x + half_size, center_y - half_size);
+        let _ = LineTo(hdc, center_x + half_size, center_y + half_size);
+        let _ = LineTo(hdc, center_x - half_size, center_y + half_size);
+        let _ = LineTo(hdc, center_x - half_size, center_y - half_size);
+        
+        // Draw crosshair lines inside square if gap > 0
+        if config.gap > 0.0 {
+            draw_classic_crosshair(hdc, center_x, center_y, config, plain);
+        }
+    }
+}
+
+fn draw_diamond_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig, plain: Option<PlainPass>) {
+    unsafe {
+        let point = offset_rotator(center_x, center_y, config.rotation);
+        let size = config.diamond.size as f32;
+        let corners = [
+            point(0.0, -size),
+            point(size, 0.0),
+            point(0.0, size),
+            point(-size, 0.0),
+        ];
+        
+        let _ = MoveToEx(hdc, corners[3].0, corners[3].1, None);
+        for (x, y) in corners {
+            let _ = LineTo(hdc, x, y);
+        }
+        
+        if config.diamond.inner_lines {
+            draw_classic_crosshair(hdc, center_x, center_y, config, plain);
+        }
+    }
+}
+
+fn draw_brackets_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig) {
+    unsafe {
+        let point = offset_rotator(center_x, center_y, config.rotation);
+        let spread = config.brackets.spread as f32;
+        let leg = config.brackets.corner_length.min(config.brackets.spread) as f32;
+        
+        // Each bracket runs from one leg end through the corner to the other,
+        // with both legs pointing back towards the center
+        for (sign_x, sign_y) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+            let corner_x = sign_x * spread;
+            let corner_y = sign_y * spread;
+            
+            let (x1, y1) = point(corner_x, corner_y - sign_y * leg);
+            let (x2, y2) = point(corner_x, corner_y);
+            let (x3, y3) = point(corner_x - sign_x * leg, corner_y);
+            
+            let _ = MoveToEx(hdc, x1, y1, None);
+            let _ = LineTo(hdc, x2, y2);
+            let _ = LineTo(hdc, x3, y3);
+        }
+    }
+}
+
+// Full-length lines from each edge of the window up to the gap; sniper mode
+// stays four line segments however large the window is
+fn draw_guide_lines(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig) {
+    unsafe {
+        // Layers are memory DCs, so the clip box is the whole frame
+        let mut rect = RECT::default();
+        GetClipBox(hdc, &mut rect);
+        let gap = pixels(config.gap);
+        
+        let _ = MoveToEx(hdc, rect.left, center_y, None);
+        let _ = LineTo(hdc, center_x - gap, center_y);
+        let _ = MoveToEx(hdc, center_x + gap, center_y, None);
+        let _ = LineTo(hdc, rect.right, center_y);
+        
+        let _ = MoveToEx(hdc, center_x, rect.top, None);
+        let _ = LineTo(hdc, center_x, center_y - gap);
+        let _ = MoveToEx(hdc, center_x, center_y + gap, None);
+        let _ = LineTo(hdc, center_x, rect.bottom);
+    }
+}
+
+fn draw_chevron_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig) {
+    unsafe {
+        let chevron = &config.chevron;
+        let point = offset_rotator(center_x, center_y, config.rotation);
+        
+        // The tip sits at the gap and the arms open away from the center
+        let direction = if chevron.below { 1.0 } else { -1.0 };
+        let half_angle = (chevron.angle.clamp(1.0, 179.0) / 2.0).to_radians();
+        let tip = direction * config.gap;
+        let arm_x = chevron.length as f32 * half_angle.sin();
+        let arm_y = direction * chevron.length as f32 * half_angle.cos();
+        
+        let (left_x, left_y) = point(-arm_x, tip + arm_y);
+        let (tip_x, tip_y) = point(0.0, tip);
+        let (right_x, right_y) = point(arm_x, tip + arm_y);
+        
+        let _ = MoveToEx(hdc, left_x, left_y, None);
+        let _ = LineTo(hdc, tip_x, tip_y);
+        let _ = LineTo(hdc, right_x, right_y);
+    }
+}
+
+fn draw_t_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig) {
+    unsafe {
+        let (gap, size) = (pixels(config.gap), pixels(config.size));
+        
+        // Draw horizontal top line
+        let _ = MoveToEx(hdc, center_x - config.t_length, center_y - gap - size, None);
+        let _ = LineTo(hdc, center_x + config.t_length, center_y - gap - size);
+        
+        // Draw vertical line
+        let _ = MoveToEx(hdc, center_x, center_y - gap - size, None);
+        let _ = LineTo(hdc, center_x, center_y - gap);
+        
+        // Optional: Draw bottom and side lines like classic
+        if gap > 0 {
+            // Bottom line
+            let _ = MoveToEx(hdc, center_x, center_y + gap, None);
+            let _ = LineTo(hdc, center_x, center_y + gap + size);
+            
+            // Left line
+            let _ = MoveToEx(hdc, center_x - gap - size, center_y, None);
+            let _ = LineTo(hdc, center_x - gap, center_y);
+            
+            // Right line
+            let _ = MoveToEx(hdc, center_x + gap, center_y, None);
+            let _ = LineTo(hdc, center_x + gap + size, center_y);
+        }
+    }
+}
+
+fn draw_custom_crosshair(hdc: HDC, center_x: i32, center_y: i32, config: &CrosshairConfig, plain: Option<PlainPass>) {
+    unsafe {
+        for element in &config.elements {
+            let (thickness, color, fill) = match *element {
+                Element::Line(ref line) => (line.thickness, line.color, None),
+                Element::Circle { thickness, color, fill, .. }
+                | Element::Rect { thickness, color, fill, .. }
+                | Element::Polygon { thickness, color, fill, .. } => (thickness, color, fill),
+                Element::Arc { thickness, color, .. } => (thickness, color, None),
+            };
+            
+            // Shadow and outline passes follow each element's own thickness and
+            // fill in the pass color, so filled shapes get a solid outline too
+            let (width, color, fill) = match plain {
+                Some(pass) if thickness > 0 || fill.is_some() => {
+                    (thickness + pass.widen, pass.color, fill.map(|_| pass.color))
+                }
+                Some(_) => continue,
+                None => (thickness, color, fill),
+            };
+            
+            let pen = if width > 0 {
+                create_pen(width, color, &config.cap_style)
+            } else {
+                HPEN(GetStockObject(NULL_PEN).0)
+            };
+            let old_pen = SelectObject(hdc, pen);
+            let brush = fill.map(|fill| CreateSolidBrush(COLORREF(rgb_to_colorref(fill))));
+            let old_brush = SelectObject(
+                hdc,
+                brush.unwrap_or_else(|| HBRUSH(GetStockObject(NULL_BRUSH).0)),
+            );
+            
+            draw_element(hdc, center_x, center_y, element);
+            
+            SelectObject(hdc, old_brush);
+            if let Some(brush) = brush {
+                let _ = DeleteObject(brush);
+            }
+            SelectObject(hdc, old_pen);
+            let _ = DeleteObject(pen);
+        }
+    }
+}
+
+// Draws `element` with the selected pen and brush
+unsafe fn draw_element(hdc: HDC, center_x: i32, center_y: i32, element: &Element) {
+    match element {
+        Element::Line(line) => {
+            let _ = MoveToEx(hdc, center_x + line.start_x, center_y + line.start_y, None);
+            let _ = LineTo(hdc, center_x + line.end_x, center_y + line.end_y);
+        }
+        Element::Circle { x, y, radius, .. } => {
+            let _ = Ellipse(
+                hdc,
+                center_x + x - radius,
+                center_y + y - radius,
+                center_x + x + radius,
+                center_y + y + radius,
+            );
+        }
+        Element::Rect { x, y, width, height, .. } => {
+            let _ = Rectangle(
+                hdc,
+                center_x + x,
+                center_y + y,
+                center_x + x + width,
+                center_y + y + height,
+            );
+        }
+        Element::Polygon { points, .. } => {
+            let points: Vec<POINT> = points
+                .iter()
+                .map(|point| POINT {
+                    x: center_x + point.x,
+                    y: center_y + point.y,
+                })
+                .collect();
+            if points.len() >= 2 {
+                let _ = Polygon(hdc, &points);
+            }
+        }
+        Element::Arc { x, y, radius, start_angle, sweep_angle, .. } => {
+            // AngleArc draws a line from the current position to the arc's start
+            let (sin_a, cos_a) = start_angle.to_radians().sin_cos();
+            let arc_x = center_x + x;
+            let arc_y = center_y + y;
+            let _ = MoveToEx(
+                hdc,
+                arc_x + (*radius as f32 * cos_a).round() as i32,
+                arc_y - (*radius as f32 * sin_a).round() as i32,
+                None,
+            );
+            let _ = AngleArc(hdc, arc_x, arc_y, (*radius).max(0) as u32, *start_angle, *sweep_angle);
+        }
+    }
+}
+
+/// Replaces the live config, resizing the window and repainting as needed.
+pub fn update_config(mut config: CrosshairConfig) -> Result<()> {
+    // Angular sizes follow the monitor or window the crosshair is centered on
+    let target = OVERLAY_STATE.lock().unwrap().attached;
+    let (_, _, area_width, _) = target_area(target);
+    resolve_sizing(&mut config, area_width);
+    
+    let mut state = OVERLAY_STATE.lock().unwrap();
+    // The UI sends the whole config on every change, often without a difference
+    if state.config == config {
+        return Ok(());
+    }
+    // Colors the key can't avoid would leave holes in the crosshair
+    if state.color_keyed {
+        if let Err(e) = pick_color_key(&config) {
+            return Err(Error::new(E_INVALIDARG, e));
+        }
+    }
+    let old_bounds = window_bounds(&state.config, state.attached, state.editing);
+    let mut timers_changed = timers_changed(&state.config, &config);
+    state.config = config.clone();
+    
+    // The window follows when the transaction is committed
+    let mut deferred = false;
+    if let Some(transaction) = state.transaction.as_mut() {
+        if transaction.started.elapsed() < MAX_TRANSACTION_AGE {
+            transaction.changed = true;
+            transaction.timers_changed |= timers_changed;
+            return Ok(());
+        }
+        // Abandoned, so catch the window up on everything it held back
+        deferred = transaction.changed;
+        timers_changed |= transaction.timers_changed;
+        state.transaction = None;
+    }
+    let attached = state.attached;
+    let editing = state.editing;
+    let overlay_hwnd = state.hwnd;
+    
+    // SetWindowPos waits on the overlay thread, which may itself be waiting for this lock
+    drop(state);
+    
+    if let Some(hwnd) = overlay_hwnd {
+        unsafe {
+            // Calculate new window bounds
+            let new_bounds = window_bounds(&config, attached, editing);
+            
+            // Only move/resize if the bounds changed
+            if new_bounds != old_bounds || deferred {
+                let (x, y, width, height) = new_bounds;
+                
+                SetWindowPos(
+                    hwnd,
+                    HWND_TOPMOST,
+                    x,
+                    y,
+                    width,
+                    height,
+                    SWP_SHOWWINDOW,
+                )?;
+            }
+            
+            if timers_changed {
+                let _ = PostMessageW(hwnd, WM_OVERLAY_TIMERS, WPARAM(0), LPARAM(0));
+            }
+            
+            let _ = InvalidateRect(hwnd, None, false);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Holds back the window updates of [`update_config`] until the matching
+/// [`commit_config_transaction`], so a burst of changes, like a slider being
+/// dragged, costs one resize and one repaint. Transactions nest, and one left
+/// open for 10 seconds is given up.
+pub fn begin_config_transaction() {
+    let mut state = OVERLAY_STATE.lock().unwrap();
+    match state.transaction.as_mut() {
+        Some(transaction) => transaction.depth += 1,
+        None => {
+            state.transaction = Some(Transaction {
+                depth: 1,
+                changed: false,
+                timers_changed: false,
+                started: Instant::now(),
+            });
+        }
+    }
+}
+
+/// Ends a [`begin_config_transaction`]. Ending the outermost one shows the
+/// config changes made since in a single window update.
+pub fn commit_config_transaction() -> Result<()> {
+    let mut state = OVERLAY_STATE.lock().unwrap();
+    let Some(transaction) = state.transaction.as_mut() else {
+        return Ok(());
+    };
+    if transaction.depth > 1 {
+        transaction.depth -= 1;
+        return Ok(());
+    }
+    let Some(transaction) = state.transaction.take() else {
+        return Ok(());
+    };
+    let Some(hwnd) = state.hwnd.filter(|_| transaction.changed) else {
+        return Ok(());
+    };
+    let (x, y, width, height) = window_bounds(&state.config, state.attached, state.editing);
+    
+    // SetWindowPos waits on the overlay thread, which may itself be waiting for this lock
+    drop(state);
+    
+    unsafe {
+        SetWindowPos(hwnd, HWND_TOPMOST, x, y, width, height, SWP_SHOWWINDOW)?;
+        if transaction.timers_changed {
+            let _ = PostMessageW(hwnd, WM_OVERLAY_TIMERS, WPARAM(0), LPARAM(0));
+        }
+        let _ = InvalidateRect(hwnd, None, false);
+    }
+    Ok(())
+}
+
+/// Centers the crosshair on the client area of `target` (a raw HWND) and keeps
+/// following it as it moves, resizes or minimizes. `None` re-centers on the screen.
+pub fn attach_to_window(target: Option<isize>) -> Result<()> {
+    let mut state = OVERLAY_STATE.lock().unwrap();
+    
+    let target = target.map(|t| HWND(t as *mut _));
+    if let Some(target) = target {
+        if !unsafe { IsWindow(target) }.as_bool() {
+            return Err(Error::from(ERROR_INVALID_WINDOW_HANDLE.to_hresult()));
+        }
+    }
+    state.attached = target;
+    
+    // WinEvent hooks have to be installed on the thread running the message loop
+    if let Some(hwnd) = state.hwnd {
+        unsafe {
+            PostMessageW(hwnd, WM_OVERLAY_ATTACH, WPARAM(0), LPARAM(0))?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Returns the raw HWND the crosshair is attached to, if any.
+pub fn get_attached_window() -> Option<isize> {
+    let state = OVERLAY_STATE.lock().unwrap();
+    state.attached.map(|hwnd| hwnd.0 as isize)
+}
+
+/// Shows or hides the crosshair by setting `config.enabled`.
+pub fn toggle_overlay(enabled: bool) -> Result<()> {
+    let mut state = OVERLAY_STATE.lock().unwrap();
+    if state.config.enabled == enabled {
+        return Ok(());
+    }
+    state.config.enabled = enabled;
+    
+    if let Some(hwnd) = state.hwnd {
+        unsafe {
+            let _ = InvalidateRect(hwnd, None, false);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Centers the crosshair on monitor `index` of [`list_monitors`](crate::list_monitors),
+/// or on the primary monitor for `None`. Attaching to a window takes precedence.
+pub fn set_monitor(index: Option<usize>) -> Result<()> {
+    *SELECTED_MONITOR.lock().unwrap() = index;
+    detect_resolution();
+    
+    refit_window(false)
+}
+
+/// Copies the screen `radius` pixels each way around the point the crosshair
+/// is anchored to, before its position offsets, as rows of 0x00RRGGBB. The
+/// overlay itself isn't captured, so a game's own reticle shows through.
+pub fn capture_anchor_area(radius: i32) -> Option<Vec<u32>> {
+    let (center_x, center_y) = {
+        let state = OVERLAY_STATE.lock().unwrap();
+        let (x, y, width, height) = target_area(state.attached);
+        let (anchor_x, anchor_y) = state.config.anchor.point(width, height);
+        (x + anchor_x, y + anchor_y)
+    };
+    let size = radius * 2 + 1;
+    sample_screen(center_x - radius, center_y - radius, size, size)
+}
+
+// Fits the window to whatever is shown now and repaints it. `post_timers`
+// also has the overlay thread re-arm its timers and input hooks.
+fn refit_window(post_timers: bool) -> Result<()> {
+    let state = OVERLAY_STATE.lock().unwrap();
+    let Some(hwnd) = state.hwnd else {
+        return Ok(());
+    };
+    let (x, y, width, height) = window_bounds(&state.config, state.attached, state.editing);
+    
+    // SetWindowPos waits on the overlay thread, which may itself be waiting for this lock
+    drop(state);
+    
+    unsafe {
+        SetWindowPos(hwnd, HWND_TOPMOST, x, y, width, height, SWP_NOACTIVATE)?;
+        if post_timers {
+            PostMessageW(hwnd, WM_OVERLAY_TIMERS, WPARAM(0), LPARAM(0))?;
+        }
+        let _ = InvalidateRect(hwnd, None, false);
+    }
+    Ok(())
+}
+
+/// Shows `grid` across the target area, or hides it for `None`. The grid is
+/// independent of the crosshair and stays up while it is toggled off.
+pub fn set_grid(grid: Option<Grid>) -> Result<()> {
+    *GRID.lock().unwrap() = grid;
+    
+    refit_window(false)
+}
+
+/// Shows or hides the crosshair in screen recordings and screenshots.
+pub fn set_capture_visible(visible: bool) -> Result<()> {
+    let mut state = OVERLAY_STATE.lock().unwrap();
+    state.capture_visible = visible;
+    let overlay_hwnd = state.hwnd;
+    drop(state);
+    
+    if let Some(hwnd) = overlay_hwnd {
+        apply_capture_visibility(hwnd, visible);
+    }
+    Ok(())
+}
+
+/// Sets how often, in milliseconds, the overlay re-asserts always-on-top over
+/// games that grab it. 0 turns the watchdog off.
+pub fn set_topmost_interval(interval_ms: u32) -> Result<()> {
+    if TOPMOST_INTERVAL_MS.swap(interval_ms, Ordering::Relaxed) == interval_ms {
+        return Ok(());
+    }
+    
+    // Timers have to be set on the overlay thread
+    let overlay_hwnd = OVERLAY_STATE.lock().unwrap().hwnd;
+    if let Some(hwnd) = overlay_hwnd {
+        unsafe {
+            PostMessageW(hwnd, WM_OVERLAY_TIMERS, WPARAM(0), LPARAM(0))?;
+        }
+    }
+    Ok(())
+}
+
+/// Hides or re-shows the crosshair without touching the user's enabled setting.
+pub fn set_suppressed(suppressed: bool) -> Result<()> {
+    let mut state = OVERLAY_STATE.lock().unwrap();
+    if state.suppressed == suppressed {
+        return Ok(());
+    }
+    state.suppressed = suppressed;
+    
+    if let Some(hwnd) = state.hwnd {
+        unsafe {
+            let _ = InvalidateRect(hwnd, None, false);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Briefly shows `message` below the crosshair, e.g. the name of a preset
+/// that was just applied. It fades out after about 1.5 seconds and is shown
+/// even while the crosshair itself is hidden.
+pub fn flash_osd(message: &str) -> Result<()> {
+    *OSD.lock().unwrap() = Some((message.to_string(), Instant::now()));
+    
+    // The fade timer has to be set on the overlay thread
+    let overlay_hwnd = OVERLAY_STATE.lock().unwrap().hwnd;
+    if let Some(hwnd) = overlay_hwnd {
+        unsafe {
+            PostMessageW(hwnd, WM_OVERLAY_OSD, WPARAM(0), LPARAM(0))?;
+        }
+    }
+    Ok(())
+}
+
+/// Shows recently pressed keys in a corner of the target area, or turns the
+/// display off for `None`. Keys are only read from the keyboard hook while it
+/// is on.
+pub fn set_keystroke_display(display: Option<KeystrokeDisplay>) -> Result<()> {
+    set_key_logging(display.is_some());
+    *KEYSTROKES.lock().unwrap() = display;
+    
+    // The keyboard hook has to be installed on the overlay thread
+    refit_window(true)
+}
+
+/// Draws a ring for every left and right click, or turns the visualizer off
+/// for `None`. Clicks are only read from the mouse hook while it is on.
+pub fn set_click_visualizer(visualizer: Option<ClickVisualizer>) -> Result<()> {
+    set_click_logging(visualizer.is_some());
+    *CLICKS.lock().unwrap() = visualizer;
+    
+    // The mouse hook has to be installed on the overlay thread
+    refit_window(true)
+}
+
+/// Shows the clock and session timer in a corner of the target area, or
+/// hides them for `None`.
+pub fn set_clock_display(clock: Option<ClockDisplay>) -> Result<()> {
+    *CLOCK.lock().unwrap() = clock;
+    
+    // The tick timer has to be set on the overlay thread
+    refit_window(true)
+}
+
+/// Counts `seconds` down above the crosshair, after `label` if it isn't
+/// empty, and blinks at zero. Replaces a countdown that is already running.
+pub fn start_timer(seconds: f32, label: &str) -> Result<()> {
+    // clamp passes NaN through, and Duration panics on it
+    if seconds.is_nan() {
+        return Err(Error::new(E_INVALIDARG, "The countdown length is not a number"));
+    }
+    *COUNTDOWN.lock().unwrap() = Some(Countdown {
+        label: label.to_string(),
+        duration: Duration::from_secs_f32(seconds.clamp(0.0, MAX_COUNTDOWN_SECS)),
+        started: Instant::now(),
+    });
+    
+    // The frame timer has to be set on the overlay thread
+    let overlay_hwnd = OVERLAY_STATE.lock().unwrap().hwnd;
+    if let Some(hwnd) = overlay_hwnd {
+        unsafe {
+            PostMessageW(hwnd, WM_OVERLAY_COUNTDOWN, WPARAM(0), LPARAM(0))?;
+        }
+    }
+    Ok(())
+}
+
+/// Registers a callback that runs on the overlay thread whenever Explorer
+/// recreates the taskbar, so the tray icon can be added back.
+pub fn set_taskbar_created_handler<F: Fn() + Send + 'static>(handler: F) {
+    *TASKBAR_CREATED_HANDLER.lock().unwrap() = Some(Box::new(handler));
+}
+
+/// Registers a callback run after the crosshair has been dragged to a new
+/// position in edit mode, with the new `position_x`/`position_y`.
+pub fn set_position_changed_handler<F: Fn(f32, f32) + Send + 'static>(handler: F) {
+    *POSITION_CHANGED_HANDLER.lock().unwrap() = Some(Box::new(handler));
+}
+
+/// Makes the overlay clickable so the crosshair can be dragged with the mouse,
+/// or locks it back to click-through.
+pub fn set_edit_mode(enabled: bool) -> Result<()> {
+    let mut state = OVERLAY_STATE.lock().unwrap();
+    state.editing = enabled;
+    let overlay_hwnd = state.hwnd;
+    drop(state);
+    
+    if let Some(hwnd) = overlay_hwnd {
+        unsafe {
+            PostMessageW(hwnd, WM_OVERLAY_EDIT, WPARAM(0), LPARAM(0))?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// True while the overlay is in edit mode.
+pub fn is_edit_mode() -> bool {
+    OVERLAY_STATE.lock().unwrap().editing
+}
+
+/// Returns a copy of the config currently used by the overlay.
+pub fn get_config() -> CrosshairConfig {
+    let state = OVERLAY_STATE.lock().unwrap();
+    state.config.clone()
+}
\ No newline at end of file
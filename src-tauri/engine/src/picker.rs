@@ -0,0 +1,91 @@
+//! Screen color picker: the next left click anywhere on screen is swallowed
+//! and the color of the clicked pixel is returned.
+
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::time::Duration;
+use windows::Win32::{
+    Foundation::*,
+    System::LibraryLoader::GetModuleHandleW,
+    UI::WindowsAndMessaging::*,
+};
+
+use crate::canvas::sample_screen;
+
+// Only one pick can run at a time; the hook reports back through these
+static PICKING: AtomicBool = AtomicBool::new(false);
+static PICKED: AtomicBool = AtomicBool::new(false);
+static PICKED_X: AtomicI32 = AtomicI32::new(0);
+static PICKED_Y: AtomicI32 = AtomicI32::new(0);
+
+/// Waits up to `timeout` for a left click and returns the clicked pixel as
+/// 0xRRGGBB, or `None` when the pick was cancelled with a right click or timed
+/// out. Blocks the calling thread.
+pub fn pick_screen_color(timeout: Duration) -> Result<Option<u32>, String> {
+    if PICKING.swap(true, Ordering::SeqCst) {
+        return Err("A color pick is already in progress".to_string());
+    }
+    PICKED.store(false, Ordering::SeqCst);
+    
+    // Low-level hooks are serviced by the installing thread's message loop
+    let result = std::thread::spawn(move || unsafe { run_pick_loop(timeout) })
+        .join()
+        .unwrap_or_else(|_| Err("Color picker thread panicked".to_string()));
+    PICKING.store(false, Ordering::SeqCst);
+    result?;
+    
+    if !PICKED.load(Ordering::SeqCst) {
+        return Ok(None);
+    }
+    
+    // The overlay is a layered window, so it never ends up in the sample
+    let x = PICKED_X.load(Ordering::SeqCst);
+    let y = PICKED_Y.load(Ordering::SeqCst);
+    let pixel = sample_screen(x, y, 1, 1).ok_or("Failed to read the screen")?;
+    Ok(Some(pixel[0]))
+}
+
+unsafe fn run_pick_loop(timeout: Duration) -> Result<(), String> {
+    let instance = GetModuleHandleW(None).map_err(|e| e.to_string())?;
+    let hook = SetWindowsHookExW(WH_MOUSE_LL, Some(pick_hook_proc), instance, 0)
+        .map_err(|e| e.to_string())?;
+    
+    // A thread timer posts WM_TIMER to this thread's queue
+    let timer = SetTimer(None, 0, timeout.as_millis().min(u32::MAX as u128) as u32, None);
+    
+    let mut msg = MSG::default();
+    while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+        if msg.message == WM_TIMER {
+            break;
+        }
+        DispatchMessageW(&msg);
+    }
+    
+    let _ = KillTimer(None, timer);
+    let _ = UnhookWindowsHookEx(hook);
+    Ok(())
+}
+
+unsafe extern "system" fn pick_hook_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code >= 0 {
+        let info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+        match wparam.0 as u32 {
+            WM_LBUTTONDOWN => {
+                PICKED_X.store(info.pt.x, Ordering::SeqCst);
+                PICKED_Y.store(info.pt.y, Ordering::SeqCst);
+                PICKED.store(true, Ordering::SeqCst);
+                PostQuitMessage(0);
+                // Swallow the click so it doesn't land in the game or app below
+                return LRESULT(1);
+            }
+            WM_RBUTTONDOWN => {
+                PostQuitMessage(0);
+                return LRESULT(1);
+            }
+            // Swallow the matching button-ups too
+            WM_LBUTTONUP | WM_RBUTTONUP => return LRESULT(1),
+            _ => {}
+        }
+    }
+    
+    CallNextHookEx(None, code, wparam, lparam)
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+//! Spray-pattern practice overlay. A pattern is a list of per-shot offsets
+//! drawn as a faint trace under the crosshair; while the left mouse button is
+//! held a marker can walk through it at the weapon's fire rate.
+
+use serde::{Deserialize, Serialize};
+
+use crate::spread::InputState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SprayPoint {
+    pub x: i32,  // Offset from the crosshair center in pixels, positive is right
+    pub y: i32,  // Offset from the crosshair center in pixels, positive is down
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SprayPattern {
+    pub enabled: bool,
+    pub points: Vec<SprayPoint>,  // One offset per shot, in firing order
+    #[serde(with = "crate::color::hex")]
+    pub color: u32,               // Trace color, keep it dim so it reads as a guide
+    pub thickness: i32,
+    pub animate: bool,            // Walk a marker through the pattern while firing
+    pub shot_interval_ms: u32,    // Time between shots while animating
+}
+
+impl Default for SprayPattern {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            points: Vec::new(),
+            color: 0x606060,
+            thickness: 1,
+            animate: true,
+            shot_interval_ms: 100,
+        }
+    }
+}
+
+impl SprayPattern {
+    /// True when the marker has to follow the left mouse button.
+    pub fn is_animated(&self) -> bool {
+        self.enabled && self.animate && !self.points.is_empty()
+    }
+    
+    /// Largest distance of any point from the center, per axis.
+    pub fn extent(&self) -> i32 {
+        if !self.enabled {
+            return 0;
+        }
+        self.points
+            .iter()
+            .map(|p| p.x.abs().max(p.y.abs()))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// How long the left button has been held, carried between frames.
+#[derive(Debug, Clone, Default)]
+pub struct SprayState {
+    firing_for: f32,
+}
+
+impl SprayState {
+    /// Advances by `dt` seconds and returns the index of the current shot, or
+    /// `None` when no marker should be drawn.
+    pub fn update(&mut self, pattern: &SprayPattern, input: InputState, dt: f32) -> Option<usize> {
+        if !pattern.is_animated() || !input.firing {
+            self.firing_for = 0.0;
+            return None;
+        }
+        
+        let shot = (self.firing_for * 1000.0 / pattern.shot_interval_ms.max(1) as f32) as usize;
+        self.firing_for += dt;
+        
+        // Hold the last point once the magazine is "empty"
+        Some(shot.min(pattern.points.len() - 1))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+//! Dynamic crosshair spread, mimicking in-game crosshairs that open up while
+//! firing. Input state comes from the platform hooks; everything here is pure.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::CrosshairConfig;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClickSpread {
+    pub enabled: bool,
+    pub expand: i32,          // Extra gap in pixels while the left button is held
+    pub recovery_speed: f32,  // Pixels per second the gap closes after release
+}
+
+impl Default for ClickSpread {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            expand: 6,
+            recovery_speed: 40.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MovementSpread {
+    pub enabled: bool,
+    pub expand: i32,     // Extra gap in pixels while a movement key (WASD) is held
+    pub smoothing: f32,  // Seconds to ease most of the way to the target gap, 0 = instant
+}
+
+impl Default for MovementSpread {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            expand: 8,
+            smoothing: 0.12,
+        }
+    }
+}
+
+/// Snapshot of the inputs that drive dynamic spread.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InputState {
+    pub firing: bool,
+    pub moving: bool,
+    pub aiming: bool,  // Right mouse button held
+}
+
+/// Current spread, carried between frames.
+#[derive(Debug, Clone, Default)]
+pub struct SpreadState {
+    click: f32,
+    movement: f32,
+}
+
+impl SpreadState {
+    /// Advances the spread by `dt` seconds and returns the extra gap in pixels.
+    pub fn update(&mut self, config: &CrosshairConfig, input: InputState, dt: f32) -> i32 {
+        let click = &config.click_spread;
+        
+        if !click.enabled {
+            self.click = 0.0;
+        } else if input.firing {
+            // Opening up is instant, like a weapon's first shot
+            self.click = click.expand as f32;
+        } else {
+            self.click = (self.click - click.recovery_speed.max(0.0) * dt).max(0.0);
+        }
+        
+        let movement = &config.movement_spread;
+        let target = if movement.enabled && input.moving {
+            movement.expand as f32
+        } else {
+            0.0
+        };
+        
+        if !movement.enabled || movement.smoothing <= 0.0 {
+            self.movement = target;
+        } else {
+            // Exponential ease so the gap springs open and settles back smoothly
+            let t = 1.0 - (-dt / movement.smoothing).exp();
+            self.movement += (target - self.movement) * t;
+            if (target - self.movement).abs() < 0.05 {
+                self.movement = target;
+            }
+        }
+        
+        (self.click + self.movement).round() as i32
+    }
+    
+    /// True while the spread hasn't settled back to zero.
+    pub fn is_active(&self) -> bool {
+        self.click > 0.0 || self.movement > 0.0
+    }
+}
+
+/// Largest extra gap `config` can produce, used to size the overlay window.
+pub fn max_spread(config: &CrosshairConfig) -> i32 {
+    let click = if config.click_spread.enabled {
+        config.click_spread.expand.max(0)
+    } else {
+        0
+    };
+    let movement = if config.movement_spread.enabled {
+        config.movement_spread.expand.max(0)
+    } else {
+        0
+    };
+    click + movement
+}
\ No newline at end of file
@@ -0,0 +1,155 @@
+//! Symmetry helpers for the Custom style: generate mirrored or rotated copies
+//! of the elements so symmetric shapes only have to be drawn once.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{CrosshairLine, Element, ElementPoint};
+use crate::geometry::{rotate, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MirrorAxis {
+    Horizontal,  // Left-right, across the vertical line through the center
+    Vertical,    // Top-bottom, across the horizontal line through the center
+    Both,        // Both of the above plus the point reflection, four copies in total
+}
+
+/// Appends mirrored copies of every element in `elements`.
+pub fn mirror_elements(elements: &mut Vec<Element>, axis: MirrorAxis) {
+    let originals = elements.clone();
+    let flips: &[(bool, bool)] = match axis {
+        MirrorAxis::Horizontal => &[(true, false)],
+        MirrorAxis::Vertical => &[(false, true)],
+        MirrorAxis::Both => &[(true, false), (false, true), (true, true)],
+    };
+    
+    for &(flip_x, flip_y) in flips {
+        elements.extend(originals.iter().map(|element| mirrored(element, flip_x, flip_y)));
+    }
+}
+
+/// Appends copies of every element in `elements` rotated by each multiple of
+/// `degrees` short of a full turn, e.g. 90 adds three copies for a four-way
+/// symmetric shape. Positive degrees turn clockwise, like `rotation`.
+pub fn rotate_elements(elements: &mut Vec<Element>, degrees: f32) -> Result<(), String> {
+    if !(1.0..360.0).contains(&degrees.abs()) {
+        return Err("Rotation must be between 1 and 359 degrees".to_string());
+    }
+    
+    let originals = elements.clone();
+    let copies = ((360.0 / degrees.abs()).ceil() as usize).saturating_sub(1);
+    for step in 1..=copies {
+        let angle = degrees * step as f32;
+        if angle.abs() >= 360.0 - 0.01 {
+            break;
+        }
+        elements.extend(originals.iter().map(|element| rotated(element, angle)));
+    }
+    Ok(())
+}
+
+fn mirrored(element: &Element, flip_x: bool, flip_y: bool) -> Element {
+    let fx = |x: i32| if flip_x { -x } else { x };
+    let fy = |y: i32| if flip_y { -y } else { y };
+    
+    let mut element = element.clone();
+    match &mut element {
+        Element::Line(line) => {
+            line.start_x = fx(line.start_x);
+            line.start_y = fy(line.start_y);
+            line.end_x = fx(line.end_x);
+            line.end_y = fy(line.end_y);
+        }
+        Element::Circle { x, y, .. } => {
+            *x = fx(*x);
+            *y = fy(*y);
+        }
+        Element::Rect { x, y, width, height, .. } => {
+            // Keep the corner top-left after flipping
+            if flip_x {
+                *x = -(*x + *width);
+            }
+            if flip_y {
+                *y = -(*y + *height);
+            }
+        }
+        Element::Polygon { points, .. } => {
+            for point in points {
+                point.x = fx(point.x);
+                point.y = fy(point.y);
+            }
+        }
+        Element::Arc { x, y, start_angle, sweep_angle, .. } => {
+            *x = fx(*x);
+            *y = fy(*y);
+            // A single flip reverses the arc's direction
+            if flip_x {
+                *start_angle = 180.0 - *start_angle;
+                *sweep_angle = -*sweep_angle;
+            }
+            if flip_y {
+                *start_angle = -*start_angle;
+                *sweep_angle = -*sweep_angle;
+            }
+        }
+    }
+    element
+}
+
+fn rotated(element: &Element, degrees: f32) -> Element {
+    let turn = |x: i32, y: i32| {
+        let point = rotate(Point::new(x as f32, y as f32), degrees);
+        (point.x.round() as i32, point.y.round() as i32)
+    };
+    
+    match element.clone() {
+        Element::Line(line) => {
+            let (start_x, start_y) = turn(line.start_x, line.start_y);
+            let (end_x, end_y) = turn(line.end_x, line.end_y);
+            Element::Line(CrosshairLine {
+                start_x,
+                start_y,
+                end_x,
+                end_y,
+                ..line
+            })
+        }
+        Element::Circle { x, y, radius, thickness, color, fill } => {
+            let (x, y) = turn(x, y);
+            Element::Circle { x, y, radius, thickness, color, fill }
+        }
+        // A turned rectangle is no longer axis-aligned
+        Element::Rect { x, y, width, height, thickness, color, fill } => {
+            let points = [(x, y), (x + width, y), (x + width, y + height), (x, y + height)]
+                .into_iter()
+                .map(|(x, y)| {
+                    let (x, y) = turn(x, y);
+                    ElementPoint { x, y }
+                })
+                .collect();
+            Element::Polygon { points, thickness, color, fill }
+        }
+        Element::Polygon { points, thickness, color, fill } => {
+            let points = points
+                .into_iter()
+                .map(|point| {
+                    let (x, y) = turn(point.x, point.y);
+                    ElementPoint { x, y }
+                })
+                .collect();
+            Element::Polygon { points, thickness, color, fill }
+        }
+        // Arc angles run counterclockwise with y up, the opposite of screen rotation
+        Element::Arc { x, y, radius, start_angle, sweep_angle, thickness, color } => {
+            let (x, y) = turn(x, y);
+            Element::Arc {
+                x,
+                y,
+                radius,
+                start_angle: start_angle - degrees,
+                sweep_angle,
+                thickness,
+                color,
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,254 @@
+//! Crosshair actions shared by the external control interfaces (HTTP API and
+//! named pipe). Each one also emits the matching app event, so the settings
+//! UI and the event stream see changes made from outside.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::preset_check;
+use crate::storage::{self, CONFIG_FILE};
+use crate::ConfigPayload;
+use crosshair_engine::{backend, CrosshairConfig, CrosshairStyle};
+
+#[derive(Clone, Serialize)]
+struct PresetAppliedPayload {
+    preset_id: String,
+    config: CrosshairConfig,
+}
+
+#[derive(Clone, Serialize)]
+struct PresetPreviewPayload {
+    preset_id: Option<String>,  // None when the preview ended
+}
+
+// Id of the last preset applied, where step_preset continues from
+static LAST_PRESET: Mutex<Option<String>> = Mutex::new(None);
+
+// Config to go back to when the preset preview ends, None while not previewing
+static PREVIEW_RESTORE: Mutex<Option<CrosshairConfig>> = Mutex::new(None);
+
+/// The config the overlay is drawing. During a preset preview it's the
+/// config the preview goes back to, so changes build on the saved config
+/// rather than on the preset.
+pub fn live_config() -> Result<CrosshairConfig, String> {
+    let config = backend().config();
+    Ok(match PREVIEW_RESTORE.lock().unwrap().clone() {
+        Some(restore) => CrosshairConfig { enabled: config.enabled, ..restore },
+        None => config,
+    })
+}
+
+/// Replaces the live config and saves it.
+pub fn apply_config<R: Runtime>(app: &AppHandle<R>, config: CrosshairConfig) -> Result<(), String> {
+    cancel_preview(app)?;
+    backend().update_config(config.clone())?;
+    let config_path = storage::file_path(CONFIG_FILE)?;
+    storage::write_json(&config_path, &config)?;
+    
+    app.emit("crosshair-config-changed", ConfigPayload { config })
+        .map_err(|e| e.to_string())
+}
+
+/// Replaces the live config without saving it, for configs read from a file
+/// that was just written.
+pub fn show_config<R: Runtime>(app: &AppHandle<R>, config: CrosshairConfig) -> Result<(), String> {
+    cancel_preview(app)?;
+    backend().update_config(config.clone())?;
+    app.emit("crosshair-config-changed", ConfigPayload { config })
+        .map_err(|e| e.to_string())
+}
+
+/// `config` with the fields of `patch`, a JSON object holding only what
+/// changes. Each field is replaced whole, so patching `elements` replaces
+/// every shape.
+pub fn patched_config(config: &CrosshairConfig, patch: Value) -> Result<CrosshairConfig, String> {
+    let Value::Object(patch) = patch else {
+        return Err("A config patch must be a JSON object".to_string());
+    };
+    let mut merged = serde_json::to_value(config).map_err(|e| e.to_string())?;
+    let fields = merged.as_object_mut().ok_or("The config is not a JSON object")?;
+    for (name, value) in patch {
+        // A typo would otherwise be dropped without a word
+        if !fields.contains_key(&name) {
+            return Err(format!("Unknown config field \"{}\"", name));
+        }
+        fields.insert(name, value);
+    }
+    serde_json::from_value(merged).map_err(|e| format!("Invalid config patch: {}", e))
+}
+
+/// Merges `patch` onto the live config (see `patched_config`) and saves it.
+/// Patches come from external scripts, so the result is held to the preset
+/// limits.
+pub fn patch_config(app: &AppHandle, patch: Value) -> Result<CrosshairConfig, String> {
+    let config = patched_config(&live_config()?, patch)?;
+    preset_check::check_limits(&config)?;
+    apply_config(app, config.clone())?;
+    Ok(config)
+}
+
+/// Replaces the live config with the defaults and saves it, keeping the
+/// current visibility. The saved config is backed up first, like any save.
+pub fn reset_to_defaults(app: &AppHandle) -> Result<CrosshairConfig, String> {
+    let config = CrosshairConfig {
+        enabled: live_config()?.enabled,
+        ..CrosshairConfig::default()
+    };
+    apply_config(app, config.clone())?;
+    Ok(config)
+}
+
+/// Shows or hides the crosshair.
+pub fn set_enabled(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    backend().toggle(enabled)?;
+    app.emit("crosshair-toggled", enabled).map_err(|e| e.to_string())
+}
+
+/// Applies a saved preset, keeping the current visibility. Returns false when
+/// no preset has that id.
+pub fn apply_preset(app: &AppHandle, preset_id: &str) -> Result<bool, String> {
+    let Some(preset) = find_preset(preset_id)? else {
+        return Ok(false);
+    };
+    
+    // Switching presets shouldn't turn a hidden crosshair back on
+    let mut config = preset.config;
+    config.enabled = backend().config().enabled;
+    cancel_preview(app)?;
+    backend().update_config(config.clone())?;
+    
+    *LAST_PRESET.lock().unwrap() = Some(preset.id.clone());
+    app.emit("preset-applied", PresetAppliedPayload { preset_id: preset.id, config })
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// Shows preset `preset_id` without saving it until `end_preview`, keeping
+/// the current visibility. Returns false when no preset has that id.
+/// Starting again while previewing changes nothing.
+pub fn start_preview(app: &AppHandle, preset_id: &str) -> Result<bool, String> {
+    let mut restore = PREVIEW_RESTORE.lock().unwrap();
+    if restore.is_some() {
+        return Ok(true);
+    }
+    let Some(preset) = find_preset(preset_id)? else {
+        return Ok(false);
+    };
+    
+    let live = live_config()?;
+    let mut config = preset.config;
+    config.enabled = live.enabled;
+    backend().update_config(config)?;
+    *restore = Some(live);
+    
+    app.emit("preset-preview", PresetPreviewPayload { preset_id: Some(preset.id) })
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// Puts back the config from before `start_preview`, keeping any visibility
+/// change made meanwhile. Does nothing when not previewing.
+pub fn end_preview(app: &AppHandle) -> Result<(), String> {
+    let Some(mut config) = PREVIEW_RESTORE.lock().unwrap().take() else {
+        return Ok(());
+    };
+    config.enabled = backend().config().enabled;
+    backend().update_config(config)?;
+    
+    app.emit("preset-preview", PresetPreviewPayload { preset_id: None })
+        .map_err(|e| e.to_string())
+}
+
+// A change made during a preview replaces what's on screen, so the preview
+// ends without putting its config back; otherwise releasing the preview key
+// would undo the change on screen but not in config.json
+fn cancel_preview<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    if PREVIEW_RESTORE.lock().unwrap().take().is_none() {
+        return Ok(());
+    }
+    app.emit("preset-preview", PresetPreviewPayload { preset_id: None })
+        .map_err(|e| e.to_string())
+}
+
+fn find_preset(preset_id: &str) -> Result<Option<crate::CrosshairPreset>, String> {
+    Ok(crate::load_favorites()?
+        .presets
+        .into_iter()
+        .find(|p| p.id == preset_id))
+}
+
+/// Applies the preset `step` places after the last one applied, wrapping
+/// around the saved order. Returns its id, None when there are no presets.
+pub fn step_preset(app: &AppHandle, step: isize) -> Result<Option<String>, String> {
+    let presets = crate::load_favorites()?.presets;
+    if presets.is_empty() {
+        return Ok(None);
+    }
+    
+    let len = presets.len() as isize;
+    let last = LAST_PRESET.lock().unwrap().clone();
+    let index = match last.and_then(|id| presets.iter().position(|p| p.id == id)) {
+        Some(index) => (index as isize + step).rem_euclid(len),
+        // Without a preset to continue from, start at either end
+        None if step > 0 => 0,
+        None => len - 1,
+    };
+    
+    let id = presets[index as usize].id.clone();
+    apply_preset(app, &id)?;
+    Ok(Some(id))
+}
+
+// Limits of the step hotkeys, matching the settings sliders
+const MIN_OPACITY: f32 = 0.1;
+const MAX_SIZE: f32 = 50.0;
+
+/// Changes the crosshair opacity by `step` (0.1 = 10%) and saves it.
+pub fn step_opacity(app: &AppHandle, step: f32) -> Result<CrosshairConfig, String> {
+    let mut config = live_config()?;
+    // Rounded so repeated steps land on whole percentages
+    config.opacity = ((config.opacity + step).clamp(MIN_OPACITY, 1.0) * 100.0).round() / 100.0;
+    apply_config(app, config.clone())?;
+    Ok(config)
+}
+
+/// Changes the crosshair size by `step` pixels and saves it.
+pub fn step_size(app: &AppHandle, step: f32) -> Result<CrosshairConfig, String> {
+    let mut config = live_config()?;
+    config.size = (config.size + step).clamp(0.0, MAX_SIZE);
+    apply_config(app, config.clone())?;
+    Ok(config)
+}
+
+// Order of the style cycling hotkey
+const STYLE_CYCLE: [CrosshairStyle; 6] = [
+    CrosshairStyle::Classic,
+    CrosshairStyle::Dot,
+    CrosshairStyle::Circle,
+    CrosshairStyle::Square,
+    CrosshairStyle::TShape,
+    CrosshairStyle::Custom,
+];
+
+/// Switches to the next style of the cycle, keeping the rest of the config,
+/// and saves it. Styles outside the cycle go back to its start.
+pub fn cycle_style(app: &AppHandle) -> Result<CrosshairConfig, String> {
+    let mut config = live_config()?;
+    let next = STYLE_CYCLE
+        .iter()
+        .position(|style| *style == config.style)
+        .map_or(0, |i| (i + 1) % STYLE_CYCLE.len());
+    config.style = STYLE_CYCLE[next].clone();
+    apply_config(app, config.clone())?;
+    Ok(config)
+}
+
+/// Changes the crosshair color to "#RRGGBB" or "#RRGGBBAA" and saves it.
+pub fn set_color(app: &AppHandle, color: &str) -> Result<(), String> {
+    let color = crosshair_engine::color::parse_hex(color).ok_or(format!("Invalid color \"{}\"", color))?;
+    let mut config = live_config()?;
+    config.color = color;
+    apply_config(app, config)
+}
\ No newline at end of file
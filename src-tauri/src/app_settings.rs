@@ -0,0 +1,165 @@
+//! App behavior settings, kept in settings.json apart from the crosshair
+//! config so presets and shared codes never carry them.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::gamepad::GamepadSettings;
+use crate::hotkeys::DoublePressAction;
+use crate::randomizer::RandomizeConstraints;
+use crate::storage::{self, SETTINGS_FILE};
+use crosshair_engine::{ClickVisualizer, ClockDisplay, CrosshairConfig, Grid, KeystrokeDisplay};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub start_minimized: bool,      // Only the tray icon shows at startup
+    pub start_disabled: bool,       // The crosshair starts hidden
+    pub autostart: bool,            // Start with Windows, mirrors the Run registry entry
+    pub hotkeys: HotkeySettings,
+    pub gamepad: GamepadSettings,   // Controller button combos
+    pub notifications: NotificationSettings,
+    pub sounds: SoundSettings,
+    pub randomizer: RandomizeConstraints,  // Used by the randomize hotkey
+    pub capture_visible: bool,      // false keeps the crosshair out of recordings and screenshots
+    pub topmost_interval_ms: u32,   // How often the overlay re-asserts topmost, 0 disables the watchdog
+    pub show_grid: bool,            // The alignment grid is drawn, whether or not the crosshair is
+    pub grid: Grid,
+    pub keystrokes: KeystrokeDisplay,  // Recently pressed keys in a screen corner, for streaming
+    pub click_visualizer: ClickVisualizer,  // Rings on left/right clicks, for tutorials and streams
+    pub clock: ClockDisplay,        // Current time and session length in a screen corner
+    pub monitor: Option<usize>,     // Index from list_monitors, None = primary
+    pub language: String,           // UI language code, applied by the settings UI
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotkeySettings {
+    pub toggle: String,  // e.g. "F9" or "Ctrl+Shift+X"
+    pub undo: Option<String>,  // Step back through config changes, None = no hotkey
+    pub redo: Option<String>,
+    pub randomize: Option<String>,  // Applies a random crosshair within `randomizer`
+    pub grid: Option<String>,       // Shows or hides the alignment grid
+    pub opacity_up: Option<String>,    // Opacity +10%
+    pub opacity_down: Option<String>,  // Opacity -10%
+    pub size_up: Option<String>,       // Size +1 pixel
+    pub size_down: Option<String>,     // Size -1 pixel
+    pub cycle_style: Option<String>,   // Classic, Dot, Circle, Square, T, Custom and around
+    pub double_toggle: Option<DoublePressAction>,  // Double press of `toggle`, None = toggles twice
+    pub double_press_ms: u32,          // Most time between the two presses of a double press
+    pub preview: Option<String>,       // Shows `preview_preset` while held
+    pub preview_preset: Option<String>,  // Preset id
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub on_toggle: bool,  // Notify when the toggle hotkey shows or hides the crosshair
+    pub on_preset: bool,  // Notify when a preset or game profile is applied
+    pub osd: bool,        // Flash the preset name next to the crosshair when one is applied
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundSettings {
+    pub enabled: bool,              // Play a sound when the toggle hotkey is pressed
+    pub on_sound: Option<String>,   // WAV file to play instead of the bundled one
+    pub off_sound: Option<String>,
+}
+
+/// The Run registry entry, see `autostart`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AutostartStatus {
+    pub enabled: bool,
+    pub minimized: bool,  // Started with --minimized, so only the tray icon shows
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            start_minimized: false,
+            start_disabled: false,
+            autostart: false,
+            hotkeys: HotkeySettings::default(),
+            gamepad: GamepadSettings::default(),
+            notifications: NotificationSettings::default(),
+            sounds: SoundSettings::default(),
+            randomizer: RandomizeConstraints::default(),
+            capture_visible: true,
+            topmost_interval_ms: 1000,
+            show_grid: false,
+            grid: Grid::default(),
+            keystrokes: KeystrokeDisplay::default(),
+            click_visualizer: ClickVisualizer::default(),
+            clock: ClockDisplay::default(),
+            monitor: None,
+            language: "en".to_string(),
+        }
+    }
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            on_toggle: false,
+            on_preset: false,
+            osd: true,
+        }
+    }
+}
+
+impl Default for HotkeySettings {
+    fn default() -> Self {
+        Self {
+            toggle: "F9".to_string(),
+            undo: None,
+            redo: None,
+            randomize: None,
+            grid: None,
+            opacity_up: None,
+            opacity_down: None,
+            size_up: None,
+            size_down: None,
+            cycle_style: None,
+            double_toggle: None,
+            double_press_ms: 300,
+            preview: None,
+            preview_preset: None,
+        }
+    }
+}
+
+// None until loaded, read as the defaults
+static SETTINGS: Mutex<Option<AppSettings>> = Mutex::new(None);
+
+// start_disabled only applies to the first config loaded after launch
+static STARTUP_CONFIG_LOADED: AtomicBool = AtomicBool::new(false);
+
+/// Loads settings.json into memory.
+pub fn load_settings() -> Result<(), String> {
+    let settings_path = storage::file_path(SETTINGS_FILE)?;
+    let settings = storage::read_json::<AppSettings>(&settings_path)?.unwrap_or_default();
+    *SETTINGS.lock().unwrap() = Some(settings);
+    Ok(())
+}
+
+pub fn get_settings() -> AppSettings {
+    SETTINGS.lock().unwrap().clone().unwrap_or_default()
+}
+
+/// Stores and saves `settings`. Applying them is up to the caller.
+pub fn save_settings(settings: &AppSettings) -> Result<(), String> {
+    let settings_path = storage::file_path(SETTINGS_FILE)?;
+    storage::write_json(&settings_path, settings)?;
+    *SETTINGS.lock().unwrap() = Some(settings.clone());
+    Ok(())
+}
+
+/// Applies the startup settings to the config loaded at launch; later loads
+/// are left alone.
+pub fn apply_startup_settings(config: &mut CrosshairConfig) {
+    if !STARTUP_CONFIG_LOADED.swap(true, Ordering::SeqCst) && get_settings().start_disabled {
+        config.enabled = false;
+    }
+}
\ No newline at end of file
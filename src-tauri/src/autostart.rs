@@ -0,0 +1,98 @@
+//! Start with Windows through the current user's Run registry key.
+
+use windows::{
+    core::{w, PCWSTR},
+    Win32::{
+        Foundation::ERROR_FILE_NOT_FOUND,
+        System::Registry::{
+            RegCloseKey, RegDeleteValueW, RegGetValueW, RegOpenKeyExW, RegSetValueExW, HKEY,
+            HKEY_CURRENT_USER, KEY_SET_VALUE, REG_SZ, RRF_RT_REG_SZ,
+        },
+    },
+};
+
+use crate::app_settings::AutostartStatus;
+
+const RUN_KEY: PCWSTR = w!("Software\\Microsoft\\Windows\\CurrentVersion\\Run");
+const VALUE_NAME: PCWSTR = w!("Crosshair Overlay");
+
+pub fn get_autostart() -> Result<AutostartStatus, String> {
+    let command = match read_run_value()? {
+        Some(command) => command,
+        None => return Ok(AutostartStatus { enabled: false, minimized: false }),
+    };
+    
+    Ok(AutostartStatus {
+        enabled: true,
+        minimized: command.contains("--minimized"),
+    })
+}
+
+/// Adds or removes the Run entry. The entry always points at the running exe,
+/// so enabling again after moving the app fixes a stale path.
+pub fn set_autostart(enabled: bool, minimized: bool) -> Result<(), String> {
+    // Built before the key is opened, so a failure here can't leak it
+    let data: Option<Vec<u8>> = if enabled {
+        let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+        let mut command = format!("\"{}\"", exe.display());
+        if minimized {
+            command.push_str(" --minimized");
+        }
+        
+        // REG_SZ data is the UTF-16 string including its terminator
+        Some(
+            command
+                .encode_utf16()
+                .chain(Some(0))
+                .flat_map(u16::to_le_bytes)
+                .collect(),
+        )
+    } else {
+        None
+    };
+    
+    unsafe {
+        let mut key = HKEY::default();
+        RegOpenKeyExW(HKEY_CURRENT_USER, RUN_KEY, 0, KEY_SET_VALUE, &mut key)
+            .ok()
+            .map_err(|e| e.to_string())?;
+        
+        let result = match data {
+            Some(data) => RegSetValueExW(key, VALUE_NAME, 0, REG_SZ, Some(&data)).ok(),
+            None => match RegDeleteValueW(key, VALUE_NAME) {
+                ERROR_FILE_NOT_FOUND => Ok(()),
+                status => status.ok(),
+            },
+        };
+        
+        let _ = RegCloseKey(key);
+        result.map_err(|e| e.to_string())
+    }
+}
+
+fn read_run_value() -> Result<Option<String>, String> {
+    unsafe {
+        let mut size = 0u32;
+        let status = RegGetValueW(HKEY_CURRENT_USER, RUN_KEY, VALUE_NAME, RRF_RT_REG_SZ, None, None, Some(&mut size));
+        if status == ERROR_FILE_NOT_FOUND {
+            return Ok(None);
+        }
+        status.ok().map_err(|e| e.to_string())?;
+        
+        let mut buffer = vec![0u16; (size as usize).div_ceil(2)];
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            RUN_KEY,
+            VALUE_NAME,
+            RRF_RT_REG_SZ,
+            None,
+            Some(buffer.as_mut_ptr().cast()),
+            Some(&mut size),
+        )
+        .ok()
+        .map_err(|e| e.to_string())?;
+        
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        Ok(Some(String::from_utf16_lossy(&buffer[..len])))
+    }
+}
\ No newline at end of file
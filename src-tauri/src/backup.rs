@@ -0,0 +1,141 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::storage::{write_atomic, CONFIG_FILE, PRESETS_FILE, PROFILES_FILE};
+
+// Number of backups kept per file before the oldest ones are removed
+const MAX_BACKUPS: usize = 10;
+
+// Files that are backed up before being overwritten
+const BACKED_UP_FILES: &[&str] = &[CONFIG_FILE, PRESETS_FILE, PROFILES_FILE];
+
+#[derive(Clone, Serialize)]
+pub struct BackupInfo {
+    pub name: String,
+    pub file: String,
+    pub created_at: u64,
+    pub size: u64,
+}
+
+fn backups_dir(config_dir: &Path) -> PathBuf {
+    config_dir.join("backups")
+}
+
+fn file_stem(file_name: &str) -> &str {
+    file_name.strip_suffix(".json").unwrap_or(file_name)
+}
+
+// Backup names look like `config-1700000000000.json`
+fn parse_backup_name(name: &str) -> Option<(String, u64)> {
+    let stem = name.strip_suffix(".json")?;
+    let (file_stem, timestamp) = stem.rsplit_once('-')?;
+    let timestamp = timestamp.parse::<u64>().ok()?;
+    let file = format!("{}.json", file_stem);
+    
+    if !BACKED_UP_FILES.contains(&file.as_str()) {
+        return None;
+    }
+    
+    Some((file, timestamp))
+}
+
+/// Copies `path` into the backups folder (if it exists) and prunes old backups
+/// so that only the newest `MAX_BACKUPS` copies of that file are kept. Files
+/// not listed in `BACKED_UP_FILES` are skipped.
+pub fn backup_file(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+    
+    let config_dir = path.parent().ok_or("Invalid file path")?;
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid file name")?;
+    
+    if !BACKED_UP_FILES.contains(&file_name) {
+        return Ok(());
+    }
+    
+    let backups_dir = backups_dir(config_dir);
+    std::fs::create_dir_all(&backups_dir)
+        .map_err(|e| e.to_string())?;
+    
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_millis();
+    
+    let backup_name = format!("{}-{}.json", file_stem(file_name), timestamp);
+    std::fs::copy(path, backups_dir.join(backup_name))
+        .map_err(|e| e.to_string())?;
+    
+    prune_backups(config_dir, file_name)
+}
+
+fn prune_backups(config_dir: &Path, file_name: &str) -> Result<(), String> {
+    let mut backups: Vec<BackupInfo> = list_backups(config_dir)?
+        .into_iter()
+        .filter(|b| b.file == file_name)
+        .collect();
+    
+    if backups.len() <= MAX_BACKUPS {
+        return Ok(());
+    }
+    
+    // list_backups returns newest first, so everything past the limit is stale
+    for backup in backups.split_off(MAX_BACKUPS) {
+        let _ = std::fs::remove_file(backups_dir(config_dir).join(&backup.name));
+    }
+    
+    Ok(())
+}
+
+/// Lists all backups in the config directory, newest first.
+pub fn list_backups(config_dir: &Path) -> Result<Vec<BackupInfo>, String> {
+    let backups_dir = backups_dir(config_dir);
+    
+    if !backups_dir.exists() {
+        return Ok(Vec::new());
+    }
+    
+    let mut backups = Vec::new();
+    
+    for entry in std::fs::read_dir(&backups_dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        
+        if let Some((file, created_at)) = parse_backup_name(&name) {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            backups.push(BackupInfo { name, file, created_at, size });
+        }
+    }
+    
+    backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+    
+    Ok(backups)
+}
+
+/// Restores the backup `name` over the file it was taken from. The current
+/// file is backed up first so a restore can itself be undone.
+pub fn restore_backup(config_dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let (file, _) = parse_backup_name(name)
+        .ok_or_else(|| format!("Invalid backup name: {}", name))?;
+    
+    let backup_path = backups_dir(config_dir).join(name);
+    if !backup_path.exists() {
+        return Err(format!("Backup not found: {}", name));
+    }
+    
+    // Read the backup before rotating, in case it is the oldest one and gets pruned
+    let contents = std::fs::read(&backup_path)
+        .map_err(|e| e.to_string())?;
+    
+    let target_path = config_dir.join(file);
+    backup_file(&target_path)?;
+    
+    write_atomic(&target_path, &contents)?;
+    
+    Ok(target_path)
+}
\ No newline at end of file
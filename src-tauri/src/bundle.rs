@@ -0,0 +1,258 @@
+//! One-file export of everything worth moving to another PC: the config,
+//! presets, app settings, game profiles, the process watch lists and the
+//! images image-style crosshairs use. The HTTP API and sync settings stay
+//! behind, since they hold tokens and machine-specific addresses.
+//!
+//! A bundle is a zip with a `manifest.json`. Image paths inside the bundled
+//! JSON point at `images/...` entries and are pointed at the local copies
+//! again on import. Imported files must parse as what they hold, and their
+//! configs must pass preset_check like any other import.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::app_settings::AppSettings;
+use crate::backup::backup_file;
+use crate::preset_check;
+use crate::process_watch::WatchData;
+use crate::profiles::ProfilesData;
+use crate::storage::{
+    self, write_atomic, CONFIG_FILE, IMAGES_DIR, PRESETS_FILE, PROCESS_WATCH_FILE, PROFILES_FILE,
+    SETTINGS_FILE,
+};
+use crate::FavoritesData;
+use crosshair_engine::CrosshairConfig;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const FORMAT: &str = "crosshair-overlay-bundle";
+
+/// Revision of the bundle layout written by this version.
+pub const BUNDLE_VERSION: u32 = 1;
+
+// Data files carried in a bundle, all optional
+const BUNDLED_FILES: &[&str] = &[
+    CONFIG_FILE,
+    PRESETS_FILE,
+    SETTINGS_FILE,
+    PROFILES_FILE,
+    PROCESS_WATCH_FILE,
+];
+
+// Nothing legitimately in a bundle comes close; guards against zip bombs
+const MAX_ENTRY_BYTES: u64 = 32 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format: String,
+    pub version: u32,
+    pub app_version: String,
+    pub created_at: u64,      // Unix seconds
+    pub files: Vec<String>,   // Data files, by their name in the config directory
+    pub images: Vec<String>,  // Entries under images/
+}
+
+/// Writes all app data to a bundle at `path`.
+pub fn export_all(path: &Path) -> Result<BundleManifest, String> {
+    let config_dir = storage::config_dir()?;
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    
+    // Images are renamed after their position so different folders can't clash
+    let mut images: HashMap<String, String> = HashMap::new();
+    let mut files = Vec::new();
+    for name in BUNDLED_FILES {
+        let Some(mut value) = storage::read_json::<Value>(&config_dir.join(name))? else {
+            continue;
+        };
+        rewrite_image_paths(&mut value, &mut |original: &str| {
+            if !Path::new(original).is_file() {
+                return None;
+            }
+            let count = images.len();
+            let entry = images.entry(original.to_string()).or_insert_with(|| {
+                let file_name = Path::new(original).file_name().map_or("image".into(), |n| n.to_string_lossy());
+                format!("{}/{}-{}", IMAGES_DIR, count, file_name)
+            });
+            Some(entry.clone())
+        });
+        
+        let contents = serde_json::to_vec_pretty(&value).map_err(|e| e.to_string())?;
+        zip.start_file(*name, options).map_err(|e| e.to_string())?;
+        zip.write_all(&contents).map_err(|e| e.to_string())?;
+        files.push(name.to_string());
+    }
+    
+    for (original, entry) in &images {
+        let contents = std::fs::read(original).map_err(|e| format!("Failed to read {}: {}", original, e))?;
+        zip.start_file(entry.as_str(), options).map_err(|e| e.to_string())?;
+        zip.write_all(&contents).map_err(|e| e.to_string())?;
+    }
+    
+    let manifest = BundleManifest {
+        format: FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs()),
+        files,
+        images: images.into_values().collect(),
+    };
+    let contents = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    zip.start_file(MANIFEST_FILE, options).map_err(|e| e.to_string())?;
+    zip.write_all(&contents).map_err(|e| e.to_string())?;
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(manifest)
+}
+
+/// Replaces the app data with the bundle at `path`. Files the bundle doesn't
+/// have are left alone, and replaced ones are backed up first. Everything is
+/// read and checked before anything is written.
+pub fn import_all(path: &Path) -> Result<BundleManifest, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut zip = ZipArchive::new(file).map_err(|_| "The file is not a crosshair overlay bundle".to_string())?;
+    
+    let manifest: BundleManifest = serde_json::from_slice(&read_entry(&mut zip, MANIFEST_FILE)?)
+        .map_err(|_| "The bundle's manifest is damaged".to_string())?;
+    if manifest.format != FORMAT {
+        return Err("The file is not a crosshair overlay bundle".to_string());
+    }
+    if manifest.version > BUNDLE_VERSION {
+        return Err(format!(
+            "The bundle was made by a newer version ({}) of the app, update to import it",
+            manifest.app_version
+        ));
+    }
+    
+    let config_dir = storage::config_dir()?;
+    let images_dir = config_dir.join(IMAGES_DIR);
+    let mut images = Vec::new();
+    for entry in &manifest.images {
+        let file_name = entry
+            .strip_prefix(&format!("{}/", IMAGES_DIR))
+            .filter(|name| !name.is_empty() && !name.contains(['/', '\\', ':']) && *name != "..")
+            .ok_or_else(|| format!("Invalid image entry \"{}\" in the bundle", entry))?;
+        images.push((entry.clone(), images_dir.join(file_name), read_entry(&mut zip, entry)?));
+    }
+    
+    let local_paths: HashMap<&str, String> = images
+        .iter()
+        .map(|(entry, local, _)| (entry.as_str(), local.to_string_lossy().into_owned()))
+        .collect();
+    let mut files = Vec::new();
+    for name in &manifest.files {
+        if !BUNDLED_FILES.contains(&name.as_str()) {
+            return Err(format!("Unexpected file \"{}\" in the bundle", name));
+        }
+        let mut value: Value = serde_json::from_slice(&read_entry(&mut zip, name)?)
+            .map_err(|_| format!("{} in the bundle is damaged", name))?;
+        rewrite_image_paths(&mut value, &mut |entry: &str| local_paths.get(entry).cloned());
+        check_file(name, &value)?;
+        let contents = serde_json::to_vec_pretty(&value).map_err(|e| e.to_string())?;
+        files.push((config_dir.join(name), contents));
+    }
+    
+    if !images.is_empty() {
+        std::fs::create_dir_all(&images_dir).map_err(|e| e.to_string())?;
+    }
+    for (_, local, contents) in &images {
+        if std::fs::read(local).is_ok_and(|existing| existing == *contents) {
+            continue;
+        }
+        backup_image(local)?;
+        write_atomic(local, contents)?;
+    }
+    for (path, contents) in &files {
+        backup_file(path)?;
+        write_atomic(path, contents)?;
+    }
+    Ok(manifest)
+}
+
+// Parses a bundled file as what it holds, so a damaged one is caught before
+// anything is replaced
+fn check_file(name: &str, value: &Value) -> Result<(), String> {
+    let invalid = |e: serde_json::Error| format!("{} in the bundle is invalid: {}", name, e);
+    match name {
+        CONFIG_FILE => {
+            let config: CrosshairConfig = serde_json::from_value(value.clone()).map_err(invalid)?;
+            preset_check::check_config(&config).map_err(|e| format!("{} in the bundle: {}", name, e))
+        }
+        PRESETS_FILE => {
+            let favorites: FavoritesData = serde_json::from_value(value.clone()).map_err(invalid)?;
+            for preset in &favorites.presets {
+                preset_check::check_config(&preset.config)
+                    .map_err(|e| format!("Preset \"{}\" in the bundle: {}", preset.name, e))?;
+            }
+            Ok(())
+        }
+        SETTINGS_FILE => serde_json::from_value::<AppSettings>(value.clone()).map(|_| ()).map_err(invalid),
+        PROFILES_FILE => serde_json::from_value::<ProfilesData>(value.clone()).map(|_| ()).map_err(invalid),
+        PROCESS_WATCH_FILE => serde_json::from_value::<WatchData>(value.clone()).map(|_| ()).map_err(invalid),
+        _ => Err(format!("Unexpected file \"{}\" in the bundle", name)),
+    }
+}
+
+// Copies an image about to be replaced into images/backups, the way
+// backup_file keeps data files
+fn backup_image(path: &Path) -> Result<(), String> {
+    if !path.is_file() {
+        return Ok(());
+    }
+    let backups_dir = path.parent().ok_or("Invalid image path")?.join("backups");
+    std::fs::create_dir_all(&backups_dir).map_err(|e| e.to_string())?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+    let file_name = path.file_name().ok_or("Invalid image path")?.to_string_lossy();
+    std::fs::copy(path, backups_dir.join(format!("{}-{}", timestamp, file_name)))
+        .map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+fn read_entry(zip: &mut ZipArchive<File>, name: &str) -> Result<Vec<u8>, String> {
+    let entry = zip.by_name(name).map_err(|_| format!("The bundle has no {}", name))?;
+    let mut contents = Vec::new();
+    entry
+        .take(MAX_ENTRY_BYTES + 1)
+        .read_to_end(&mut contents)
+        .map_err(|e| format!("Failed to read {} from the bundle: {}", name, e))?;
+    if contents.len() as u64 > MAX_ENTRY_BYTES {
+        return Err(format!("{} in the bundle is too large", name));
+    }
+    Ok(contents)
+}
+
+// Replaces the path of every image-style crosshair in `value`, wherever it
+// is nested (presets, profiles, secondary configs), with what `map` returns
+// for it. Paths `map` returns None for are kept.
+fn rewrite_image_paths(value: &mut Value, map: &mut dyn FnMut(&str) -> Option<String>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Object(image)) = object.get_mut("Image") {
+                if let Some(Value::String(path)) = image.get_mut("path") {
+                    if let Some(mapped) = map(path) {
+                        *path = mapped;
+                    }
+                }
+            }
+            for child in object.values_mut() {
+                rewrite_image_paths(child, map);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                rewrite_image_paths(item, map);
+            }
+        }
+        _ => {}
+    }
+}
\ No newline at end of file
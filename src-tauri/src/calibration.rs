@@ -0,0 +1,69 @@
+//! Center calibration for games whose aim point sits a few pixels off the
+//! screen center. The settings UI shows a capture of the area around the
+//! center, the user clicks the game's own reticle in it, and that offset
+//! becomes the crosshair position.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::actions;
+use crosshair_engine::{backend, CrosshairConfig};
+
+const MIN_RADIUS: i32 = 16;
+const MAX_RADIUS: i32 = 200;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationCapture {
+    pub image: String,  // PNG data URL with the uncorrected aim point in its center pixel
+    pub width: i32,
+    pub height: i32,
+}
+
+// Side of the last capture, which clicks are relative to
+static CAPTURE_SIZE: Mutex<Option<i32>> = Mutex::new(None);
+
+/// Captures the screen `radius` pixels each way around the center, without
+/// the crosshair.
+pub fn capture(radius: i32) -> Result<CalibrationCapture, String> {
+    let radius = radius.clamp(MIN_RADIUS, MAX_RADIUS);
+    let pixels = backend().capture_anchor_area(radius)?;
+    let size = radius * 2 + 1;
+    
+    let rgba: Vec<u8> = pixels
+        .iter()
+        .flat_map(|&pixel| [(pixel >> 16) as u8, (pixel >> 8) as u8, pixel as u8, 255])
+        .collect();
+    let mut png_data = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png_data, size as u32, size as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(&rgba).map_err(|e| e.to_string())?;
+    }
+    
+    *CAPTURE_SIZE.lock().unwrap() = Some(size);
+    Ok(CalibrationCapture {
+        image: format!("data:image/png;base64,{}", STANDARD.encode(png_data)),
+        width: size,
+        height: size,
+    })
+}
+
+/// Moves the crosshair onto the reticle clicked at (`x`, `y`) in the last
+/// capture, and saves it.
+pub fn apply(app: &AppHandle, x: i32, y: i32) -> Result<CrosshairConfig, String> {
+    let size = CAPTURE_SIZE.lock().unwrap().ok_or("Capture the screen before calibrating")?;
+    if !(0..size).contains(&x) || !(0..size).contains(&y) {
+        return Err(format!("({}, {}) is outside the {}x{} capture", x, y, size, size));
+    }
+    
+    let center = size / 2;
+    let mut config = actions::live_config()?;
+    config.position_x = (x - center) as f32;
+    config.position_y = (y - center) as f32;
+    actions::apply_config(app, config.clone())?;
+    Ok(config)
+}
\ No newline at end of file
@@ -0,0 +1,107 @@
+//! Command-line control. Flags passed to a second launch of the exe are
+//! forwarded to the running instance by the single-instance plugin, so
+//! shortcuts and scripts can run e.g.
+//!
+//! ```text
+//! crosshair-overlay.exe --toggle
+//! crosshair-overlay.exe --preset 1712345678901
+//! crosshair-overlay.exe --set color=#FF0000 --set size=8
+//! ```
+//!
+//! `--set` takes any top-level config field; the value is read as JSON when
+//! it parses, otherwise as a string.
+//!
+//! `--headless` and `--minimized` only apply to the first launch. Headless
+//! doesn't create the settings window until it's opened from the tray;
+//! minimized creates it hidden (used when starting with Windows).
+
+use serde_json::{Map, Value};
+use tauri::AppHandle;
+use tracing::{error, warn};
+
+use crate::actions::{apply_preset, live_config, patch_config, set_enabled};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    Toggle,
+    Show,
+    Hide,
+    Preset(String),
+    Set(String, String),
+}
+
+/// True when `args` contains `flag`, e.g. `--headless`.
+pub fn has_flag(args: &[String], flag: &str) -> bool {
+    args.iter().skip(1).any(|arg| arg == flag)
+}
+
+/// Parses the control flags in `args` (the first entry is the exe path).
+/// Other arguments, like share links, are skipped.
+pub fn parse(args: &[String]) -> Result<Vec<CliCommand>, String> {
+    let mut commands = Vec::new();
+    let mut args = args.iter().skip(1);
+    
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{} needs a value", flag))
+        };
+        match arg.as_str() {
+            "--toggle" => commands.push(CliCommand::Toggle),
+            "--show" => commands.push(CliCommand::Show),
+            "--hide" => commands.push(CliCommand::Hide),
+            "--preset" => commands.push(CliCommand::Preset(value("--preset")?)),
+            "--set" => {
+                let setting = value("--set")?;
+                let (key, value) = setting
+                    .split_once('=')
+                    .ok_or_else(|| format!("Expected --set key=value, got \"{}\"", setting))?;
+                commands.push(CliCommand::Set(key.trim().to_string(), value.trim().to_string()));
+            }
+            _ => {}
+        }
+    }
+    
+    Ok(commands)
+}
+
+/// Parses and runs the control flags in `args`, logging failures. Returns
+/// false when there were none.
+pub fn run(app: &AppHandle, args: &[String]) -> bool {
+    let commands = match parse(args) {
+        Ok(commands) => commands,
+        Err(e) => {
+            warn!("Invalid command line: {}", e);
+            return true;
+        }
+    };
+    
+    for command in &commands {
+        if let Err(e) = run_command(app, command) {
+            error!("Command line {:?} failed: {}", command, e);
+        }
+    }
+    !commands.is_empty()
+}
+
+fn run_command(app: &AppHandle, command: &CliCommand) -> Result<(), String> {
+    match command {
+        CliCommand::Toggle => set_enabled(app, !live_config()?.enabled),
+        CliCommand::Show => set_enabled(app, true),
+        CliCommand::Hide => set_enabled(app, false),
+        CliCommand::Preset(id) => {
+            if apply_preset(app, id)? {
+                Ok(())
+            } else {
+                Err(format!("Preset not found: {}", id))
+            }
+        }
+        CliCommand::Set(key, value) => {
+            let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.clone()));
+            let mut patch = Map::new();
+            patch.insert(key.clone(), value);
+            patch_config(app, Value::Object(patch)).map(|_| ())
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,124 @@
+//! Diagnostics report for bug reports: everything about the app's state that
+//! tends to matter for overlay and hotkey problems, as one JSON blob.
+
+use serde_json::{json, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::actions::live_config;
+use crate::{app_settings, hotkeys};
+use crosshair_engine::backend;
+
+#[cfg(windows)]
+use windows::{
+    core::{w, PCWSTR},
+    Win32::{
+        System::Registry::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_DWORD, RRF_RT_REG_SZ},
+        UI::HiDpi::GetDpiForSystem,
+    },
+};
+
+#[cfg(windows)]
+const VERSION_KEY: PCWSTR = w!("SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion");
+
+/// Collects the report as pretty-printed JSON.
+pub fn generate() -> Result<String, String> {
+    let overlay = backend();
+    let mut report = json!({
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "generated_at": SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "hotkeys": hotkeys::toggle_hotkey_status(),
+        "settings": app_settings::get_settings(),
+        "config": live_config().ok(),
+        // Null for whatever the backend doesn't support
+        "overlay": {
+            "renderer": overlay.name(),
+            "status": overlay.status().ok(),
+            "edit_mode": overlay.is_edit_mode().ok(),
+            "render_metrics": overlay.render_metrics().ok(),
+            "attached_window": overlay.attached_window().ok().flatten(),
+        },
+    });
+    
+    #[cfg(windows)]
+    {
+        let dpi = unsafe { GetDpiForSystem() };
+        let report = report.as_object_mut().ok_or("Invalid report")?;
+        report.insert("windows".to_string(), windows_version());
+        report.insert(
+            "display".to_string(),
+            json!({
+                "dpi": dpi,
+                "scale_percent": dpi * 100 / 96,
+                "monitors": overlay.list_monitors().ok(),
+            }),
+        );
+    }
+    
+    serde_json::to_string_pretty(&report).map_err(|e| e.to_string())
+}
+
+// Product name, feature update (e.g. "23H2") and build with its revision
+#[cfg(windows)]
+fn windows_version() -> Value {
+    let build = read_string(w!("CurrentBuild"));
+    let revision = read_dword(w!("UBR"));
+    json!({
+        "product": read_string(w!("ProductName")),
+        "version": read_string(w!("DisplayVersion")),
+        "build": match (build, revision) {
+            (Some(build), Some(revision)) => Some(format!("{}.{}", build, revision)),
+            (build, _) => build,
+        },
+    })
+}
+
+#[cfg(windows)]
+fn read_string(name: PCWSTR) -> Option<String> {
+    unsafe {
+        let mut size = 0u32;
+        RegGetValueW(HKEY_LOCAL_MACHINE, VERSION_KEY, name, RRF_RT_REG_SZ, None, None, Some(&mut size))
+            .ok()
+            .ok()?;
+        
+        let mut buffer = vec![0u16; (size as usize).div_ceil(2)];
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            VERSION_KEY,
+            name,
+            RRF_RT_REG_SZ,
+            None,
+            Some(buffer.as_mut_ptr().cast()),
+            Some(&mut size),
+        )
+        .ok()
+        .ok()?;
+        
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        Some(String::from_utf16_lossy(&buffer[..len]))
+    }
+}
+
+#[cfg(windows)]
+fn read_dword(name: PCWSTR) -> Option<u32> {
+    unsafe {
+        let mut value = 0u32;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            VERSION_KEY,
+            name,
+            RRF_RT_REG_DWORD,
+            None,
+            Some((&mut value as *mut u32).cast()),
+            Some(&mut size),
+        )
+        .ok()
+        .ok()?;
+        Some(value)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,176 @@
+//! WebSocket event stream served by the HTTP API at `GET /events`.
+//!
+//! Every connected client receives the app's crosshair events as text frames
+//! of the form `{"event": "toggled", "payload": ...}`. Browsers can't set
+//! headers on WebSockets, so the token may also be passed as `?token=`.
+//!
+//! Each client has a queue drained by its own writer thread, so a stalled
+//! client never holds up the app's event emitters; one that falls a whole
+//! queue behind is dropped.
+
+use base64::Engine;
+use crossbeam_channel::{bounded, Receiver, Sender};
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Listener};
+
+// Fixed by RFC 6455 for computing Sec-WebSocket-Accept
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+// App events forwarded to clients, and the name they get on the stream
+const FORWARDED_EVENTS: &[(&str, &str)] = &[
+    ("crosshair-toggled", "toggled"),
+    ("crosshair-config-changed", "config-changed"),
+    ("preset-applied", "preset-applied"),
+    ("profile-applied", "profile-switched"),
+    ("preset-preview", "preset-preview"),
+];
+
+// Client messages are only read to answer pings and closes
+const MAX_CLIENT_PAYLOAD: u64 = 4096;
+
+// Frames waiting for a slow client before it is dropped
+const QUEUE_FRAMES: usize = 64;
+
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+type Frame = Arc<[u8]>;
+
+// Queues of the connected clients, only ever sent to without blocking
+static CLIENTS: Mutex<Vec<Sender<Frame>>> = Mutex::new(Vec::new());
+
+/// Starts forwarding app events to connected clients. Call once at startup.
+pub fn forward_events(app: &AppHandle) {
+    for &(event, name) in FORWARDED_EVENTS {
+        app.listen_any(event, move |event| {
+            broadcast(&format!("{{\"event\":\"{}\",\"payload\":{}}}", name, event.payload()));
+        });
+    }
+}
+
+/// Completes the WebSocket handshake for a request carrying `key` as its
+/// Sec-WebSocket-Key, adds the connection to the stream and serves it until
+/// it closes. The caller's thread reads from the client meanwhile.
+pub fn serve(stream: TcpStream, key: &str) -> std::io::Result<()> {
+    let digest = sha1_smol::Sha1::from(format!("{}{}", key, WEBSOCKET_GUID)).digest().bytes();
+    let accept = base64::engine::general_purpose::STANDARD.encode(digest);
+    
+    let mut writer = &stream;
+    write!(
+        writer,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept
+    )?;
+    
+    // Events can be minutes apart, so the handshake timeout must not apply
+    stream.set_read_timeout(None)?;
+    let writer = stream.try_clone()?;
+    let (sender, receiver) = bounded(QUEUE_FRAMES);
+    CLIENTS.lock().unwrap().push(sender.clone());
+    std::thread::spawn(move || write_frames(writer, receiver));
+    read_frames(stream, sender);
+    Ok(())
+}
+
+fn broadcast(message: &str) {
+    let frame: Frame = encode_frame(OPCODE_TEXT, message.as_bytes()).into();
+    let mut clients = CLIENTS.lock().unwrap();
+    // A full queue is a stalled client; dropping its sender ends the writer,
+    // which closes the connection
+    clients.retain(|client| client.try_send(frame.clone()).is_ok());
+}
+
+// Sends queued frames until a close frame, a failed write or the client
+// being dropped from CLIENTS, then closes the connection
+fn write_frames(mut stream: TcpStream, receiver: Receiver<Frame>) {
+    for frame in receiver {
+        if stream.write_all(&frame).is_err() || frame[0] & 0x0F == OPCODE_CLOSE {
+            break;
+        }
+    }
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+// Server frames are never masked; the length takes 1, 3 or 9 bytes
+fn encode_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+// Runs until the client closes or errors, then has the writer close the
+// connection. Replies go through the client's queue like any other frame.
+fn read_frames(mut stream: TcpStream, sender: Sender<Frame>) {
+    let mut close_payload = Vec::new();
+    loop {
+        let mut header = [0u8; 2];
+        if stream.read_exact(&mut header).is_err() {
+            break;
+        }
+        let opcode = header[0] & 0x0F;
+        let masked = header[1] & 0x80 != 0;
+        let len = match header[1] & 0x7F {
+            126 => {
+                let mut ext = [0u8; 2];
+                if stream.read_exact(&mut ext).is_err() {
+                    break;
+                }
+                u16::from_be_bytes(ext) as u64
+            }
+            127 => {
+                let mut ext = [0u8; 8];
+                if stream.read_exact(&mut ext).is_err() {
+                    break;
+                }
+                u64::from_be_bytes(ext)
+            }
+            len => len as u64,
+        };
+        if len > MAX_CLIENT_PAYLOAD {
+            break;
+        }
+        
+        let mut mask = [0u8; 4];
+        if masked && stream.read_exact(&mut mask).is_err() {
+            break;
+        }
+        let mut payload = vec![0u8; len as usize];
+        if stream.read_exact(&mut payload).is_err() {
+            break;
+        }
+        if masked {
+            for (i, byte) in payload.iter_mut().enumerate() {
+                *byte ^= mask[i % 4];
+            }
+        }
+        
+        match opcode {
+            OPCODE_PING if sender.try_send(encode_frame(OPCODE_PONG, &payload).into()).is_err() => break,
+            OPCODE_CLOSE => {
+                close_payload = payload;
+                break;
+            }
+            _ => {}
+        }
+    }
+    
+    // A client too far behind to queue the close frame is cut off instead
+    if sender.try_send(encode_frame(OPCODE_CLOSE, &close_payload).into()).is_err() {
+        let _ = stream.shutdown(Shutdown::Both);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,394 @@
+//! Imports configs exported by other crosshair overlays, to ease switching
+//! over. Their files are read leniently: known keys are mapped onto a
+//! CrosshairConfig, anything else is ignored, and a file without a single
+//! known key is rejected.
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Read;
+
+use crate::preset_check;
+use crosshair_engine::{color, CrosshairConfig};
+
+// Settings files are tiny; anything much bigger isn't one
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum ExternalFormat {
+    CrosshairX,  // CrossHair X JSON export
+    HudSight,    // HudSight settings INI
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Color,
+    Opacity,              // 0.0-1.0
+    OpacityPercent,       // 0-100
+    TransparencyPercent,  // 0-100, inverse of opacity
+    Size,
+    Thickness,
+    Gap,
+    Outline,
+    OutlineThickness,
+    OutlineColor,
+    Dot,
+    DotSize,
+    DotColor,
+}
+
+// Keys holding each setting, lowercase. Nested JSON keys are joined with
+// '.'; INI keys are looked up with and without their section.
+const CROSSHAIR_X_KEYS: &[(Field, &[&str])] = &[
+    (Field::Color, &["color", "crosshair.color", "lines.color"]),
+    (Field::OpacityPercent, &["opacity", "crosshair.opacity"]),
+    (Field::Opacity, &["alpha"]),
+    (Field::Size, &["length", "size", "lines.length", "crosshair.length"]),
+    (Field::Thickness, &["thickness", "width", "lines.thickness", "crosshair.thickness"]),
+    (Field::Gap, &["gap", "offset", "lines.gap", "crosshair.gap"]),
+    (Field::Outline, &["outline", "outline.enabled", "border", "border.enabled"]),
+    (Field::OutlineThickness, &["outline.thickness", "outline.width", "border.thickness"]),
+    (Field::OutlineColor, &["outline.color", "border.color"]),
+    (Field::Dot, &["dot", "dot.enabled", "centerdot", "center_dot"]),
+    (Field::DotSize, &["dot.size", "dotsize", "dot_size"]),
+    (Field::DotColor, &["dot.color", "dotcolor", "dot_color"]),
+];
+
+// The [Crosshair] keys come first, other sections reuse names like "size"
+const HUDSIGHT_KEYS: &[(Field, &[&str])] = &[
+    (Field::Color, &["crosshair.color", "crosshair.crosshaircolor", "color", "crosshaircolor"]),
+    (Field::OpacityPercent, &["crosshair.opacity", "opacity"]),
+    (Field::TransparencyPercent, &["crosshair.transparency", "transparency"]),
+    (Field::Size, &["crosshair.size", "crosshair.length", "size", "length"]),
+    (Field::Thickness, &["crosshair.thickness", "crosshair.linewidth", "thickness", "linewidth"]),
+    (Field::Gap, &["crosshair.gap", "gap"]),
+    (Field::Outline, &["crosshair.outline", "crosshair.shadow", "outline", "shadow"]),
+    (Field::OutlineThickness, &["outlinethickness", "outlinewidth", "shadowsize"]),
+    (Field::OutlineColor, &["outlinecolor", "shadowcolor"]),
+    (Field::Dot, &["crosshair.dot", "crosshair.centerdot", "dot", "centerdot"]),
+    (Field::DotSize, &["crosshair.dotsize", "dotsize"]),
+    (Field::DotColor, &["crosshair.dotcolor", "dotcolor"]),
+];
+
+/// Reads the `format` file at `path` as a config. Settings the file doesn't
+/// have keep their defaults.
+pub fn import(path: &str, format: ExternalFormat) -> Result<CrosshairConfig, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let mut contents = String::new();
+    file.take(MAX_FILE_BYTES + 1)
+        .read_to_string(&mut contents)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    if contents.len() as u64 > MAX_FILE_BYTES {
+        return Err("The file is too large to be a crosshair config".to_string());
+    }
+    
+    let (fields, keys) = match format {
+        ExternalFormat::CrosshairX => (json_fields(&contents)?, CROSSHAIR_X_KEYS),
+        ExternalFormat::HudSight => (ini_fields(&contents), HUDSIGHT_KEYS),
+    };
+    map_fields(&fields, keys)
+}
+
+// Every scalar in a JSON document by its dotted path
+fn json_fields(contents: &str) -> Result<HashMap<String, String>, String> {
+    let value: Value = serde_json::from_str(contents).map_err(|e| format!("Not a JSON file: {}", e))?;
+    let mut fields = HashMap::new();
+    flatten(&value, String::new(), &mut fields);
+    Ok(fields)
+}
+
+fn flatten(value: &Value, path: String, fields: &mut HashMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let key = key.to_lowercase();
+                let path = if path.is_empty() { key } else { format!("{}.{}", path, key) };
+                flatten(value, path, fields);
+            }
+        }
+        // Colors are sometimes [r, g, b]
+        Value::Array(items) => {
+            let numbers: Vec<String> = items.iter().filter(|item| item.is_number()).map(Value::to_string).collect();
+            if numbers.len() == items.len() {
+                fields.insert(path, numbers.join(","));
+            }
+        }
+        Value::String(text) => {
+            fields.insert(path, text.clone());
+        }
+        Value::Null => {}
+        other => {
+            fields.insert(path, other.to_string());
+        }
+    }
+}
+
+// `key=value` lines, each stored as `section.key` and as plain `key`
+fn ini_fields(contents: &str) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+    let mut section = String::new();
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            section = name.trim().to_lowercase();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        
+        let key = key.trim().to_lowercase();
+        let value = value.trim().trim_matches('"').to_string();
+        if !section.is_empty() {
+            fields.insert(format!("{}.{}", section, key), value.clone());
+        }
+        fields.entry(key).or_insert(value);
+    }
+    fields
+}
+
+fn map_fields(fields: &HashMap<String, String>, keys: &[(Field, &[&str])]) -> Result<CrosshairConfig, String> {
+    let lookup = |field: Field| {
+        keys.iter()
+            .filter(|(known, _)| *known == field)
+            .flat_map(|(_, names)| names.iter())
+            .find_map(|name| fields.get(*name))
+            .map(String::as_str)
+    };
+    let number = |field: Field| lookup(field).and_then(|value| value.trim().parse::<f32>().ok());
+    let percent = |field: Field| number(field).map(|value| value / 100.0);
+    let pixels = |field: Field| number(field).map(|value| value.round().max(0.0) as i32);
+    let sizes = |field: Field| number(field).map(|value| value.max(0.0));
+    let flag = |field: Field| lookup(field).and_then(parse_bool);
+    let color = |field: Field| lookup(field).and_then(parse_color);
+    
+    let mut config = CrosshairConfig::default();
+    let mut found = false;
+    
+    if let Some(value) = color(Field::Color) {
+        config.color = value;
+        found = true;
+    }
+    if let Some(value) = percent(Field::OpacityPercent).or_else(|| number(Field::Opacity)) {
+        config.opacity = opacity(value);
+        found = true;
+    } else if let Some(value) = percent(Field::TransparencyPercent) {
+        config.opacity = opacity(1.0 - value);
+        found = true;
+    }
+    if let Some(value) = sizes(Field::Size) {
+        config.size = value;
+        found = true;
+    }
+    if let Some(value) = sizes(Field::Thickness) {
+        config.thickness = value.max(1.0);
+        found = true;
+    }
+    if let Some(value) = sizes(Field::Gap) {
+        config.gap = value;
+        found = true;
+    }
+    if let Some(value) = flag(Field::Outline) {
+        config.show_outline = value;
+        found = true;
+    }
+    if let Some(value) = pixels(Field::OutlineThickness) {
+        config.outline_thickness = value;
+        found = true;
+    }
+    if let Some(value) = color(Field::OutlineColor) {
+        config.outline_color = value;
+        found = true;
+    }
+    if let Some(value) = flag(Field::Dot) {
+        config.show_dot = value;
+        found = true;
+    }
+    if let Some(value) = sizes(Field::DotSize) {
+        config.dot_size = value.max(1.0);
+        found = true;
+    }
+    if let Some(value) = color(Field::DotColor) {
+        config.dot_color = Some(value);
+        found = true;
+    }
+    
+    if !found {
+        return Err("The file has no crosshair settings this app understands".to_string());
+    }
+    preset_check::check_config(&config)?;
+    Ok(config)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Some(true),
+        "false" | "0" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+// "#RRGGBB", "0xRRGGBB", "r,g,b", bare hex digits or a decimal 0xRRGGBB
+fn parse_color(value: &str) -> Option<u32> {
+    let value = value.trim();
+    if value.contains(',') {
+        let channels: Vec<u32> = value
+            .split(',')
+            .map(|part| part.trim().parse::<f32>().ok().map(|c| c.round().clamp(0.0, 255.0) as u32))
+            .collect::<Option<_>>()?;
+        return match channels[..] {
+            [r, g, b] => Some(r << 16 | g << 8 | b),
+            [r, g, b, a] => Some(color::from_rgba(r << 16 | g << 8 | b, a as u8)),
+            _ => None,
+        };
+    }
+    if let Some(hex) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        return u32::from_str_radix(hex, 16).ok().map(color::rgb);
+    }
+    // Six or eight digits read as hex, so "008000" stays green
+    let hex_length = matches!(value.len(), 6 | 8);
+    if !hex_length && !value.starts_with('#') && value.chars().all(|c| c.is_ascii_digit()) {
+        return value.parse::<u32>().ok().map(color::rgb);
+    }
+    color::parse_hex(value)
+}
+
+// Kept visible, like the opacity slider
+fn opacity(fraction: f32) -> f32 {
+    fraction.clamp(0.1, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // Trimmed from a CrossHair X "Export profile" file
+    const CROSSHAIR_X_SAMPLE: &str = r##"{
+        "Name": "Tactical",
+        "Version": 3,
+        "Crosshair": {
+            "Color": "#00FF00",
+            "Opacity": 85,
+            "Length": 6,
+            "Thickness": 2,
+            "Gap": 3
+        },
+        "Outline": { "Enabled": true, "Thickness": 1, "Color": [0, 0, 0] },
+        "Dot": { "Enabled": false, "Size": 2, "Color": null },
+        "Tags": ["fps", 1]
+    }"##;
+    
+    // Trimmed from a HudSight settings.ini
+    const HUDSIGHT_SAMPLE: &str = "\
+; HudSight settings
+[General]
+Language=\"English\"
+Size=1
+
+[Crosshair]
+CrosshairColor=255,0,0
+Transparency=80
+Size=12
+LineWidth=3
+Gap = 4
+Shadow=1
+ShadowColor=0x000000
+CenterDot=0
+# Saved by HudSight 2.3
+";
+    
+    #[test]
+    fn parses_color_forms() {
+        assert_eq!(parse_color("#00FF00"), Some(0x00FF00));
+        assert_eq!(parse_color("00ff00"), Some(0x00FF00));
+        assert_eq!(parse_color("0xFF0000"), Some(0xFF0000));
+        assert_eq!(parse_color("0x80FF0000"), Some(0xFF0000));
+        assert_eq!(parse_color(" 255, 128, 0 "), Some(0xFF8000));
+        assert_eq!(parse_color("0,0,255,128"), Some(color::from_rgba(0x0000FF, 128)));
+        assert_eq!(parse_color("300,-5,0"), Some(0xFF0000));
+        assert_eq!(parse_color("65280"), Some(0x00FF00));
+        assert_eq!(parse_color("008000"), Some(0x008000));
+        assert_eq!(parse_color("#00FF0080"), Some(color::from_rgba(0x00FF00, 0x80)));
+    }
+    
+    #[test]
+    fn rejects_bad_colors() {
+        assert_eq!(parse_color(""), None);
+        assert_eq!(parse_color("green"), None);
+        assert_eq!(parse_color("1,2"), None);
+        assert_eq!(parse_color("1,2,x"), None);
+        assert_eq!(parse_color("#12345"), None);
+    }
+    
+    #[test]
+    fn reads_ini_sections_and_plain_keys() {
+        let fields = ini_fields(HUDSIGHT_SAMPLE);
+        
+        assert_eq!(fields.get("crosshair.crosshaircolor").map(String::as_str), Some("255,0,0"));
+        assert_eq!(fields.get("crosshaircolor").map(String::as_str), Some("255,0,0"));
+        assert_eq!(fields.get("general.language").map(String::as_str), Some("English"));
+        assert_eq!(fields.get("gap").map(String::as_str), Some("4"));
+        
+        // A plain key keeps the first section's value
+        assert_eq!(fields.get("size").map(String::as_str), Some("1"));
+        assert_eq!(fields.get("crosshair.size").map(String::as_str), Some("12"));
+        
+        assert!(!fields.keys().any(|key| key.contains("hudsight")));
+    }
+    
+    #[test]
+    fn flattens_nested_json() {
+        let value: Value = serde_json::from_str(CROSSHAIR_X_SAMPLE).unwrap();
+        let mut fields = HashMap::new();
+        flatten(&value, String::new(), &mut fields);
+        
+        assert_eq!(fields.get("crosshair.color").map(String::as_str), Some("#00FF00"));
+        assert_eq!(fields.get("crosshair.opacity").map(String::as_str), Some("85"));
+        assert_eq!(fields.get("outline.enabled").map(String::as_str), Some("true"));
+        assert_eq!(fields.get("outline.color").map(String::as_str), Some("0,0,0"));
+        assert_eq!(fields.get("version").map(String::as_str), Some("3"));
+        
+        // Nulls and arrays that aren't all numbers are dropped
+        assert!(!fields.contains_key("dot.color"));
+        assert!(!fields.contains_key("tags"));
+    }
+    
+    #[test]
+    fn maps_crosshair_x_export() {
+        let config = map_fields(&json_fields(CROSSHAIR_X_SAMPLE).unwrap(), CROSSHAIR_X_KEYS).unwrap();
+        
+        assert_eq!(config.color, 0x00FF00);
+        assert!((config.opacity - 0.85).abs() < 1e-4);
+        assert_eq!((config.size, config.thickness, config.gap), (6.0, 2.0, 3.0));
+        assert!(config.show_outline);
+        assert_eq!(config.outline_color, 0x000000);
+        assert!(!config.show_dot);
+    }
+    
+    #[test]
+    fn maps_hudsight_transparency_as_inverse_opacity() {
+        let config = map_fields(&ini_fields(HUDSIGHT_SAMPLE), HUDSIGHT_KEYS).unwrap();
+        
+        assert_eq!(config.color, 0xFF0000);
+        assert!((config.opacity - 0.2).abs() < 1e-4);
+        assert_eq!((config.size, config.thickness, config.gap), (12.0, 3.0, 4.0));
+        assert!(config.show_outline);
+        assert!(!config.show_dot);
+        
+        // Percentages even below 1, not fractions
+        let low = ini_fields("[Crosshair]\nTransparency=1\n");
+        assert!((map_fields(&low, HUDSIGHT_KEYS).unwrap().opacity - 0.99).abs() < 1e-4);
+        
+        // An explicit opacity wins over transparency
+        let both = ini_fields("[Crosshair]\nOpacity=60\nTransparency=90\n");
+        assert!((map_fields(&both, HUDSIGHT_KEYS).unwrap().opacity - 0.6).abs() < 1e-4);
+    }
+    
+    #[test]
+    fn rejects_files_without_known_keys() {
+        let fields = ini_fields("[Window]\nLeft=10\nTop=20\n");
+        assert!(map_fields(&fields, HUDSIGHT_KEYS).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,190 @@
+//! Controller button combos for couch and controller players: holding e.g.
+//! Back+RS toggles the crosshair or switches presets without a keyboard.
+//! Controllers are read through XInput, which has no events, so the held
+//! buttons are polled.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+#[cfg(windows)]
+use tauri::AppHandle;
+#[cfg(windows)]
+use tracing::error;
+
+#[cfg(windows)]
+use crate::{actions, hotkeys};
+#[cfg(windows)]
+use windows::Win32::{
+    Foundation::ERROR_SUCCESS,
+    UI::Input::XboxController::{XInputGetState, XINPUT_STATE, XUSER_MAX_COUNT},
+};
+
+// How often held buttons are read while a combo is bound
+#[cfg(windows)]
+const POLL_INTERVAL_MS: u64 = 30;
+
+// How often the watcher checks for newly bound combos otherwise
+#[cfg(windows)]
+const IDLE_INTERVAL_MS: u64 = 500;
+
+// Trigger travel (0-255) past which LT and RT count as held
+#[cfg(windows)]
+const TRIGGER_THRESHOLD: u8 = 128;
+
+// The triggers are analog, so they get bits above XInput's button flags
+const LEFT_TRIGGER: u32 = 1 << 16;
+const RIGHT_TRIGGER: u32 = 1 << 17;
+
+// Names accepted in combos, with XInput's button flags
+const BUTTONS: &[(&str, u32)] = &[
+    ("A", 0x1000),
+    ("B", 0x2000),
+    ("X", 0x4000),
+    ("Y", 0x8000),
+    ("LB", 0x0100),
+    ("RB", 0x0200),
+    ("LT", LEFT_TRIGGER),
+    ("RT", RIGHT_TRIGGER),
+    ("LS", 0x0040),  // Left stick click
+    ("RS", 0x0080),
+    ("Back", 0x0020),
+    ("Start", 0x0010),
+    ("Up", 0x0001),  // D-pad
+    ("Down", 0x0002),
+    ("Left", 0x0004),
+    ("Right", 0x0008),
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GamepadSettings {
+    pub enabled: bool,                    // Watch connected controllers for the combos below
+    pub toggle: Option<String>,           // e.g. "Back+RS", None = no combo
+    pub next_preset: Option<String>,      // Applies the next saved preset
+    pub previous_preset: Option<String>,
+}
+
+impl Default for GamepadSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            toggle: Some("Back+RS".to_string()),
+            next_preset: None,
+            previous_preset: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GamepadAction {
+    Toggle,
+    NextPreset,
+    PreviousPreset,
+}
+
+// Bound combos as button bits, empty while controller combos are off
+static COMBOS: Mutex<Vec<(GamepadAction, u32)>> = Mutex::new(Vec::new());
+
+/// Parses a combo like "Back+RS" or "LB+RB+Up" into button bits. Button
+/// names are case-insensitive.
+pub fn parse_combo(combo: &str) -> Result<u32, String> {
+    combo.split('+').map(str::trim).try_fold(0, |bits, name| {
+        BUTTONS
+            .iter()
+            .find(|(button, _)| button.eq_ignore_ascii_case(name))
+            .map(|&(_, bit)| bits | bit)
+            .ok_or_else(|| format!("Unknown controller button \"{}\" in \"{}\"", name, combo))
+    })
+}
+
+/// Checks that every configured combo parses.
+pub fn validate_settings(settings: &GamepadSettings) -> Result<(), String> {
+    for combo in [&settings.toggle, &settings.next_preset, &settings.previous_preset].into_iter().flatten() {
+        parse_combo(combo)?;
+    }
+    Ok(())
+}
+
+/// Binds the combos in `settings`, or unbinds them all when disabled. Nothing
+/// changes unless every combo parses.
+pub fn apply_settings(settings: &GamepadSettings) -> Result<(), String> {
+    let combos = [
+        (GamepadAction::Toggle, settings.toggle.as_deref()),
+        (GamepadAction::NextPreset, settings.next_preset.as_deref()),
+        (GamepadAction::PreviousPreset, settings.previous_preset.as_deref()),
+    ];
+    let mut bound = Vec::new();
+    for (action, combo) in combos {
+        if let Some(combo) = combo {
+            bound.push((action, parse_combo(combo)?));
+        }
+    }
+    
+    if !settings.enabled {
+        bound.clear();
+    }
+    *COMBOS.lock().unwrap() = bound;
+    Ok(())
+}
+
+/// Starts watching controllers for the bound combos.
+#[cfg(windows)]
+pub fn start_watcher(app: AppHandle) {
+    std::thread::spawn(move || {
+        // Buttons held at the last poll, so a held combo fires only once
+        let mut held = 0;
+        loop {
+            let combos = COMBOS.lock().unwrap().clone();
+            if combos.is_empty() {
+                held = 0;
+                std::thread::sleep(std::time::Duration::from_millis(IDLE_INTERVAL_MS));
+                continue;
+            }
+            
+            let pressed = held_buttons();
+            for (action, combo) in combos {
+                if pressed & combo == combo && held & combo != combo {
+                    run(&app, action);
+                }
+            }
+            held = pressed;
+            std::thread::sleep(std::time::Duration::from_millis(POLL_INTERVAL_MS));
+        }
+    });
+}
+
+// Buttons held on any connected controller
+#[cfg(windows)]
+fn held_buttons() -> u32 {
+    let mut buttons = 0;
+    for user in 0..XUSER_MAX_COUNT {
+        let mut state = XINPUT_STATE::default();
+        if unsafe { XInputGetState(user, &mut state) } != ERROR_SUCCESS.0 {
+            continue;
+        }
+        
+        let pad = state.Gamepad;
+        buttons |= pad.wButtons.0 as u32;
+        if pad.bLeftTrigger >= TRIGGER_THRESHOLD {
+            buttons |= LEFT_TRIGGER;
+        }
+        if pad.bRightTrigger >= TRIGGER_THRESHOLD {
+            buttons |= RIGHT_TRIGGER;
+        }
+    }
+    buttons
+}
+
+#[cfg(windows)]
+fn run(app: &AppHandle, action: GamepadAction) {
+    let result = match action {
+        GamepadAction::Toggle => {
+            hotkeys::toggle(app);
+            Ok(())
+        }
+        GamepadAction::NextPreset => actions::step_preset(app, 1).map(|_| ()),
+        GamepadAction::PreviousPreset => actions::step_preset(app, -1).map(|_| ()),
+    };
+    if let Err(e) = result {
+        error!("Controller {:?} failed: {}", action, e);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,130 @@
+//! Undo/redo for crosshair config changes. Every applied config is recorded
+//! from the change events, so edits from the settings UI, presets, profiles
+//! and the control interfaces can all be stepped back through.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Listener};
+
+use crate::actions;
+use crate::ConfigPayload;
+use crosshair_engine::CrosshairConfig;
+
+// Oldest states are dropped past this
+const MAX_HISTORY: usize = 50;
+
+// Changes closer together than this, like dragging a slider, undo as one step
+const COALESCE_WINDOW: Duration = Duration::from_millis(500);
+
+// Events carrying a config the crosshair now shows
+const CHANGE_EVENTS: &[&str] = &["crosshair-config-changed", "preset-applied", "profile-applied"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryStatus {
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
+
+struct History {
+    undo: VecDeque<CrosshairConfig>,
+    redo: Vec<CrosshairConfig>,
+    current: Option<CrosshairConfig>,  // None until the first config is applied
+    last_change: Option<Instant>,
+}
+
+static HISTORY: Mutex<History> = Mutex::new(History {
+    undo: VecDeque::new(),
+    redo: Vec::new(),
+    current: None,
+    last_change: None,
+});
+
+/// Starts recording config changes. Call once at startup.
+pub fn track_changes(app: &AppHandle) {
+    for &event in CHANGE_EVENTS {
+        app.listen_any(event, |event| {
+            if let Ok(payload) = serde_json::from_str::<ConfigPayload>(event.payload()) {
+                record(payload.config);
+            }
+        });
+    }
+}
+
+pub fn status() -> HistoryStatus {
+    let history = HISTORY.lock().unwrap();
+    HistoryStatus {
+        can_undo: !history.undo.is_empty(),
+        can_redo: !history.redo.is_empty(),
+    }
+}
+
+/// Goes back to the config before the last change. Returns the applied config,
+/// or None when there is nothing to undo.
+pub fn undo(app: &AppHandle) -> Result<Option<CrosshairConfig>, String> {
+    let target = {
+        let mut history = HISTORY.lock().unwrap();
+        let Some(target) = history.undo.pop_back() else {
+            return Ok(None);
+        };
+        if let Some(current) = history.current.replace(target.clone()) {
+            history.redo.push(current);
+        }
+        history.last_change = None;
+        target
+    };
+    restore(app, target).map(Some)
+}
+
+/// Reapplies the last undone change. Returns the applied config, or None when
+/// there is nothing to redo.
+pub fn redo(app: &AppHandle) -> Result<Option<CrosshairConfig>, String> {
+    let target = {
+        let mut history = HISTORY.lock().unwrap();
+        let Some(target) = history.redo.pop() else {
+            return Ok(None);
+        };
+        if let Some(current) = history.current.replace(target.clone()) {
+            history.undo.push_back(current);
+        }
+        history.last_change = None;
+        target
+    };
+    restore(app, target).map(Some)
+}
+
+// Showing or hiding the crosshair is not an edit, so visibility is left as is
+fn restore(app: &AppHandle, mut config: CrosshairConfig) -> Result<CrosshairConfig, String> {
+    config.enabled = actions::live_config()?.enabled;
+    // The change event this emits matches `current` and isn't recorded again
+    actions::apply_config(app, config.clone())?;
+    Ok(config)
+}
+
+fn record(mut config: CrosshairConfig) {
+    // Stored with a fixed visibility so toggling alone never adds a step
+    config.enabled = true;
+    
+    let mut history = HISTORY.lock().unwrap();
+    let Some(previous) = history.current.replace(config.clone()) else {
+        // The first config is where history starts
+        return;
+    };
+    if previous == config {
+        return;
+    }
+    
+    let now = Instant::now();
+    let continues_burst = history
+        .last_change
+        .is_some_and(|last| now.duration_since(last) < COALESCE_WINDOW);
+    history.last_change = Some(now);
+    history.redo.clear();
+    if !continues_burst {
+        history.undo.push_back(previous);
+        if history.undo.len() > MAX_HISTORY {
+            history.undo.pop_front();
+        }
+    }
+}
\ No newline at end of file
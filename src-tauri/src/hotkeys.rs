@@ -1,52 +1,397 @@
-use global_hotkey::{
-    hotkey::{Code, HotKey},
-    GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState,
-};
-use std::sync::mpsc;
+use crossbeam_channel::{at, never, select, Sender};
+use global_hotkey::{hotkey::HotKey, GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
+use tracing::error;
 
-#[cfg(windows)]
-use crate::overlay::{get_config, toggle_overlay};
+use crate::app_settings::{self, HotkeySettings};
+use crate::{actions, history, randomizer};
+use crosshair_engine::backend;
 
-pub fn setup_global_hotkeys(app: AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+/// What a global hotkey does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyAction {
+    Toggle,
+    Undo,
+    Redo,
+    Randomize,
+    Grid,
+    OpacityUp,
+    OpacityDown,
+    SizeUp,
+    SizeDown,
+    CycleStyle,
+    Preview,
+}
+
+/// What pressing the toggle hotkey twice in quick succession does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DoublePressAction {
+    NextPreset,
+    PreviousPreset,
+    CycleStyle,
+    Randomize,
+    Grid,
+}
+
+/// Outcome of the last attempt to register the toggle hotkey.
+#[derive(Debug, Clone, Serialize)]
+pub struct HotkeyStatus {
+    pub hotkey: String,
+    pub registered: bool,
+    pub error: Option<String>,  // Why registration failed, e.g. another app holds the key
+}
+
+static TOGGLE_STATUS: Mutex<Option<HotkeyStatus>> = Mutex::new(None);
+
+// Sending on (or dropping) this stops the event thread
+static SHUTDOWN: Mutex<Option<Sender<()>>> = Mutex::new(None);
+
+// Registered hotkeys, looked up by the event thread
+static BINDINGS: Mutex<Vec<(HotkeyAction, HotKey)>> = Mutex::new(Vec::new());
+
+thread_local! {
+    // The manager belongs to the main thread and unregisters everything when dropped
+    static MANAGER: RefCell<Option<GlobalHotKeyManager>> = const { RefCell::new(None) };
+}
+
+/// Parses a hotkey like "F9" or "Ctrl+Shift+X".
+pub fn parse_hotkey(hotkey: &str) -> Result<HotKey, String> {
+    hotkey
+        .parse::<HotKey>()
+        .map_err(|e| format!("Invalid hotkey \"{}\": {}", hotkey, e))
+}
+
+/// The toggle hotkey's registration result, None before hotkeys are set up.
+pub fn toggle_hotkey_status() -> Option<HotkeyStatus> {
+    TOGGLE_STATUS.lock().unwrap().clone()
+}
+
+/// Checks that every configured hotkey parses and the double press window
+/// is in range.
+pub fn validate_settings(settings: &HotkeySettings) -> Result<(), String> {
+    parse_hotkey(&settings.toggle)?;
+    for hotkey in optional_hotkeys(settings).into_iter().filter_map(|(_, hotkey)| hotkey) {
+        parse_hotkey(hotkey)?;
+    }
+    if !(MIN_DOUBLE_PRESS_MS..=MAX_DOUBLE_PRESS_MS).contains(&settings.double_press_ms) {
+        return Err(format!(
+            "The double press window must be {}-{} ms",
+            MIN_DOUBLE_PRESS_MS, MAX_DOUBLE_PRESS_MS
+        ));
+    }
+    Ok(())
+}
+
+// What a double press of `action`'s hotkey does, if anything. A single press
+// of such a hotkey waits out the double press window before it acts.
+fn double_press_action(settings: &HotkeySettings, action: HotkeyAction) -> Option<DoublePressAction> {
+    match action {
+        HotkeyAction::Toggle => settings.double_toggle,
+        _ => None,
+    }
+}
+
+// Allowed range of `double_press_ms`
+const MIN_DOUBLE_PRESS_MS: u32 = 100;
+const MAX_DOUBLE_PRESS_MS: u32 = 1000;
+
+// Steps of the opacity and size hotkeys
+const OPACITY_STEP: f32 = 0.1;
+const SIZE_STEP: f32 = 1.0;
+
+// Every hotkey besides toggle, which may be left unset
+fn optional_hotkeys(settings: &HotkeySettings) -> [(HotkeyAction, Option<&str>); 10] {
+    [
+        (HotkeyAction::Undo, settings.undo.as_deref()),
+        (HotkeyAction::Redo, settings.redo.as_deref()),
+        (HotkeyAction::Randomize, settings.randomize.as_deref()),
+        (HotkeyAction::Grid, settings.grid.as_deref()),
+        (HotkeyAction::OpacityUp, settings.opacity_up.as_deref()),
+        (HotkeyAction::OpacityDown, settings.opacity_down.as_deref()),
+        (HotkeyAction::SizeUp, settings.size_up.as_deref()),
+        (HotkeyAction::SizeDown, settings.size_down.as_deref()),
+        (HotkeyAction::CycleStyle, settings.cycle_style.as_deref()),
+        (HotkeyAction::Preview, settings.preview.as_deref()),
+    ]
+}
+
+fn record_status(hotkey: &str, error: Option<String>) {
+    *TOGGLE_STATUS.lock().unwrap() = Some(HotkeyStatus {
+        hotkey: hotkey.to_string(),
+        registered: error.is_none(),
+        error,
+    });
+}
+
+/// Replaces the toggle hotkey. Must run on the main thread.
+pub fn set_toggle_hotkey(toggle: &str) -> Result<(), String> {
+    let result = bind(HotkeyAction::Toggle, Some(toggle));
+    // A failed replacement leaves the old hotkey registered
+    if result.is_ok() {
+        record_status(toggle, None);
+    }
+    result
+}
+
+/// Registers the hotkeys that changed between `previous` and `settings`. Must
+/// run on the main thread.
+pub fn apply_settings(previous: &HotkeySettings, settings: &HotkeySettings) {
+    if settings.toggle != previous.toggle {
+        if let Err(e) = set_toggle_hotkey(&settings.toggle) {
+            error!("Failed to change toggle hotkey: {}", e);
+        }
+    }
+    for ((action, hotkey), (_, old)) in optional_hotkeys(settings).into_iter().zip(optional_hotkeys(previous)) {
+        if hotkey != old {
+            if let Err(e) = bind(action, hotkey) {
+                error!("Failed to change {:?} hotkey: {}", action, e);
+            }
+        }
+    }
+}
+
+// Points `action` at `hotkey`, or unbinds it with None. The new hotkey is
+// registered before the old one goes, so a failure changes nothing.
+fn bind(action: HotkeyAction, hotkey: Option<&str>) -> Result<(), String> {
+    let hotkey = hotkey.map(parse_hotkey).transpose()?;
+    let current = BINDINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(bound, _)| *bound == action)
+        .map(|&(_, hotkey)| hotkey);
+    if current == hotkey {
+        return Ok(());
+    }
+    
+    MANAGER.with(|manager| {
+        let manager = manager.borrow();
+        let manager = manager.as_ref().ok_or("Hotkeys are not set up")?;
+        if let Some(hotkey) = hotkey {
+            manager.register(hotkey).map_err(|e| e.to_string())?;
+        }
+        if let Some(current) = current {
+            let _ = manager.unregister(current);
+        }
+        Ok::<(), String>(())
+    })?;
+    
+    let mut bindings = BINDINGS.lock().unwrap();
+    bindings.retain(|(bound, _)| *bound != action);
+    if let Some(hotkey) = hotkey {
+        bindings.push((action, hotkey));
+    }
+    Ok(())
+}
+
+/// Registers the configured hotkeys and starts handling presses. Only a failed
+/// toggle hotkey is an error; the others are logged. Must run on the main
+/// thread.
+pub fn setup_global_hotkeys(app: AppHandle, settings: &HotkeySettings) -> Result<(), Box<dyn std::error::Error>> {
+    let result = start_hotkeys(app, settings);
+    record_status(&settings.toggle, result.as_ref().err().map(|e| e.to_string()));
+    result
+}
+
+fn start_hotkeys(app: AppHandle, settings: &HotkeySettings) -> Result<(), Box<dyn std::error::Error>> {
     let manager = GlobalHotKeyManager::new()?;
+    MANAGER.with(|m| *m.borrow_mut() = Some(manager));
     
-    // F9 hotkey for toggle
-    let hotkey = HotKey::new(None, Code::F9);
-    manager.register(hotkey)?;
+    bind(HotkeyAction::Toggle, Some(&settings.toggle))?;
+    for (action, hotkey) in optional_hotkeys(settings) {
+        if let Err(e) = bind(action, hotkey) {
+            error!("Failed to register {:?} hotkey: {}", action, e);
+        }
+    }
     
-    // Create a channel for hotkey events (unused but kept for future use)
-    let (_tx, _rx) = mpsc::channel::<()>();
+    let (shutdown_tx, shutdown_rx) = crossbeam_channel::bounded::<()>(1);
+    *SHUTDOWN.lock().unwrap() = Some(shutdown_tx);
     
-    // Spawn a thread to handle hotkey events
-    let app_handle = app.clone();
+    // Blocks until a hotkey fires or shutdown is requested, so toggles are instant
+    // and the thread doesn't wake up otherwise
     std::thread::spawn(move || {
         let global_hotkey_receiver = GlobalHotKeyEvent::receiver();
+        // A single press held back until it can't become a double press
+        let mut pending: Option<(HotkeyAction, Instant)> = None;
         
         loop {
-            if let Ok(event) = global_hotkey_receiver.try_recv() {
-                if event.state() == HotKeyState::Pressed {
-                    // Toggle crosshair when F9 is pressed
-                    #[cfg(windows)]
-                    {
-                        let current_config = get_config();
-                        let new_enabled = !current_config.enabled;
-                        
-                        if let Err(e) = toggle_overlay(new_enabled) {
-                            eprintln!("Failed to toggle overlay: {}", e);
-                        }
-                        
-                        // Emit event to frontend to update UI
-                        if let Err(e) = app_handle.emit("crosshair-toggled", new_enabled) {
-                            eprintln!("Failed to emit toggle event: {}", e);
-                        }
+            let deadline = pending.map_or(never(), |(_, deadline)| at(deadline));
+            select! {
+                recv(global_hotkey_receiver) -> event => match event {
+                    Ok(event) => handle_event(&app, event, &mut pending),
+                    Err(_) => break,
+                },
+                recv(deadline) -> _ => {
+                    if let Some((action, _)) = pending.take() {
+                        run(&app, action);
                     }
                 }
+                recv(shutdown_rx) -> _ => break,
             }
-            
-            std::thread::sleep(std::time::Duration::from_millis(100));
         }
     });
     
     Ok(())
+}
+
+/// Stops the hotkey event thread. Call before exiting.
+pub fn shutdown_hotkeys() {
+    if let Some(shutdown) = SHUTDOWN.lock().unwrap().take() {
+        let _ = shutdown.send(());
+    }
+}
+
+fn handle_event(app: &AppHandle, event: GlobalHotKeyEvent, pending: &mut Option<(HotkeyAction, Instant)>) {
+    let action = BINDINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(_, hotkey)| hotkey.id() == event.id())
+        .map(|&(action, _)| action);
+    let Some(action) = action else {
+        return;
+    };
+    
+    // The preview lasts while the key is held, so it needs the release too
+    if action == HotkeyAction::Preview {
+        preview(app, event.state() == HotKeyState::Pressed);
+        return;
+    }
+    if event.state() != HotKeyState::Pressed {
+        return;
+    }
+    
+    let settings = app_settings::get_settings().hotkeys;
+    match pending.take() {
+        // Second press in time
+        Some((waiting, _)) if waiting == action => {
+            if let Some(double) = double_press_action(&settings, action) {
+                run_double(app, double);
+            }
+            return;
+        }
+        // Another hotkey doesn't wait for the first
+        Some((waiting, _)) => run(app, waiting),
+        None => {}
+    }
+    
+    if double_press_action(&settings, action).is_some() {
+        let window = Duration::from_millis(settings.double_press_ms as u64);
+        *pending = Some((action, Instant::now() + window));
+    } else {
+        run(app, action);
+    }
+}
+
+fn run(app: &AppHandle, action: HotkeyAction) {
+    match action {
+        HotkeyAction::Toggle => toggle(app),
+        HotkeyAction::Undo => {
+            if let Err(e) = history::undo(app) {
+                error!("Failed to undo config change: {}", e);
+            }
+        }
+        HotkeyAction::Redo => {
+            if let Err(e) = history::redo(app) {
+                error!("Failed to redo config change: {}", e);
+            }
+        }
+        HotkeyAction::Randomize => randomize(app),
+        HotkeyAction::Grid => toggle_grid(app),
+        HotkeyAction::OpacityUp => step_opacity(app, OPACITY_STEP),
+        HotkeyAction::OpacityDown => step_opacity(app, -OPACITY_STEP),
+        HotkeyAction::SizeUp => step_size(app, SIZE_STEP),
+        HotkeyAction::SizeDown => step_size(app, -SIZE_STEP),
+        HotkeyAction::CycleStyle => cycle_style(app),
+        HotkeyAction::Preview => preview(app, true),
+    }
+}
+
+fn run_double(app: &AppHandle, action: DoublePressAction) {
+    match action {
+        DoublePressAction::NextPreset => step_preset(app, 1),
+        DoublePressAction::PreviousPreset => step_preset(app, -1),
+        DoublePressAction::CycleStyle => cycle_style(app),
+        DoublePressAction::Randomize => randomize(app),
+        DoublePressAction::Grid => toggle_grid(app),
+    }
+}
+
+fn randomize(app: &AppHandle) {
+    let constraints = app_settings::get_settings().randomizer;
+    if let Err(e) = randomizer::randomize_live(app, &constraints) {
+        error!("Failed to randomize crosshair: {}", e);
+    }
+}
+
+fn toggle_grid(app: &AppHandle) {
+    let visible = !app_settings::get_settings().show_grid;
+    if let Err(e) = crate::show_grid(app, visible) {
+        error!("Failed to toggle grid: {}", e);
+    }
+}
+
+fn cycle_style(app: &AppHandle) {
+    if let Err(e) = actions::cycle_style(app) {
+        error!("Failed to change style: {}", e);
+    }
+}
+
+// Shows the preview preset while `held`, then puts the config back
+fn preview(app: &AppHandle, held: bool) {
+    let result = if held {
+        match app_settings::get_settings().hotkeys.preview_preset {
+            Some(preset_id) => actions::start_preview(app, &preset_id).and_then(|found| {
+                found.then_some(()).ok_or(format!("No preset with id \"{}\"", preset_id))
+            }),
+            None => Ok(()),
+        }
+    } else {
+        actions::end_preview(app)
+    };
+    if let Err(e) = result {
+        error!("Failed to preview preset: {}", e);
+    }
+}
+
+fn step_preset(app: &AppHandle, step: isize) {
+    if let Err(e) = actions::step_preset(app, step) {
+        error!("Failed to switch preset: {}", e);
+    }
+}
+
+// The config change event keeps the settings sliders in sync
+fn step_opacity(app: &AppHandle, step: f32) {
+    if let Err(e) = actions::step_opacity(app, step) {
+        error!("Failed to change opacity: {}", e);
+    }
+}
+
+fn step_size(app: &AppHandle, step: f32) {
+    if let Err(e) = actions::step_size(app, step) {
+        error!("Failed to change size: {}", e);
+    }
+}
+
+/// Flips the crosshair's visibility with the toggle sound, notification and
+/// event, as the toggle hotkey does.
+pub fn toggle(app: &AppHandle) {
+    let current_config = backend().config();
+    let new_enabled = !current_config.enabled;
+    
+    if let Err(e) = backend().toggle(new_enabled) {
+        error!("Failed to toggle overlay: {}", e);
+    }
+    #[cfg(windows)]
+    crate::sounds::play_toggle_sound(new_enabled);
+    crate::toast::notify_toggled(app, &backend().config());
+    
+    // Emit event to frontend to update UI
+    if let Err(e) = app.emit("crosshair-toggled", new_enabled) {
+        error!("Failed to emit toggle event: {}", e);
+    }
 }
\ No newline at end of file
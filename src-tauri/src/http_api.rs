@@ -0,0 +1,364 @@
+//! Opt-in HTTP control API for Stream Deck, AutoHotkey and other scripts.
+//!
+//! The server only listens on 127.0.0.1 and every request has to carry the
+//! configured token as `Authorization: Bearer <token>`. Endpoints:
+//!
+//! - `GET /config` returns the live config
+//! - `POST /config` replaces it with the JSON body and saves it
+//! - `PATCH /config` changes only the fields in the JSON body and saves it
+//! - `POST /toggle` flips visibility, or sets it from an `{"enabled": bool}` body
+//! - `POST /preset/{id}` applies a saved preset
+//! - `GET /events` upgrades to a WebSocket streaming app events, see [`crate::event_stream`]
+
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tauri::AppHandle;
+use tracing::{error, warn};
+
+use crate::actions::{apply_config, apply_preset, live_config, patch_config, set_enabled};
+use crate::event_stream;
+use crate::preset_check;
+use crate::storage::{self, HTTP_API_FILE};
+use crosshair_engine::CrosshairConfig;
+
+const DEFAULT_PORT: u16 = 47800;
+
+// Configs with large custom shapes are still far below this
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+
+// How often the accept loop checks whether it should stop
+const ACCEPT_POLL_MS: u64 = 100;
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Clients served at once, each on its own thread; more are turned away.
+// Event stream clients hold their place for as long as they stay connected.
+const MAX_CLIENTS: usize = 16;
+static ACTIVE_CLIENTS: AtomicUsize = AtomicUsize::new(0);
+
+// Frees a client's place in ACTIVE_CLIENTS when dropped, even on a panic
+struct ClientSlot;
+
+impl Drop for ClientSlot {
+    fn drop(&mut self) {
+        ACTIVE_CLIENTS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,  // Required on every request, generated when left empty
+}
+
+#[derive(Deserialize)]
+struct ToggleBody {
+    enabled: bool,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    token: Option<String>,
+    websocket_key: Option<String>,  // Set on WebSocket upgrade requests
+    body: Vec<u8>,
+}
+
+struct Server {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+static SETTINGS: Mutex<HttpApiSettings> = Mutex::new(HttpApiSettings {
+    enabled: false,
+    port: DEFAULT_PORT,
+    token: String::new(),
+});
+
+static SERVER: Mutex<Option<Server>> = Mutex::new(None);
+
+/// Loads saved API settings from disk into memory.
+pub fn load_settings() -> Result<(), String> {
+    let api_path = storage::file_path(HTTP_API_FILE)?;
+    if let Some(settings) = storage::read_json::<HttpApiSettings>(&api_path)? {
+        *SETTINGS.lock().unwrap() = settings;
+    }
+    Ok(())
+}
+
+pub fn get_settings() -> HttpApiSettings {
+    SETTINGS.lock().unwrap().clone()
+}
+
+/// Saves `settings` and restarts the server with them. Returns the settings
+/// actually in use, including a generated token.
+pub fn set_settings(app: &AppHandle, mut settings: HttpApiSettings) -> Result<HttpApiSettings, String> {
+    if settings.port == 0 {
+        return Err("Port cannot be 0".to_string());
+    }
+    settings.token = settings.token.trim().to_string();
+    if settings.token.is_empty() {
+        settings.token = generate_token()?;
+    }
+    
+    *SETTINGS.lock().unwrap() = settings.clone();
+    let api_path = storage::file_path(HTTP_API_FILE)?;
+    storage::write_json(&api_path, &settings)?;
+    
+    restart(app)?;
+    Ok(settings)
+}
+
+/// Stops the running server, if any, and starts a new one when the API is enabled.
+pub fn restart(app: &AppHandle) -> Result<(), String> {
+    let previous = SERVER.lock().unwrap().take();
+    if let Some(server) = previous {
+        // Joining frees the port before it is bound again
+        server.stop.store(true, Ordering::SeqCst);
+        let _ = server.thread.join();
+    }
+    
+    let settings = get_settings();
+    if !settings.enabled {
+        return Ok(());
+    }
+    if settings.token.is_empty() {
+        return Err("The HTTP API needs a token".to_string());
+    }
+    
+    let listener = TcpListener::bind(("127.0.0.1", settings.port))
+        .map_err(|e| format!("Failed to listen on port {}: {}", settings.port, e))?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+    
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    let app = app.clone();
+    let thread = std::thread::spawn(move || {
+        while !thread_stop.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let busy = ACTIVE_CLIENTS.fetch_add(1, Ordering::SeqCst) >= MAX_CLIENTS;
+                    let slot = ClientSlot;
+                    if busy {
+                        warn!("HTTP API is serving {} clients already, dropping a connection", MAX_CLIENTS);
+                        continue;
+                    }
+                    let app = app.clone();
+                    let token = settings.token.clone();
+                    std::thread::spawn(move || {
+                        let _slot = slot;
+                        handle_client(&app, stream, &token);
+                    });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(ACCEPT_POLL_MS));
+                }
+                Err(e) => {
+                    error!("HTTP API accept failed: {}", e);
+                    // Errors like running out of handles last, so don't spin on them
+                    std::thread::sleep(Duration::from_millis(ACCEPT_POLL_MS));
+                }
+            }
+        }
+    });
+    
+    *SERVER.lock().unwrap() = Some(Server { stop, thread });
+    Ok(())
+}
+
+fn handle_client(app: &AppHandle, stream: TcpStream, token: &str) {
+    // Accepted sockets inherit non-blocking mode from the listener
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(CLIENT_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(CLIENT_TIMEOUT));
+    
+    let result = match read_request(&stream) {
+        Ok(request) if !token_matches(request.token.as_deref(), token) => {
+            Err((401, "Missing or invalid token".to_string()))
+        }
+        Ok(request) if request_path(&request) == "/events" => match request.websocket_key {
+            Some(key) => {
+                // Served on this thread, so the connection keeps its client slot
+                if let Err(e) = event_stream::serve(stream, &key) {
+                    warn!("WebSocket handshake failed: {}", e);
+                }
+                return;
+            }
+            None => Err((400, "Expected a WebSocket upgrade".to_string())),
+        },
+        Ok(request) => route(app, &request),
+        Err(e) => Err((400, e)),
+    };
+    
+    let (status, body) = match result {
+        Ok(body) => (200, body),
+        Err((status, message)) => (status, serde_json::json!({ "error": message }).to_string()),
+    };
+    if let Err(e) = write_response(&stream, status, &body) {
+        warn!("HTTP API response failed: {}", e);
+    }
+}
+
+fn read_request(stream: &TcpStream) -> Result<Request, String> {
+    let mut reader = BufReader::new(stream.take((MAX_HEADER_BYTES + MAX_BODY_BYTES) as u64));
+    
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|e| e.to_string())?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or("Empty request")?.to_string();
+    let path = parts.next().ok_or("Missing request path")?.to_string();
+    
+    let mut header_bytes = line.len();
+    let mut content_length = 0;
+    let mut token = None;
+    let mut upgrade = false;
+    let mut websocket_key = None;
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line).map_err(|e| e.to_string())?;
+        header_bytes += read;
+        if header_bytes > MAX_HEADER_BYTES {
+            return Err("Headers too large".to_string());
+        }
+        
+        let header = line.trim_end();
+        if read == 0 || header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            let value = value.trim();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => {
+                    content_length = value.parse().map_err(|_| "Invalid Content-Length")?;
+                }
+                "authorization" => token = value.strip_prefix("Bearer ").map(|t| t.trim().to_string()),
+                "upgrade" => upgrade = value.eq_ignore_ascii_case("websocket"),
+                "sec-websocket-key" => websocket_key = Some(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+    
+    if content_length > MAX_BODY_BYTES {
+        return Err("Request body too large".to_string());
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(|e| e.to_string())?;
+    
+    let mut request = Request {
+        method,
+        path,
+        token,
+        websocket_key: websocket_key.filter(|_| upgrade),
+        body,
+    };
+    
+    // WebSocket clients in browsers can only pass the token in the URL. Every
+    // other client can send the header, so the query is ignored for them to
+    // keep the token out of URLs and logs.
+    if request.token.is_none() && request.websocket_key.is_some() && request_path(&request) == "/events" {
+        request.token = request
+            .path
+            .split_once('?')
+            .and_then(|(_, query)| query.split('&').find_map(|pair| pair.strip_prefix("token=")))
+            .map(str::to_string);
+    }
+    
+    Ok(request)
+}
+
+fn write_response(mut stream: &TcpStream, status: u16, body: &str) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+// The request path without its query string or a trailing slash
+fn request_path(request: &Request) -> &str {
+    request.path.split('?').next().unwrap_or_default().trim_end_matches('/')
+}
+
+fn route(app: &AppHandle, request: &Request) -> Result<String, (u16, String)> {
+    let path = request_path(request);
+    let internal = |e: String| (500, e);
+    
+    match (request.method.as_str(), path) {
+        ("GET", "/config") => {
+            let config = live_config().map_err(internal)?;
+            serde_json::to_string(&config).map_err(|e| internal(e.to_string()))
+        }
+        ("POST", "/config") => {
+            let config: CrosshairConfig =
+                serde_json::from_slice(&request.body).map_err(|e| (400, e.to_string()))?;
+            preset_check::check_limits(&config).map_err(|e| (400, e))?;
+            apply_config(app, config).map_err(internal)?;
+            Ok("{}".to_string())
+        }
+        ("PATCH", "/config") => {
+            let patch = serde_json::from_slice(&request.body).map_err(|e| (400, e.to_string()))?;
+            let config = patch_config(app, patch).map_err(|e| (400, e))?;
+            serde_json::to_string(&config).map_err(|e| internal(e.to_string()))
+        }
+        ("POST", "/toggle") => {
+            let enabled = if request.body.iter().all(u8::is_ascii_whitespace) {
+                !live_config().map_err(internal)?.enabled
+            } else {
+                serde_json::from_slice::<ToggleBody>(&request.body)
+                    .map_err(|e| (400, e.to_string()))?
+                    .enabled
+            };
+            set_enabled(app, enabled).map_err(internal)?;
+            Ok(serde_json::json!({ "enabled": enabled }).to_string())
+        }
+        ("POST", _) if path.starts_with("/preset/") => {
+            let preset_id = &path["/preset/".len()..];
+            let found = apply_preset(app, preset_id).map_err(internal)?;
+            if found {
+                Ok("{}".to_string())
+            } else {
+                Err((404, format!("Preset not found: {}", preset_id)))
+            }
+        }
+        (_, "/config" | "/toggle") => Err((405, "Method not allowed".to_string())),
+        _ => Err((404, "Not found".to_string())),
+    }
+}
+
+fn token_matches(given: Option<&str>, expected: &str) -> bool {
+    // Compare every byte so the response time doesn't leak how much matched
+    given.is_some_and(|given| {
+        given.len() == expected.len()
+            && given
+                .bytes()
+                .zip(expected.bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    })
+}
+
+// 128 random bits as hex, from the OS's secure random number generator
+fn generate_token() -> Result<String, String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate a token: {}", e))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
\ No newline at end of file
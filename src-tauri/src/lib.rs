@@ -1,29 +1,129 @@
-#[cfg(windows)]
-mod overlay;
+// The overlay, hotkeys and most commands only do their work on Windows; elsewhere
+// their arguments and helpers go unused
+#![cfg_attr(not(windows), allow(unused))]
+
 mod hotkeys;
+mod backup;
+mod bundle;
+mod storage;
+mod profiles;
+mod process_watch;
+mod actions;
+mod http_api;
+mod event_stream;
+mod share_code;
+mod preset_import;
+mod preset_check;
+mod external_import;
+mod sync;
+mod cli;
+mod app_settings;
+mod toast;
+mod stats;
+mod logging;
+mod diagnostics;
+mod history;
+mod randomizer;
+mod gamepad;
+mod calibration;
+#[cfg(windows)]
+mod pipe_server;
+#[cfg(windows)]
+mod process_list;
+#[cfg(windows)]
+mod autostart;
+#[cfg(windows)]
+mod sounds;
+#[cfg(not(windows))]
+mod webview_overlay;
 
 use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
 use tauri::{
-    menu::{Menu, MenuItem},
+    image::Image,
+    menu::{CheckMenuItem, Menu, MenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, Runtime,
+    Emitter, Listener, Manager, Runtime,
 };
+use tauri_plugin_opener::OpenerExt;
+use tracing::{error, info};
 use hotkeys::setup_global_hotkeys;
+use backup::BackupInfo;
+use bundle::BundleManifest;
+use profiles::GameProfile;
+use process_watch::{AutoHideSettings, RunningProcess};
+use http_api::HttpApiSettings;
+use sync::{SyncSettings, SyncStatus};
+use app_settings::{AppSettings, AutostartStatus};
+use stats::UsageStats;
+use history::HistoryStatus;
+use randomizer::RandomizeConstraints;
+use external_import::ExternalFormat;
+use calibration::CalibrationCapture;
+use crosshair_engine::{MonitorInfo, RenderMetrics};
+use storage::{CONFIG_FILE, PRESETS_FILE};
+use crosshair_engine::{backend, CrosshairConfig, CrosshairStyle, Deficiency, MirrorAxis, OverlayStatus, Palette};
 
-#[cfg(windows)]
-use overlay::{CrosshairConfig, create_overlay_window, update_config, toggle_overlay, get_config};
+const TRAY_ID: &str = "main";
+
+// Shown while the crosshair is visible / hidden
+const TRAY_ICON_ACTIVE: &[u8] = include_bytes!("../icons/tray-active.png");
+const TRAY_ICON_INACTIVE: &[u8] = include_bytes!("../icons/tray-inactive.png");
+
+// Quick-adjust values offered in the tray, as (menu label, value)
+const TRAY_OPACITIES: &[(&str, f32)] = &[("25%", 0.25), ("50%", 0.5), ("75%", 0.75), ("100%", 1.0)];
+const TRAY_SIZES: &[(&str, f32)] = &[("Small", 6.0), ("Medium", 10.0), ("Large", 16.0)];
+
+// The tray's toggle item, replaced whenever the tray is rebuilt
+struct TrayToggleItem<R: Runtime>(Mutex<Option<CheckMenuItem<R>>>);
 
 #[derive(Clone, Serialize, Deserialize)]
 struct ConfigPayload {
     config: CrosshairConfig,
 }
 
+#[derive(Clone, Serialize)]
+struct PositionPayload {
+    position_x: f32,
+    position_y: f32,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 struct CrosshairPreset {
     id: String,
     name: String,
     config: CrosshairConfig,
     created_at: String,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    game: Option<String>,        // Game the preset was made for
+    #[serde(default)]
+    source_url: Option<String>,  // Where it was shared or downloaded from
+}
+
+#[derive(Clone, Serialize)]
+struct SimulatedColor {
+    deficiency: Deficiency,
+    color: String,
+}
+
+#[derive(Clone, Serialize)]
+struct AppInfo {
+    version: String,
+    platform: String,           // "windows", "macos" or "linux"
+    overlay_available: bool,    // The native overlay works on this platform
+    renderer: Option<String>,   // Draws the overlay, e.g. "gdi"
+    monitor_count: usize,
+    hotkeys_available: bool,    // The toggle hotkey is registered
+}
+
+#[derive(Clone, Serialize)]
+struct ShareCode {
+    code: String,
+    link: String,  // crosshair-overlay://import?code=...
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -33,24 +133,338 @@ struct FavoritesData {
 
 #[tauri::command]
 async fn init_overlay() -> Result<String, String> {
+    backend().create()?;
+    Ok("Overlay initialized".to_string())
+}
+
+#[tauri::command]
+async fn get_overlay_status() -> Result<OverlayStatus, String> {
+    backend().status()
+}
+
+#[tauri::command]
+async fn restart_overlay() -> Result<(), String> {
+    backend().restart()
+}
+
+#[tauri::command]
+async fn update_crosshair_config(app: tauri::AppHandle, config: CrosshairConfig) -> Result<(), String> {
+    // Also lets the event stream tell external tools about edits made in the UI
+    actions::show_config(&app, config)
+}
+
+/// Changes only the fields in `patch`, so one slider doesn't resend the whole
+/// config. Like update_crosshair_config, the result isn't saved.
+#[tauri::command]
+async fn patch_crosshair_config(
+    app: tauri::AppHandle,
+    patch: serde_json::Value,
+) -> Result<CrosshairConfig, String> {
+    let config = actions::patched_config(&actions::live_config()?, patch)?;
+    actions::show_config(&app, config.clone())?;
+    Ok(config)
+}
+
+/// Starts batching config updates, e.g. while a slider is dragged: the
+/// overlay catches up once, on the matching commit_config_transaction.
+#[tauri::command]
+async fn begin_config_transaction() -> Result<(), String> {
+    backend().begin_transaction();
+    Ok(())
+}
+
+#[tauri::command]
+async fn commit_config_transaction() -> Result<(), String> {
+    backend().commit_transaction()
+}
+
+#[tauri::command]
+async fn toggle_crosshair(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    backend().toggle(enabled)?;
+    app.emit("crosshair-toggled", enabled).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_crosshair_config() -> Result<CrosshairConfig, String> {
+    Ok(backend().config())
+}
+
+/// The config a fresh install starts with.
+#[tauri::command]
+async fn get_default_config() -> Result<CrosshairConfig, String> {
+    Ok(CrosshairConfig::default())
+}
+
+/// Recovers from a broken config: the overlay and the saved config go back
+/// to the defaults, and the previous saved config lands in the backups.
+#[tauri::command]
+async fn reset_to_defaults(app: tauri::AppHandle) -> Result<CrosshairConfig, String> {
+    actions::reset_to_defaults(&app)
+}
+
+#[tauri::command]
+async fn save_config(config: CrosshairConfig) -> Result<(), String> {
+    let config_path = storage::file_path(CONFIG_FILE)?;
+    storage::write_json(&config_path, &config)
+}
+
+fn load_favorites() -> Result<FavoritesData, String> {
+    let presets_path = storage::file_path(PRESETS_FILE)?;
+    
+    // An unreadable or corrupted presets file is treated as empty rather than blocking the UI
+    Ok(storage::read_json::<FavoritesData>(&presets_path)
+        .unwrap_or(None)
+        .unwrap_or(FavoritesData { presets: Vec::new() }))
+}
+
+fn save_favorites(favorites_data: &FavoritesData) -> Result<(), String> {
+    let presets_path = storage::file_path(PRESETS_FILE)?;
+    storage::write_json(&presets_path, favorites_data)
+}
+
+#[tauri::command]
+async fn save_preset(preset: CrosshairPreset) -> Result<(), String> {
+    preset_check::check_limits(&preset.config)
+        .map_err(|e| format!("Preset \"{}\" can't be saved: {}", preset.name, e))?;
+    
+    let mut favorites_data = load_favorites()?;
+    
+    // Remove existing preset with same ID if it exists
+    favorites_data.presets.retain(|p| p.id != preset.id);
+    
+    // Add new preset
+    favorites_data.presets.push(preset);
+    
+    save_favorites(&favorites_data)
+}
+
+/// Adds the presets of an exported presets file, checked like any other
+/// import. Each gets a fresh id, so importing never replaces a preset.
+#[tauri::command]
+async fn import_presets(presets: Vec<CrosshairPreset>) -> Result<usize, String> {
+    for preset in &presets {
+        preset_check::check_config(&preset.config)
+            .map_err(|e| format!("Preset \"{}\" can't be imported: {}", preset.name, e))?;
+    }
+    
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let count = presets.len();
+    let mut favorites_data = load_favorites()?;
+    for (i, mut preset) in presets.into_iter().enumerate() {
+        preset.id = (now + i as u128).to_string();
+        favorites_data.presets.push(preset);
+    }
+    save_favorites(&favorites_data)?;
+    Ok(count)
+}
+
+/// Downloads a preset, bare config or share code from an https:// URL and
+/// adds it to the saved presets.
+#[tauri::command]
+async fn import_preset_from_url(url: String) -> Result<CrosshairPreset, String> {
+    let preset = tauri::async_runtime::spawn_blocking(move || preset_import::download_preset(&url))
+        .await
+        .map_err(|e| e.to_string())??;
+    
+    let mut favorites_data = load_favorites()?;
+    favorites_data.presets.push(preset.clone());
+    save_favorites(&favorites_data)?;
+    Ok(preset)
+}
+
+#[tauri::command]
+async fn load_presets() -> Result<Vec<CrosshairPreset>, String> {
+    Ok(load_favorites()?.presets)
+}
+
+#[tauri::command]
+async fn delete_preset(id: String) -> Result<(), String> {
+    let mut favorites_data = load_favorites()?;
+    
+    // Remove preset with matching ID
+    favorites_data.presets.retain(|p| p.id != id);
+    
+    save_favorites(&favorites_data)
+}
+
+#[tauri::command]
+async fn load_config() -> Result<CrosshairConfig, String> {
+    let config_path = storage::file_path(CONFIG_FILE)?;
+    
+    let mut config = storage::read_json::<CrosshairConfig>(&config_path)?
+        .unwrap_or_default();
+    app_settings::apply_startup_settings(&mut config);
+    
+    Ok(config)
+}
+
+#[tauri::command]
+async fn list_backups() -> Result<Vec<BackupInfo>, String> {
+    backup::list_backups(&storage::config_dir()?)
+}
+
+#[tauri::command]
+async fn restore_backup(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    let restored_path = backup::restore_backup(&storage::config_dir()?, &name)?;
+    
+    // Apply a restored config to the live overlay right away
+    if restored_path.file_name().is_some_and(|n| n == CONFIG_FILE) {
+        if let Some(config) = storage::read_json::<CrosshairConfig>(&restored_path)? {
+            actions::show_config(&app, config)?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Saves the config, presets, settings and images to one bundle file.
+#[tauri::command]
+async fn export_all(path: String) -> Result<BundleManifest, String> {
+    bundle::export_all(std::path::Path::new(&path))
+}
+
+/// Replaces the app data with a bundle from export_all. The config, presets,
+/// profiles and watch lists apply right away; settings applied at startup,
+/// like hotkeys, take effect on the next launch.
+#[tauri::command]
+async fn import_all(app: tauri::AppHandle, path: String) -> Result<BundleManifest, String> {
+    let manifest = bundle::import_all(std::path::Path::new(&path))?;
+    
+    app_settings::load_settings()?;
+    profiles::load_profiles()?;
+    process_watch::load_settings()?;
+    if let Some(config) = storage::read_json::<CrosshairConfig>(&storage::file_path(CONFIG_FILE)?)? {
+        actions::show_config(&app, config)?;
+    }
+    Ok(manifest)
+}
+
+#[tauri::command]
+async fn set_game_profile(exe_name: String, preset_id: Option<String>) -> Result<(), String> {
+    profiles::set_profile(&exe_name, preset_id)
+}
+
+#[tauri::command]
+async fn get_game_profiles() -> Result<Vec<GameProfile>, String> {
+    Ok(profiles::get_profiles())
+}
+
+#[tauri::command]
+async fn get_auto_hide() -> Result<AutoHideSettings, String> {
+    Ok(process_watch::get_auto_hide())
+}
+
+#[tauri::command]
+async fn set_auto_hide(settings: AutoHideSettings) -> Result<(), String> {
+    process_watch::set_auto_hide(settings)
+}
+
+#[tauri::command]
+async fn get_blacklist() -> Result<Vec<String>, String> {
+    Ok(process_watch::get_blacklist())
+}
+
+#[tauri::command]
+async fn set_blacklist(exe_names: Vec<String>) -> Result<(), String> {
+    process_watch::set_blacklist(exe_names)
+}
+
+#[tauri::command]
+async fn list_running_processes() -> Result<Vec<RunningProcess>, String> {
     #[cfg(windows)]
     {
-        create_overlay_window().map_err(|e| e.to_string())?;
-        Ok("Overlay initialized".to_string())
+        Ok(process_list::list_running_processes())
     }
     
     #[cfg(not(windows))]
     {
-        Err("Overlay is only supported on Windows".to_string())
+        Err("Process listing is only supported on Windows".to_string())
+    }
+}
+
+#[tauri::command]
+async fn attach_to_window(hwnd: Option<isize>) -> Result<(), String> {
+    backend().attach_to_window(hwnd)
+}
+
+#[tauri::command]
+async fn get_attached_window() -> Result<Option<isize>, String> {
+    backend().attached_window()
+}
+
+#[tauri::command]
+async fn set_edit_mode(enabled: bool) -> Result<(), String> {
+    backend().set_edit_mode(enabled)?;
+    
+    // Keep the dragged position once the overlay is locked again
+    if !enabled {
+        let config_path = storage::file_path(CONFIG_FILE)?;
+        storage::write_json(&config_path, &actions::live_config()?)?;
     }
+    Ok(())
 }
 
 #[tauri::command]
-async fn update_crosshair_config(config: CrosshairConfig) -> Result<(), String> {
+async fn get_edit_mode() -> Result<bool, String> {
+    backend().is_edit_mode()
+}
+
+/// Switches the crosshair to the PNG, APNG or GIF at `path`. The file is
+/// validated and decoded up front so a bad image is reported here instead of
+/// at draw time.
+#[tauri::command]
+async fn set_crosshair_image(
+    app: tauri::AppHandle,
+    path: String,
+    scale: f32,
+    fps_limit: Option<u32>,
+) -> Result<CrosshairConfig, String> {
+    crosshair_engine::load_image(&path)?;
+    if !(scale > 0.0 && scale <= 10.0) {
+        return Err("Scale must be between 0 and 10".to_string());
+    }
+    
+    modify_config(&app, |config| {
+        config.style = CrosshairStyle::Image {
+            path,
+            scale,
+            fps_limit: fps_limit.unwrap_or(30).clamp(1, 144),
+        };
+        Ok(())
+    })
+}
+
+/// Adds mirrored copies of the custom shape's elements.
+#[tauri::command]
+async fn mirror_custom_shape(app: tauri::AppHandle, axis: MirrorAxis) -> Result<CrosshairConfig, String> {
+    modify_config(&app, |config| {
+        crosshair_engine::mirror_elements(&mut config.elements, axis);
+        Ok(())
+    })
+}
+
+/// Adds copies of the custom shape's elements rotated by every multiple of
+/// `degrees`, e.g. 90 for four-way symmetry.
+#[tauri::command]
+async fn rotate_custom_shape(app: tauri::AppHandle, degrees: f32) -> Result<CrosshairConfig, String> {
+    modify_config(&app, |config| crosshair_engine::rotate_elements(&mut config.elements, degrees))
+}
+
+/// Waits for a click anywhere on screen and returns that pixel's color as
+/// "#RRGGBB", or `None` if the user right-clicked or nothing was picked in time.
+#[tauri::command]
+async fn pick_screen_color() -> Result<Option<String>, String> {
     #[cfg(windows)]
     {
-        update_config(config).map_err(|e| e.to_string())?;
-        Ok(())
+        let picked = tauri::async_runtime::spawn_blocking(|| {
+            crosshair_engine::pick_screen_color(std::time::Duration::from_secs(30))
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+        Ok(picked.map(crosshair_engine::color::to_hex))
     }
     
     #[cfg(not(windows))]
@@ -59,190 +473,494 @@ async fn update_crosshair_config(config: CrosshairConfig) -> Result<(), String>
     }
 }
 
+/// Captures the screen around the center, without the crosshair, so the user
+/// can click the game's own reticle in it. `radius` defaults to 64 pixels.
+#[tauri::command]
+async fn capture_calibration(radius: Option<i32>) -> Result<CalibrationCapture, String> {
+    calibration::capture(radius.unwrap_or(64))
+}
+
+/// Sets position_x/position_y so the crosshair sits on the point clicked at
+/// (`x`, `y`) in the last calibration capture, and returns the new config.
+#[tauri::command]
+async fn apply_calibration(app: tauri::AppHandle, x: i32, y: i32) -> Result<CrosshairConfig, String> {
+    calibration::apply(&app, x, y)
+}
+
+/// Returns the live config as a share code and as an import link.
+#[tauri::command]
+async fn export_share_code() -> Result<ShareCode, String> {
+    let config = actions::live_config()?;
+    Ok(ShareCode {
+        code: share_code::encode(&config)?,
+        link: share_code::encode_link(&config)?,
+    })
+}
+
+/// Applies the crosshair from a share code and saves it.
+#[tauri::command]
+async fn import_share_code(app: tauri::AppHandle, code: String) -> Result<CrosshairConfig, String> {
+    import_code(&app, &code)
+}
+
+fn import_code(app: &tauri::AppHandle, code: &str) -> Result<CrosshairConfig, String> {
+    let config = share_code::decode(code)?;
+    actions::apply_config(app, config.clone())?;
+    Ok(config)
+}
+
+/// Applies a config exported by another crosshair overlay and saves it.
+#[tauri::command]
+async fn import_external_config(
+    app: tauri::AppHandle,
+    path: String,
+    format: ExternalFormat,
+) -> Result<CrosshairConfig, String> {
+    let config = external_import::import(&path, format)?;
+    actions::apply_config(&app, config.clone())?;
+    Ok(config)
+}
+
+// Imports the code from a crosshair-overlay:// link passed on the command line,
+// either at startup or forwarded from a second instance. False without a link.
+fn handle_deep_link(app: &tauri::AppHandle, args: &[String]) -> bool {
+    let Some(code) = args.iter().find_map(|arg| share_code::code_from_link(arg)) else {
+        return false;
+    };
+    
+    match import_code(app, &code) {
+        Ok(_) => {
+            show_settings_window(app);
+        }
+        Err(e) => error!("Failed to import shared crosshair: {}", e),
+    }
+    true
+}
+
 #[tauri::command]
-async fn toggle_crosshair(enabled: bool) -> Result<(), String> {
+async fn get_autostart() -> Result<AutostartStatus, String> {
     #[cfg(windows)]
     {
-        toggle_overlay(enabled).map_err(|e| e.to_string())?;
-        Ok(())
+        autostart::get_autostart()
     }
     
     #[cfg(not(windows))]
     {
-        Err("Overlay is only supported on Windows".to_string())
+        Err("Autostart is only supported on Windows".to_string())
     }
 }
 
+/// Starts the app at login, optionally with only the tray icon showing.
 #[tauri::command]
-async fn get_crosshair_config() -> Result<CrosshairConfig, String> {
+async fn set_autostart(enabled: bool, minimized: Option<bool>) -> Result<(), String> {
     #[cfg(windows)]
     {
-        Ok(get_config())
+        let mut settings = app_settings::get_settings();
+        autostart::set_autostart(enabled, minimized.unwrap_or(settings.start_minimized))?;
+        settings.autostart = enabled;
+        app_settings::save_settings(&settings)
     }
     
     #[cfg(not(windows))]
     {
-        Err("Overlay is only supported on Windows".to_string())
+        Err("Autostart is only supported on Windows".to_string())
     }
 }
 
 #[tauri::command]
-async fn save_config(config: CrosshairConfig) -> Result<(), String> {
-    let config_str = serde_json::to_string_pretty(&config)
-        .map_err(|e| e.to_string())?;
-    
-    let config_path = dirs::config_dir()
-        .ok_or("Could not find config directory")?
-        .join("crosshair-overlay")
-        .join("config.json");
-    
-    std::fs::create_dir_all(config_path.parent().unwrap())
-        .map_err(|e| e.to_string())?;
-    
-    std::fs::write(config_path, config_str)
-        .map_err(|e| e.to_string())?;
-    
-    Ok(())
+async fn get_app_settings() -> Result<AppSettings, String> {
+    let settings = app_settings::get_settings();
+    // The Run entry can be removed outside the app, e.g. from Task Manager
+    #[cfg(windows)]
+    let settings = AppSettings {
+        autostart: autostart::get_autostart()?.enabled,
+        ..settings
+    };
+    Ok(settings)
 }
 
+/// Saves the app settings and applies the ones that take effect right away.
+/// start_minimized and start_disabled apply at the next launch.
 #[tauri::command]
-async fn save_preset(preset: CrosshairPreset) -> Result<(), String> {
-    let config_dir = dirs::config_dir()
-        .ok_or("Failed to get config directory")?
-        .join("crosshair-overlay");
+async fn set_app_settings(app: tauri::AppHandle, settings: AppSettings) -> Result<AppSettings, String> {
+    // Everything that can be refused is checked before anything changes, so
+    // a failed save leaves nothing applied
+    hotkeys::validate_settings(&settings.hotkeys)?;
+    gamepad::validate_settings(&settings.gamepad)?;
+    let previous = app_settings::get_settings();
     
-    std::fs::create_dir_all(&config_dir)
-        .map_err(|e| e.to_string())?;
-    
-    let presets_path = config_dir.join("presets.json");
+    #[cfg(windows)]
+    {
+        // The Run entry carries --minimized, so it follows start_minimized too
+        let minimized_changed = settings.start_minimized != previous.start_minimized;
+        if settings.autostart != previous.autostart || (settings.autostart && minimized_changed) {
+            autostart::set_autostart(settings.autostart, settings.start_minimized)?;
+        }
+    }
+    gamepad::apply_settings(&settings.gamepad)?;
     
-    // Load existing presets
-    let mut favorites_data = if presets_path.exists() {
-        let presets_str = std::fs::read_to_string(&presets_path)
-            .map_err(|e| e.to_string())?;
-        serde_json::from_str::<FavoritesData>(&presets_str)
-            .unwrap_or(FavoritesData { presets: Vec::new() })
-    } else {
-        FavoritesData { presets: Vec::new() }
+    // Only what changed. A feature the backend lacks is logged rather than
+    // failing the save, which would leave the changes above unsaved.
+    let overlay = backend();
+    let log_failure = |result: Result<(), String>| {
+        if let Err(e) = result {
+            error!("Failed to apply app setting: {}", e);
+        }
     };
+    if settings.capture_visible != previous.capture_visible {
+        log_failure(overlay.set_capture_visible(settings.capture_visible));
+    }
+    if settings.topmost_interval_ms != previous.topmost_interval_ms {
+        log_failure(overlay.set_topmost_interval(settings.topmost_interval_ms));
+    }
+    if settings.monitor != previous.monitor {
+        log_failure(overlay.set_monitor(settings.monitor));
+    }
+    if (settings.show_grid, &settings.grid) != (previous.show_grid, &previous.grid) {
+        log_failure(apply_grid(&settings));
+    }
+    if settings.keystrokes != previous.keystrokes {
+        log_failure(apply_keystrokes(&settings));
+    }
+    if settings.click_visualizer != previous.click_visualizer {
+        log_failure(apply_click_visualizer(&settings));
+    }
+    if settings.clock != previous.clock {
+        log_failure(apply_clock(&settings));
+    }
     
-    // Remove existing preset with same ID if it exists
-    favorites_data.presets.retain(|p| p.id != preset.id);
-    
-    // Add new preset
-    favorites_data.presets.push(preset);
-    
-    // Save back to file
-    let presets_str = serde_json::to_string_pretty(&favorites_data)
-        .map_err(|e| e.to_string())?;
+    // Hotkeys can only be registered from the main thread
+    if settings.hotkeys != previous.hotkeys {
+        let (previous, hotkeys) = (previous.hotkeys.clone(), settings.hotkeys.clone());
+        log_failure(
+            app.run_on_main_thread(move || hotkeys::apply_settings(&previous, &hotkeys))
+                .map_err(|e| e.to_string()),
+        );
+    }
     
-    std::fs::write(presets_path, presets_str)
-        .map_err(|e| e.to_string())?;
+    app_settings::save_settings(&settings)?;
     
-    Ok(())
+    // The tooltip names the toggle hotkey
+    refresh_tray(&app);
+    Ok(settings)
 }
 
 #[tauri::command]
-async fn load_presets() -> Result<Vec<CrosshairPreset>, String> {
-    let config_dir = dirs::config_dir()
-        .ok_or("Failed to get config directory")?
-        .join("crosshair-overlay");
-    
-    let presets_path = config_dir.join("presets.json");
-    
-    if !presets_path.exists() {
-        return Ok(Vec::new());
-    }
-    
-    let presets_str = std::fs::read_to_string(presets_path)
-        .map_err(|e| e.to_string())?;
-    
-    let favorites_data: FavoritesData = serde_json::from_str(&presets_str)
-        .unwrap_or(FavoritesData { presets: Vec::new() });
-    
-    Ok(favorites_data.presets)
+async fn list_monitors() -> Result<Vec<MonitorInfo>, String> {
+    backend().list_monitors()
 }
 
 #[tauri::command]
-async fn delete_preset(id: String) -> Result<(), String> {
-    let config_dir = dirs::config_dir()
-        .ok_or("Failed to get config directory")?
-        .join("crosshair-overlay");
+async fn get_sync_settings() -> Result<SyncSettings, String> {
+    Ok(sync::get_settings())
+}
+
+/// Saves the sync settings and, when enabled, syncs right away.
+#[tauri::command]
+async fn set_sync_settings(app: tauri::AppHandle, settings: SyncSettings) -> Result<SyncStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || sync::set_settings(&app, settings))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn sync_now(app: tauri::AppHandle) -> Result<SyncStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || sync::sync_now(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn get_sync_status() -> Result<SyncStatus, String> {
+    Ok(sync::get_status())
+}
+
+#[tauri::command]
+async fn get_recent_logs(lines: Option<usize>) -> Result<Vec<String>, String> {
+    logging::recent_lines(lines)
+}
+
+#[tauri::command]
+async fn open_log_folder(app: tauri::AppHandle) -> Result<(), String> {
+    let log_dir = logging::log_dir()?;
+    app.opener()
+        .open_path(log_dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| e.to_string())
+}
+
+/// Paint timings of the overlay, for reports about stutter.
+#[tauri::command]
+async fn get_render_metrics() -> Result<RenderMetrics, String> {
+    backend().render_metrics()
+}
+
+/// What this build and platform support, so the UI can hide what doesn't work.
+#[tauri::command]
+async fn get_app_info() -> Result<AppInfo, String> {
+    let hotkeys_available = hotkeys::toggle_hotkey_status().is_some_and(|status| status.registered);
     
-    let presets_path = config_dir.join("presets.json");
+    let overlay = backend();
     
-    if !presets_path.exists() {
-        return Ok(());
+    Ok(AppInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        platform: std::env::consts::OS.to_string(),
+        overlay_available: overlay.is_native(),
+        renderer: Some(overlay.name().to_string()),
+        monitor_count: overlay.list_monitors().map_or(0, |monitors| monitors.len()),
+        hotkeys_available,
+    })
+}
+
+/// Reverts the last config change, keeping the crosshair's visibility. Returns
+/// the restored config, or null when there is nothing to undo.
+#[tauri::command]
+async fn undo_config(app: tauri::AppHandle) -> Result<Option<CrosshairConfig>, String> {
+    history::undo(&app)
+}
+
+/// Reapplies the last undone change, or returns null when there is none.
+#[tauri::command]
+async fn redo_config(app: tauri::AppHandle) -> Result<Option<CrosshairConfig>, String> {
+    history::redo(&app)
+}
+
+#[tauri::command]
+async fn get_history_status() -> Result<HistoryStatus, String> {
+    Ok(history::status())
+}
+
+/// Applies a random crosshair within `constraints`, or within the saved
+/// randomizer settings when none are given.
+#[tauri::command]
+async fn randomize_crosshair(
+    app: tauri::AppHandle,
+    constraints: Option<RandomizeConstraints>,
+) -> Result<CrosshairConfig, String> {
+    let constraints = constraints.unwrap_or_else(|| app_settings::get_settings().randomizer);
+    randomizer::randomize_live(&app, &constraints)
+}
+
+/// Everything worth attaching to a bug report, as pretty-printed JSON.
+#[tauri::command]
+async fn generate_diagnostics() -> Result<String, String> {
+    diagnostics::generate()
+}
+
+#[tauri::command]
+async fn get_stats() -> Result<UsageStats, String> {
+    Ok(stats::get_stats())
+}
+
+#[tauri::command]
+async fn reset_stats() -> Result<UsageStats, String> {
+    stats::reset_stats()
+}
+
+#[tauri::command]
+async fn get_http_api() -> Result<HttpApiSettings, String> {
+    Ok(http_api::get_settings())
+}
+
+/// Saves the HTTP API settings and restarts the server. An empty token is
+/// replaced with a generated one, which is returned.
+#[tauri::command]
+async fn set_http_api(app: tauri::AppHandle, settings: HttpApiSettings) -> Result<HttpApiSettings, String> {
+    http_api::set_settings(&app, settings)
+}
+
+/// Shows or hides the crosshair in screen recordings and screenshots.
+#[tauri::command]
+async fn set_capture_visible(visible: bool) -> Result<AppSettings, String> {
+    backend().set_capture_visible(visible)?;
+    let mut settings = app_settings::get_settings();
+    settings.capture_visible = visible;
+    app_settings::save_settings(&settings)?;
+    Ok(settings)
+}
+
+/// Counts `seconds` down next to the crosshair, e.g. for ability cooldowns,
+/// with an optional label in front. Starting a new timer replaces the old one.
+#[tauri::command]
+async fn start_timer(seconds: f32, label: Option<String>) -> Result<(), String> {
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return Err(format!("Invalid timer length: {}", seconds));
     }
     
-    let presets_str = std::fs::read_to_string(&presets_path)
-        .map_err(|e| e.to_string())?;
-    
-    let mut favorites_data: FavoritesData = serde_json::from_str(&presets_str)
-        .unwrap_or(FavoritesData { presets: Vec::new() });
-    
-    // Remove preset with matching ID
-    favorites_data.presets.retain(|p| p.id != id);
-    
-    // Save back to file
-    let presets_str = serde_json::to_string_pretty(&favorites_data)
-        .map_err(|e| e.to_string())?;
-    
-    std::fs::write(presets_path, presets_str)
-        .map_err(|e| e.to_string())?;
-    
-    Ok(())
+    backend().start_timer(seconds, label.as_deref().unwrap_or_default())
 }
 
+/// Shows or hides the alignment grid. It is independent of the crosshair and
+/// stays up while that is toggled off.
 #[tauri::command]
-async fn load_config() -> Result<CrosshairConfig, String> {
-    let config_path = dirs::config_dir()
-        .ok_or("Could not find config directory")?
-        .join("crosshair-overlay")
-        .join("config.json");
+async fn set_grid_visible(app: tauri::AppHandle, visible: bool) -> Result<AppSettings, String> {
+    show_grid(&app, visible)
+}
+
+// Applies and saves `visible` and tells the UI, also used by the grid hotkey
+fn show_grid(app: &tauri::AppHandle, visible: bool) -> Result<AppSettings, String> {
+    let mut settings = app_settings::get_settings();
+    settings.show_grid = visible;
+    apply_grid(&settings)?;
+    app_settings::save_settings(&settings)?;
     
-    if !config_path.exists() {
-        return Ok(CrosshairConfig::default());
+    if let Err(e) = app.emit("grid-toggled", visible) {
+        error!("Failed to emit grid event: {}", e);
     }
+    Ok(settings)
+}
+
+fn apply_grid(settings: &AppSettings) -> Result<(), String> {
+    backend().set_grid(settings.show_grid.then(|| settings.grid.clone()))
+}
+
+fn apply_keystrokes(settings: &AppSettings) -> Result<(), String> {
+    backend().set_keystroke_display(settings.keystrokes.enabled.then(|| settings.keystrokes.clone()))
+}
+
+fn apply_click_visualizer(settings: &AppSettings) -> Result<(), String> {
+    backend().set_click_visualizer(settings.click_visualizer.enabled.then(|| settings.click_visualizer.clone()))
+}
+
+fn apply_clock(settings: &AppSettings) -> Result<(), String> {
+    backend().set_clock_display(settings.clock.enabled.then(|| settings.clock.clone()))
+}
+
+#[tauri::command]
+async fn get_colorblind_palettes() -> Result<Vec<Palette>, String> {
+    Ok(crosshair_engine::palettes())
+}
+
+/// Shows how `color` ("#RRGGBB"), or the current crosshair color when omitted,
+/// looks under each color vision deficiency.
+#[tauri::command]
+async fn simulate_color_vision(color: Option<String>) -> Result<Vec<SimulatedColor>, String> {
+    let color = match color {
+        Some(color) => crosshair_engine::color::parse_hex(&color).ok_or(format!("Invalid color \"{}\"", color))?,
+        None => current_color()?,
+    };
     
-    let config_str = std::fs::read_to_string(config_path)
-        .map_err(|e| e.to_string())?;
-    
-    let config: CrosshairConfig = serde_json::from_str(&config_str)
-        .map_err(|e| e.to_string())?;
-    
+    Ok(Deficiency::ALL
+        .into_iter()
+        .map(|deficiency| SimulatedColor {
+            deficiency,
+            color: crosshair_engine::color::to_hex(crosshair_engine::simulate(color, deficiency)),
+        })
+        .collect())
+}
+
+fn current_color() -> Result<u32, String> {
+    Ok(backend().config().color)
+}
+
+// Applies `change` to the live config, then saves and returns the result
+fn modify_config(
+    app: &tauri::AppHandle,
+    change: impl FnOnce(&mut CrosshairConfig) -> Result<(), String>,
+) -> Result<CrosshairConfig, String> {
+    let mut config = actions::live_config()?;
+    change(&mut config)?;
+    actions::apply_config(app, config.clone())?;
     Ok(config)
 }
 
+// Shows and focuses the settings window, creating it first if it was never
+// opened (headless mode)
+fn show_settings_window<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let window = match app.get_webview_window("main") {
+        Some(window) => window,
+        None => match create_settings_window(app, true) {
+            Ok(window) => window,
+            Err(e) => {
+                error!("Failed to open settings window: {}", e);
+                return;
+            }
+        },
+    };
+    let _ = window.show();
+    let _ = window.set_focus();
+}
+
+// The window is declared in tauri.conf.json with `create: false` so headless
+// mode can skip it
+fn create_settings_window<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    visible: bool,
+) -> tauri::Result<tauri::WebviewWindow<R>> {
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|w| w.label == "main")
+        .cloned()
+        .unwrap_or_default();
+    tauri::WebviewWindowBuilder::from_config(app, &config)?
+        .visible(visible)
+        .build()
+}
+
+// Closes the overlay window and joins its thread before the process exits
+fn shutdown_overlay() {
+    if let Err(e) = backend().destroy() {
+        error!("Failed to shut down overlay: {}", e);
+    }
+}
+
 fn create_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
-    let toggle_item = MenuItem::with_id(app, "toggle", "Toggle Crosshair", true, None::<&str>)?;
+    let config = actions::live_config().unwrap_or_default();
+    let enabled = config.enabled;
+    let toggle_item = CheckMenuItem::with_id(app, "toggle", "Toggle Crosshair", true, enabled, None::<&str>)?;
     let settings_item = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
     let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
     
-    let menu = Menu::with_items(app, &[&toggle_item, &settings_item, &quit_item])?;
+    let opacity_menu = Submenu::with_id(app, "opacity", "Opacity", true)?;
+    for (label, _) in TRAY_OPACITIES {
+        opacity_menu.append(&MenuItem::with_id(app, format!("opacity:{}", label), *label, true, None::<&str>)?)?;
+    }
+    let size_menu = Submenu::with_id(app, "size", "Size", true)?;
+    for (label, _) in TRAY_SIZES {
+        size_menu.append(&MenuItem::with_id(app, format!("size:{}", label), *label, true, None::<&str>)?)?;
+    }
     
-    let _ = TrayIconBuilder::new()
-        .icon(app.default_window_icon().unwrap().clone())
+    let menu = Menu::with_items(app, &[&toggle_item, &opacity_menu, &size_menu, &settings_item, &quit_item])?;
+    
+    match app.try_state::<TrayToggleItem<R>>() {
+        Some(item) => *item.0.lock().unwrap() = Some(toggle_item.clone()),
+        None => {
+            app.manage(TrayToggleItem(Mutex::new(Some(toggle_item.clone()))));
+        }
+    }
+    
+    let _ = TrayIconBuilder::with_id(TRAY_ID)
+        .icon(Image::from_bytes(tray_icon(enabled))?)
+        .tooltip(tray_tooltip(&config))
         .menu(&menu)
         .show_menu_on_left_click(false)
         .on_menu_event(move |app, event| match event.id.as_ref() {
             "toggle" => {
-                #[cfg(windows)]
-                {
-                    let config = get_config();
-                    let _ = toggle_overlay(!config.enabled);
+                let enabled = !backend().config().enabled;
+                if backend().toggle(enabled).is_ok() {
+                    let _ = app.emit("crosshair-toggled", enabled);
                 }
             }
             "settings" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
+                show_settings_window(app);
             }
             "quit" => {
+                shutdown_overlay();
                 app.exit(0);
             }
-            _ => {}
+            id => {
+                if let Some(label) = id.strip_prefix("opacity:") {
+                    if let Some(&(_, opacity)) = TRAY_OPACITIES.iter().find(|(l, _)| *l == label) {
+                        quick_adjust(app, |config| config.opacity = opacity);
+                    }
+                } else if let Some(label) = id.strip_prefix("size:") {
+                    if let Some(&(_, size)) = TRAY_SIZES.iter().find(|(l, _)| *l == label) {
+                        quick_adjust(app, |config| config.size = size);
+                    }
+                }
+            }
         })
         .on_tray_icon_event(|tray, event| {
             if let TrayIconEvent::Click {
@@ -251,10 +969,7 @@ fn create_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
                 ..
             } = event
             {
-                if let Some(window) = tray.app_handle().get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
+                show_settings_window(tray.app_handle());
             }
         })
         .build(app)?;
@@ -262,25 +977,236 @@ fn create_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
     Ok(())
 }
 
+// Applies a tray quick adjustment to the live config and saves it
+fn quick_adjust<R: Runtime>(app: &tauri::AppHandle<R>, adjust: impl FnOnce(&mut CrosshairConfig)) {
+    let result = actions::live_config().and_then(|mut config| {
+        adjust(&mut config);
+        actions::apply_config(app, config)
+    });
+    if let Err(e) = result {
+        error!("Failed to adjust crosshair: {}", e);
+    }
+}
+
+fn tray_icon(enabled: bool) -> &'static [u8] {
+    if enabled {
+        TRAY_ICON_ACTIVE
+    } else {
+        TRAY_ICON_INACTIVE
+    }
+}
+
+// e.g. "Crosshair Overlay — Preset: Dot Green — ON (F9)"
+fn tray_tooltip(config: &CrosshairConfig) -> String {
+    let preset = match matching_preset_name(config) {
+        Some(name) => format!("Preset: {}", name),
+        None => "Custom".to_string(),
+    };
+    let state = if config.enabled { "ON" } else { "OFF" };
+    let hotkey = app_settings::get_settings().hotkeys.toggle;
+    format!("Crosshair Overlay — {} — {} ({})", preset, state, hotkey)
+}
+
+// The UI applies presets as plain config updates, so the live config is
+// matched against the saved presets instead of tracking which was applied
+fn matching_preset(config: &CrosshairConfig) -> Option<CrosshairPreset> {
+    let without_enabled = |config: &CrosshairConfig| {
+        let mut value = serde_json::to_value(config).ok()?;
+        value.as_object_mut()?.remove("enabled");
+        Some(value)
+    };
+    let current = without_enabled(config)?;
+    load_favorites()
+        .ok()?
+        .presets
+        .into_iter()
+        .find(|preset| without_enabled(&preset.config).as_ref() == Some(&current))
+}
+
+fn matching_preset_name(config: &CrosshairConfig) -> Option<String> {
+    matching_preset(config).map(|preset| preset.name)
+}
+
+// Brings the tray icon, tooltip and the toggle item's check mark in line with `config`
+fn update_tray_state<R: Runtime>(app: &tauri::AppHandle<R>, config: &CrosshairConfig) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        match Image::from_bytes(tray_icon(config.enabled)) {
+            Ok(icon) => {
+                let _ = tray.set_icon(Some(icon));
+            }
+            Err(e) => error!("Failed to load tray icon: {}", e),
+        }
+        let _ = tray.set_tooltip(Some(tray_tooltip(config)));
+    }
+    
+    if let Some(item) = app.try_state::<TrayToggleItem<R>>() {
+        if let Some(item) = item.0.lock().unwrap().as_ref() {
+            let _ = item.set_checked(config.enabled);
+        }
+    }
+}
+
+fn refresh_tray<R: Runtime>(app: &tauri::AppHandle<R>) {
+    if let Ok(config) = actions::live_config() {
+        update_tray_state(app, &config);
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Nothing else can report the failure yet
+    if let Err(e) = logging::init() {
+        eprintln!("Failed to set up logging: {}", e);
+    }
+    info!("Crosshair Overlay {} starting", env!("CARGO_PKG_VERSION"));
+    
     tauri::Builder::default()
+        // Must come first: a second launch (a share link, a CLI command or just
+        // opening the app again) forwards its arguments here and exits
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            let handled = handle_deep_link(app, &args) | cli::run(app, &args);
+            if !handled {
+                show_settings_window(app);
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
+            // Without a native overlay, draw the crosshair in a transparent webview
+            #[cfg(not(windows))]
+            crosshair_engine::set_backend(std::sync::Arc::new(webview_overlay::WebviewBackend::new(app.handle().clone())));
+            
+            if let Err(e) = app_settings::load_settings() {
+                error!("Failed to load app settings: {}", e);
+            }
+            let settings = app_settings::get_settings();
+            
+            let args: Vec<String> = std::env::args().collect();
+            let headless = cli::has_flag(&args, "--headless");
+            if !headless {
+                let minimized = settings.start_minimized || cli::has_flag(&args, "--minimized");
+                create_settings_window(app.handle(), !minimized)?;
+            }
+            
             create_tray(app.handle())?;
             
+            // Every way of toggling emits this, and the UI pushes its saved config on startup
+            let app_handle = app.handle().clone();
+            app.listen_any("crosshair-toggled", move |_| refresh_tray(&app_handle));
+            // Preset and profile payloads carry the applied config as well
+            for event in ["crosshair-config-changed", "preset-applied", "profile-applied"] {
+                let app_handle = app.handle().clone();
+                app.listen_any(event, move |event| {
+                    if let Ok(payload) = serde_json::from_str::<ConfigPayload>(event.payload()) {
+                        update_tray_state(&app_handle, &payload.config);
+                    }
+                });
+            }
+            
+            // Explorer restarts drop our tray icon, so rebuild it when the taskbar comes back
+            let app_handle = app.handle().clone();
+            backend().set_taskbar_created_handler(Box::new(move || {
+                let handle = app_handle.clone();
+                let _ = app_handle.run_on_main_thread(move || {
+                    let _ = handle.remove_tray_by_id(TRAY_ID);
+                    if let Err(e) = create_tray(&handle) {
+                        error!("Failed to recreate tray icon: {}", e);
+                    }
+                });
+            }));
+            
+            // Let the settings UI follow the crosshair while it's dragged in edit mode
+            let app_handle = app.handle().clone();
+            backend().set_position_changed_handler(Box::new(move |position_x, position_y| {
+                let payload = PositionPayload { position_x, position_y };
+                if let Err(e) = app_handle.emit("crosshair-position-changed", payload) {
+                    error!("Failed to emit position change: {}", e);
+                }
+            }));
+            
             // Setup global hotkeys
-            if let Err(e) = setup_global_hotkeys(app.handle().clone()) {
-                eprintln!("Failed to setup hotkeys: {}", e);
+            if let Err(e) = setup_global_hotkeys(app.handle().clone(), &settings.hotkeys) {
+                error!("Failed to setup hotkeys: {}", e);
             }
             
-            // Initialize overlay on startup for Windows
+            // Normally the settings UI pushes the saved config; without it
+            // the overlay has to load it itself
+            if headless {
+                match storage::file_path(CONFIG_FILE).and_then(|path| storage::read_json::<CrosshairConfig>(&path)) {
+                    Ok(Some(mut config)) => {
+                        app_settings::apply_startup_settings(&mut config);
+                        if let Err(e) = actions::show_config(app.handle(), config) {
+                            error!("Failed to apply saved config: {}", e);
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => error!("Failed to load saved config: {}", e),
+                }
+            }
+            
+            // These are kept by the engine across overlay restarts. Backends
+            // without some of them just skip those.
+            let overlay = backend();
+            let _ = overlay.set_capture_visible(settings.capture_visible);
+            let _ = overlay.set_topmost_interval(settings.topmost_interval_ms);
+            let _ = overlay.set_monitor(settings.monitor);
+            let _ = apply_grid(&settings);
+            let _ = apply_keystrokes(&settings);
+            let _ = apply_click_visualizer(&settings);
+            let _ = apply_clock(&settings);
+            
+            tauri::async_runtime::spawn(async {
+                let _ = init_overlay().await;
+            });
+            
+            // Load per-game profiles and start watching for game switches
+            if let Err(e) = profiles::load_profiles() {
+                error!("Failed to load game profiles: {}", e);
+            }
+            if let Err(e) = process_watch::load_settings() {
+                error!("Failed to load process watch settings: {}", e);
+            }
+            #[cfg(windows)]
+            process_watch::start_watcher(app.handle().clone());
+            
+            if let Err(e) = gamepad::apply_settings(&settings.gamepad) {
+                error!("Failed to bind controller combos: {}", e);
+            }
             #[cfg(windows)]
+            gamepad::start_watcher(app.handle().clone());
+            
+            if let Err(e) = sync::load_settings() {
+                error!("Failed to load sync settings: {}", e);
+            }
+            sync::start_sync_thread(app.handle().clone());
+            
+            toast::notify_preset_switches(app.handle());
+            history::track_changes(app.handle());
+            
+            if let Err(e) = stats::load_stats() {
+                error!("Failed to load stats: {}", e);
+            }
+            stats::start_tracking(app.handle());
+            
+            // Opt-in local control API for scripts and Stream Deck
+            event_stream::forward_events(app.handle());
+            if let Err(e) = http_api::load_settings().and_then(|_| http_api::restart(app.handle())) {
+                error!("Failed to start HTTP API: {}", e);
+            }
+            #[cfg(windows)]
+            pipe_server::start_pipe_server(app.handle().clone());
+            
+            // Installers register the URI scheme; dev and portable builds do it here
+            #[cfg(any(windows, target_os = "linux"))]
             {
-                tauri::async_runtime::spawn(async {
-                    let _ = init_overlay().await;
-                });
+                use tauri_plugin_deep_link::DeepLinkExt;
+                if let Err(e) = app.deep_link().register_all() {
+                    error!("Failed to register {}:// links: {}", share_code::URI_SCHEME, e);
+                }
             }
+            handle_deep_link(app.handle(), &args);
+            cli::run(app.handle(), &args);
             
             Ok(())
         })
@@ -295,15 +1221,81 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             init_overlay,
+            get_overlay_status,
+            restart_overlay,
             update_crosshair_config,
+            patch_crosshair_config,
+            get_default_config,
+            reset_to_defaults,
+            begin_config_transaction,
+            commit_config_transaction,
             toggle_crosshair,
             get_crosshair_config,
             save_config,
             load_config,
             save_preset,
             load_presets,
+            import_presets,
             delete_preset,
+            list_backups,
+            restore_backup,
+            export_all,
+            import_all,
+            set_game_profile,
+            get_game_profiles,
+            get_auto_hide,
+            set_auto_hide,
+            get_blacklist,
+            set_blacklist,
+            list_running_processes,
+            attach_to_window,
+            get_attached_window,
+            set_edit_mode,
+            get_edit_mode,
+            set_crosshair_image,
+            mirror_custom_shape,
+            rotate_custom_shape,
+            pick_screen_color,
+            capture_calibration,
+            apply_calibration,
+            get_colorblind_palettes,
+            simulate_color_vision,
+            set_capture_visible,
+            set_grid_visible,
+            start_timer,
+            get_http_api,
+            set_http_api,
+            export_share_code,
+            import_share_code,
+            import_external_config,
+            import_preset_from_url,
+            get_sync_settings,
+            set_sync_settings,
+            sync_now,
+            get_sync_status,
+            get_autostart,
+            set_autostart,
+            get_app_settings,
+            set_app_settings,
+            list_monitors,
+            get_stats,
+            reset_stats,
+            get_recent_logs,
+            open_log_folder,
+            generate_diagnostics,
+            get_app_info,
+            get_render_metrics,
+            undo_config,
+            redo_config,
+            get_history_status,
+            randomize_crosshair,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                hotkeys::shutdown_hotkeys();
+                shutdown_overlay();
+            }
+        });
 }
\ No newline at end of file
@@ -0,0 +1,74 @@
+//! Log file setup. Everything logged through `tracing`, the engine included,
+//! goes to a daily log file in the config directory and to stderr.
+
+use std::path::PathBuf;
+use tracing::level_filters::LevelFilter;
+use tracing_appender::rolling::{Builder, Rotation};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt};
+
+use crate::storage;
+
+const LOG_DIR: &str = "logs";
+const LOG_PREFIX: &str = "crosshair-overlay";
+const LOG_SUFFIX: &str = "log";
+
+// A week of daily files; older ones are deleted on rotation
+const MAX_LOG_FILES: usize = 7;
+
+const DEFAULT_RECENT_LINES: usize = 200;
+const MAX_RECENT_LINES: usize = 5000;
+
+/// Returns the log directory, creating it if it doesn't exist yet.
+pub fn log_dir() -> Result<PathBuf, String> {
+    let log_dir = storage::config_dir()?.join(LOG_DIR);
+    std::fs::create_dir_all(&log_dir).map_err(|e| e.to_string())?;
+    Ok(log_dir)
+}
+
+/// Installs the global subscriber. Call once, before anything is logged.
+pub fn init() -> Result<(), String> {
+    let appender = Builder::new()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_PREFIX)
+        .filename_suffix(LOG_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(log_dir()?)
+        .map_err(|e| e.to_string())?;
+    
+    tracing_subscriber::registry()
+        .with(fmt::layer().with_writer(appender).with_ansi(false))
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(LevelFilter::INFO)
+        .try_init()
+        .map_err(|e| e.to_string())
+}
+
+/// The last `lines` lines of the current log file, oldest first.
+pub fn recent_lines(lines: Option<usize>) -> Result<Vec<String>, String> {
+    let lines = lines.unwrap_or(DEFAULT_RECENT_LINES).min(MAX_RECENT_LINES);
+    let Some(path) = latest_log_file()? else {
+        return Ok(Vec::new());
+    };
+    
+    let contents = std::fs::read(&path).map_err(|e| e.to_string())?;
+    let contents = String::from_utf8_lossy(&contents);
+    let all: Vec<&str> = contents.lines().collect();
+    Ok(all[all.len().saturating_sub(lines)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect())
+}
+
+// Daily files are named crosshair-overlay.YYYY-MM-DD.log, so the newest sorts last
+fn latest_log_file() -> Result<Option<PathBuf>, String> {
+    let entries = std::fs::read_dir(log_dir()?).map_err(|e| e.to_string())?;
+    Ok(entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(LOG_PREFIX) && name.ends_with(LOG_SUFFIX))
+        })
+        .max())
+}
\ No newline at end of file
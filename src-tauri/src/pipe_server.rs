@@ -0,0 +1,150 @@
+//! Named pipe server at `\\.\pipe\crosshair-overlay` for PowerShell, AHK and
+//! other scripts. Clients write one JSON command per line and get one JSON
+//! reply per line:
+//!
+//! ```text
+//! {"command": "toggle"}                         -> {"ok": true}
+//! {"command": "toggle", "enabled": false}
+//! {"command": "apply_preset", "id": "..."}
+//! {"command": "set_color", "color": "#00FF00"}  -> {"ok": false, "error": "..."} on failure
+//! {"command": "patch_config", "patch": {"size": 8, "gap": 3}}
+//! ```
+
+use serde::Deserialize;
+use tauri::AppHandle;
+use windows::{
+    core::w,
+    Win32::{
+        Foundation::{CloseHandle, ERROR_PIPE_CONNECTED, HANDLE},
+        Storage::FileSystem::{FlushFileBuffers, ReadFile, WriteFile, PIPE_ACCESS_DUPLEX},
+        System::Pipes::{
+            ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, PIPE_READMODE_BYTE,
+            PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+        },
+    },
+};
+use tracing::error;
+
+use crate::actions::{apply_preset, live_config, patch_config, set_color, set_enabled};
+
+const PIPE_BUFFER_SIZE: u32 = 4096;
+
+// A line longer than this can't be a valid command, so the client is dropped
+const MAX_LINE_BYTES: usize = 64 * 1024;
+
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum PipeCommand {
+    Toggle { enabled: Option<bool> },
+    ApplyPreset { id: String },
+    SetColor { color: String },
+    PatchConfig { patch: serde_json::Value },
+}
+
+// HANDLE wraps a raw pointer; pipe handles are fine to use from any thread
+struct PipeHandle(HANDLE);
+
+unsafe impl Send for PipeHandle {}
+
+/// Spawns the thread accepting pipe clients, each served on its own thread.
+pub fn start_pipe_server(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let pipe = unsafe {
+            CreateNamedPipeW(
+                w!(r"\\.\pipe\crosshair-overlay"),
+                PIPE_ACCESS_DUPLEX,
+                PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                PIPE_BUFFER_SIZE,
+                PIPE_BUFFER_SIZE,
+                0,
+                None,
+            )
+        };
+        if pipe.is_invalid() {
+            error!("Failed to create named pipe: {}", windows::core::Error::from_win32());
+            return;
+        }
+        
+        // A client that connected between create and connect reports ERROR_PIPE_CONNECTED
+        let connected = match unsafe { ConnectNamedPipe(pipe, None) } {
+            Ok(()) => true,
+            Err(e) => e.code() == ERROR_PIPE_CONNECTED.to_hresult(),
+        };
+        if !connected {
+            unsafe {
+                let _ = CloseHandle(pipe);
+            }
+            continue;
+        }
+        
+        let app = app.clone();
+        let client = PipeHandle(pipe);
+        std::thread::spawn(move || {
+            // Move the whole wrapper in, not just its non-Send field
+            let client = client;
+            serve_client(&app, client.0);
+            unsafe {
+                let _ = FlushFileBuffers(client.0);
+                let _ = DisconnectNamedPipe(client.0);
+                let _ = CloseHandle(client.0);
+            }
+        });
+    });
+}
+
+fn serve_client(app: &AppHandle, pipe: HANDLE) {
+    let mut pending = Vec::new();
+    let mut buffer = [0u8; PIPE_BUFFER_SIZE as usize];
+    
+    loop {
+        let mut read = 0u32;
+        if unsafe { ReadFile(pipe, Some(&mut buffer), Some(&mut read), None) }.is_err() || read == 0 {
+            return;
+        }
+        pending.extend_from_slice(&buffer[..read as usize]);
+        
+        while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            if line.trim().is_empty() {
+                continue;
+            }
+            
+            let reply = match run_command(app, line.trim()) {
+                Ok(()) => serde_json::json!({ "ok": true }),
+                Err(e) => serde_json::json!({ "ok": false, "error": e }),
+            };
+            let reply = format!("{}\n", reply);
+            if unsafe { WriteFile(pipe, Some(reply.as_bytes()), None, None) }.is_err() {
+                return;
+            }
+        }
+        
+        if pending.len() > MAX_LINE_BYTES {
+            return;
+        }
+    }
+}
+
+fn run_command(app: &AppHandle, line: &str) -> Result<(), String> {
+    let command: PipeCommand = serde_json::from_str(line).map_err(|e| e.to_string())?;
+    match command {
+        PipeCommand::Toggle { enabled } => {
+            let enabled = match enabled {
+                Some(enabled) => enabled,
+                None => !live_config()?.enabled,
+            };
+            set_enabled(app, enabled)
+        }
+        PipeCommand::ApplyPreset { id } => {
+            if apply_preset(app, &id)? {
+                Ok(())
+            } else {
+                Err(format!("Preset not found: {}", id))
+            }
+        }
+        PipeCommand::SetColor { color } => set_color(app, &color),
+        PipeCommand::PatchConfig { patch } => patch_config(app, patch).map(|_| ()),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,220 @@
+//! Integrity checks for configs arriving from outside (preset downloads and
+//! files, share codes, other overlays' exports). A config that parses can
+//! still be corrupt or hostile, e.g. a million shapes or a 10^9 pixel arm,
+//! so imports are held to limits no real crosshair comes near. Image
+//! crosshairs from outside may only use images in the app's images folder:
+//! any other path could point at a local file or at a network share that
+//! Windows would hand the user's credentials to.
+//!
+//! Shared presets may also carry a `checksum` of their config: `sha1:`
+//! followed by the hex SHA-1 of the config's compact JSON with its keys
+//! sorted, so whitespace and key order don't matter.
+
+use serde_json::Value;
+use std::path::{Component, Path};
+
+use crate::storage::{self, IMAGES_DIR};
+use crosshair_engine::{color, CrosshairConfig, CrosshairStyle, Element};
+
+const MAX_ELEMENTS: usize = 256;
+const MAX_POLYGON_POINTS: usize = 64;
+const MAX_TEXTS: usize = 16;
+const MAX_TEXT_LENGTH: usize = 200;
+const MAX_RESOLUTION_OVERRIDES: usize = 32;
+
+// Largest length or offset in pixels, either sign; past any real screen
+const MAX_PIXELS: i32 = 4096;
+
+// A secondary config may have its own, but not endlessly
+const MAX_SECONDARY_DEPTH: usize = 4;
+
+const CHECKSUM_PREFIX: &str = "sha1:";
+
+/// Fails with the first limit `config` breaks, or when it uses an image from
+/// outside the images folder. For configs from outside the app.
+pub fn check_config(config: &CrosshairConfig) -> Result<(), String> {
+    check_limits(config)?;
+    check_image_paths(config)
+}
+
+/// Only the limits, for configs made in the app, whose images are wherever
+/// the user picked them.
+pub fn check_limits(config: &CrosshairConfig) -> Result<(), String> {
+    check_level(config, 0)
+}
+
+fn check_image_paths(config: &CrosshairConfig) -> Result<(), String> {
+    if let CrosshairStyle::Image { path, .. } = &config.style {
+        let images_dir = storage::file_path(IMAGES_DIR)?;
+        let path = Path::new(path);
+        let inside = path.starts_with(&images_dir) && !path.components().any(|c| c == Component::ParentDir);
+        if !inside {
+            return Err(format!(
+                "Shared image crosshairs can only use images in {}",
+                images_dir.display()
+            ));
+        }
+    }
+    match config.secondary_config.as_deref() {
+        Some(secondary) => check_image_paths(secondary),
+        None => Ok(()),
+    }
+}
+
+fn check_level(config: &CrosshairConfig, depth: usize) -> Result<(), String> {
+    if depth > MAX_SECONDARY_DEPTH {
+        return Err(format!("Secondary configs are nested more than {} deep", MAX_SECONDARY_DEPTH));
+    }
+    
+    check_count("shapes", config.elements.len(), MAX_ELEMENTS)?;
+    check_count("texts", config.texts.len(), MAX_TEXTS)?;
+    check_count("resolution overrides", config.resolution_overrides.len(), MAX_RESOLUTION_OVERRIDES)?;
+    
+    for (name, value) in [
+        ("size", config.size),
+        ("thickness", config.thickness),
+        ("gap", config.gap),
+        ("dot size", config.dot_size),
+    ] {
+        check_size(name, value)?;
+    }
+    for (name, value) in [
+        ("outline thickness", config.outline_thickness),
+        ("T length", config.t_length),
+        ("shadow offset", config.shadow_offset),
+        ("shadow blur", config.shadow_blur),
+        ("dead zone radius", config.dead_zone_radius),
+    ] {
+        check_pixels(name, value)?;
+    }
+    if !(0.0..=1.0).contains(&config.opacity) {
+        return Err(format!("Opacity {} is not between 0 and 1", config.opacity));
+    }
+    for (name, value) in [
+        ("horizontal position", config.position_x),
+        ("vertical position", config.position_y),
+        ("rotation", config.rotation),
+    ] {
+        if !value.is_finite() {
+            return Err(format!("The {} is not a number", name));
+        }
+    }
+    // Nobody shares an invisible crosshair; it's a damaged file. Custom and
+    // image crosshairs draw in their own colors.
+    let own_colors = matches!(config.style, CrosshairStyle::Custom | CrosshairStyle::Image { .. });
+    if !own_colors && color::alpha(config.color) == 0 {
+        return Err("The crosshair color is fully transparent".to_string());
+    }
+    
+    for element in &config.elements {
+        check_element(element)?;
+    }
+    for text in &config.texts {
+        if text.text.chars().count() > MAX_TEXT_LENGTH {
+            return Err(format!("A text is longer than {} characters", MAX_TEXT_LENGTH));
+        }
+        check_pixels("text size", text.size)?;
+        check_pixels("text offset", text.offset_x)?;
+        check_pixels("text offset", text.offset_y)?;
+    }
+    
+    match config.secondary_config.as_deref() {
+        Some(secondary) => check_level(secondary, depth + 1),
+        None => Ok(()),
+    }
+}
+
+fn check_element(element: &Element) -> Result<(), String> {
+    let pixels = match element {
+        Element::Line(line) => vec![line.start_x, line.start_y, line.end_x, line.end_y, line.thickness],
+        Element::Circle { x, y, radius, thickness, .. } => vec![*x, *y, *radius, *thickness],
+        Element::Rect { x, y, width, height, thickness, .. } => vec![*x, *y, *width, *height, *thickness],
+        Element::Arc { x, y, radius, thickness, start_angle, sweep_angle, .. } => {
+            if !start_angle.is_finite() || !sweep_angle.is_finite() {
+                return Err("An arc angle is not a number".to_string());
+            }
+            vec![*x, *y, *radius, *thickness]
+        }
+        Element::Polygon { points, thickness, .. } => {
+            check_count("polygon points", points.len(), MAX_POLYGON_POINTS)?;
+            points.iter().flat_map(|point| [point.x, point.y]).chain([*thickness]).collect()
+        }
+    };
+    pixels.into_iter().try_for_each(|value| check_pixels("shape coordinate", value))
+}
+
+fn check_count(name: &str, count: usize, max: usize) -> Result<(), String> {
+    if count > max {
+        return Err(format!("Too many {} ({}, at most {})", name, count, max));
+    }
+    Ok(())
+}
+
+fn check_pixels(name: &str, value: i32) -> Result<(), String> {
+    if value.unsigned_abs() > MAX_PIXELS as u32 {
+        return Err(format!("The {} of {} pixels is out of range (at most {})", name, value, MAX_PIXELS));
+    }
+    Ok(())
+}
+
+// Sizes are fractional, so they can also be NaN or infinite
+fn check_size(name: &str, value: f32) -> Result<(), String> {
+    if !value.is_finite() {
+        return Err(format!("The {} is not a number", name));
+    }
+    if value.abs() > MAX_PIXELS as f32 {
+        return Err(format!("The {} of {} pixels is out of range (at most {})", name, value, MAX_PIXELS));
+    }
+    Ok(())
+}
+
+/// The checksum a preset should carry for `config`, the config's JSON.
+pub fn checksum(config: &Value) -> String {
+    let mut json = String::new();
+    write_sorted(config, &mut json);
+    format!("{}{}", CHECKSUM_PREFIX, sha1_smol::Sha1::from(json).digest())
+}
+
+// Compact JSON with object keys in sorted order, whatever order serde_json
+// keeps them in
+fn write_sorted(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_sorted(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_sorted(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+/// Fails unless `expected` is the checksum of `config`, the config's JSON.
+pub fn verify_checksum(config: &Value, expected: &str) -> Result<(), String> {
+    let expected = expected.trim();
+    if !expected.starts_with(CHECKSUM_PREFIX) {
+        return Err(format!("Unsupported checksum \"{}\", expected {}...", expected, CHECKSUM_PREFIX));
+    }
+    if !checksum(config).eq_ignore_ascii_case(expected) {
+        return Err("The preset is damaged: its checksum doesn't match its config".to_string());
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,181 @@
+//! Downloads presets shared on websites. A URL may point at an exported
+//! preset (`{"name": ..., "config": ...}` plus optional `author`,
+//! `description`, `game`, `source_url` and `checksum`), a bare config, or a
+//! share code. Whatever it holds must pass the preset_check limits.
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::io::Read;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::preset_check;
+use crate::share_code;
+use crate::CrosshairPreset;
+use crosshair_engine::CrosshairConfig;
+
+// Presets are a few KB; anything much bigger isn't one
+const MAX_DOWNLOAD_BYTES: u64 = 512 * 1024;
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_NAME_LENGTH: usize = 64;
+const MAX_DESCRIPTION_LENGTH: usize = 500;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Download {
+    Preset {
+        name: String,
+        config: CrosshairConfig,
+        #[serde(default)]
+        author: Option<String>,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        game: Option<String>,
+        #[serde(default)]
+        source_url: Option<String>,
+    },
+    Config(CrosshairConfig),
+}
+
+// What a download says about itself besides the config
+#[derive(Default)]
+struct Metadata {
+    name: Option<String>,
+    author: Option<String>,
+    description: Option<String>,
+    game: Option<String>,
+    source_url: Option<String>,
+}
+
+/// Fetches and validates the preset at `url`. The result gets a fresh id, so
+/// importing never replaces an existing preset.
+pub fn download_preset(url: &str) -> Result<CrosshairPreset, String> {
+    let url = url.trim();
+    if !url.get(..8).is_some_and(|scheme| scheme.eq_ignore_ascii_case("https://")) {
+        return Err("Only https:// URLs can be imported".to_string());
+    }
+    
+    let response = ureq::get(url)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .call()
+        .map_err(|e| format!("Download failed: {}", e))?;
+    
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_DOWNLOAD_BYTES + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| format!("Download failed: {}", e))?;
+    if body.len() as u64 > MAX_DOWNLOAD_BYTES {
+        return Err("The download is too large to be a preset".to_string());
+    }
+    
+    let text = String::from_utf8(body).map_err(|_| "The download is not a preset")?;
+    let text = text.trim();
+    let (metadata, config) = if text.starts_with('{') {
+        let value: Value = serde_json::from_str(text).map_err(|e| format!("The download is malformed JSON: {}", e))?;
+        if let (Some(checksum), Some(config)) = (value.get("checksum"), value.get("config")) {
+            let checksum = checksum.as_str().ok_or("The preset's checksum is not a string")?;
+            preset_check::verify_checksum(config, checksum)?;
+        }
+        
+        match serde_json::from_value::<Download>(value.clone()) {
+            Ok(Download::Preset { name, config, author, description, game, source_url }) => {
+                let metadata = Metadata {
+                    name: Some(name),
+                    author,
+                    description,
+                    game,
+                    source_url,
+                };
+                (metadata, config)
+            }
+            Ok(Download::Config(config)) => (Metadata::default(), config),
+            // Name what's wrong with a preset's config rather than just rejecting it
+            Err(_) => match value.get("config").map(|config| serde_json::from_value::<CrosshairConfig>(config.clone())) {
+                Some(Err(e)) => return Err(format!("The preset's config is invalid: {}", e)),
+                _ => return Err("The download is not a crosshair preset or config".to_string()),
+            },
+        }
+    } else {
+        (Metadata::default(), share_code::decode(text)?)
+    };
+    preset_check::check_config(&config)?;
+    
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Ok(CrosshairPreset {
+        id: now.as_millis().to_string(),
+        name: preset_name(metadata.name.as_deref(), url),
+        config,
+        created_at: iso_timestamp(now.as_secs()),
+        author: metadata.author.as_deref().and_then(|author| clean_text(author, MAX_NAME_LENGTH)),
+        description: metadata
+            .description
+            .as_deref()
+            .and_then(|description| clean_text(description, MAX_DESCRIPTION_LENGTH)),
+        game: metadata.game.as_deref().and_then(|game| clean_text(game, MAX_NAME_LENGTH)),
+        // A preset that names its origin keeps it, otherwise it came from here
+        source_url: metadata
+            .source_url
+            .as_deref()
+            .and_then(|source| clean_text(source, MAX_DESCRIPTION_LENGTH))
+            .or_else(|| Some(url.to_string())),
+    })
+}
+
+// Trimmed and cut to `max_length` characters, without control characters
+// (line breaks are fine). None when nothing is left.
+fn clean_text(text: &str, max_length: usize) -> Option<String> {
+    let text: String = text
+        .trim()
+        .chars()
+        .filter(|&c| !c.is_control() || c == '\n')
+        .take(max_length)
+        .collect();
+    (!text.is_empty()).then_some(text)
+}
+
+// The preset's own name, falling back to the file name in the URL
+fn preset_name(name: Option<&str>, url: &str) -> String {
+    let from_url = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.trim_end_matches('/').rsplit('/').next())
+        .map(|file| file.rsplit_once('.').map_or(file, |(stem, _)| stem));
+    
+    let name = name
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .or(from_url)
+        .unwrap_or("Imported preset");
+    name.chars().filter(|c| !c.is_control()).take(MAX_NAME_LENGTH).collect()
+}
+
+// `YYYY-MM-DDTHH:MM:SS.000Z`, matching the JavaScript toISOString() the UI uses
+fn iso_timestamp(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let time = secs % 86400;
+    
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.000Z",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
\ No newline at end of file
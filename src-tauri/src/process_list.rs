@@ -0,0 +1,193 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use windows::Win32::{
+    Foundation::{BOOL, HWND, LPARAM, WPARAM},
+    Graphics::Gdi::{
+        DeleteObject, GetDC, GetDIBits, GetObjectW, ReleaseDC, BITMAP, BITMAPINFO,
+        BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, HBITMAP,
+    },
+    UI::WindowsAndMessaging::{
+        EnumWindows, GetClassLongPtrW, GetIconInfo, GetWindowLongW, GetWindowTextLengthW,
+        GetWindowTextW, IsWindowVisible, SendMessageTimeoutW, GCLP_HICON, GWL_EXSTYLE, HICON,
+        ICONINFO, ICON_BIG, ICON_SMALL2, SMTO_ABORTIFHUNG, WM_GETICON, WS_EX_TOOLWINDOW,
+    },
+};
+
+use crate::process_watch::{process_name_for_window, RunningProcess};
+
+// How long to wait for a window to answer WM_GETICON before giving up on it
+const ICON_TIMEOUT_MS: u32 = 100;
+
+/// Lists visible top-level windows with their executable name and icon, one
+/// entry per executable, sorted by executable name.
+pub fn list_running_processes() -> Vec<RunningProcess> {
+    let mut windows: Vec<HWND> = Vec::new();
+    
+    unsafe {
+        let _ = EnumWindows(
+            Some(collect_window),
+            LPARAM(&mut windows as *mut Vec<HWND> as isize),
+        );
+    }
+    
+    let mut processes: Vec<RunningProcess> = Vec::new();
+    
+    for hwnd in windows {
+        let Some(exe_name) = process_name_for_window(hwnd) else {
+            continue;
+        };
+        if processes.iter().any(|p| p.exe_name == exe_name) {
+            continue;
+        }
+        
+        processes.push(RunningProcess {
+            hwnd: hwnd.0 as isize,
+            title: window_title(hwnd),
+            exe_name,
+            icon: window_icon(hwnd).and_then(icon_to_data_url),
+        });
+    }
+    
+    processes.sort_by(|a, b| a.exe_name.cmp(&b.exe_name));
+    processes
+}
+
+unsafe extern "system" fn collect_window(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    let windows = &mut *(lparam.0 as *mut Vec<HWND>);
+    
+    // Skip hidden, untitled and tool windows (tray popups, overlays like ours)
+    let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+    if IsWindowVisible(hwnd).as_bool()
+        && GetWindowTextLengthW(hwnd) > 0
+        && ex_style & WS_EX_TOOLWINDOW.0 == 0
+    {
+        windows.push(hwnd);
+    }
+    
+    true.into()
+}
+
+fn window_title(hwnd: HWND) -> String {
+    unsafe {
+        let len = GetWindowTextLengthW(hwnd);
+        let mut buffer = vec![0u16; len as usize + 1];
+        let copied = GetWindowTextW(hwnd, &mut buffer);
+        String::from_utf16_lossy(&buffer[..copied as usize])
+    }
+}
+
+fn window_icon(hwnd: HWND) -> Option<HICON> {
+    unsafe {
+        for size in [ICON_BIG, ICON_SMALL2] {
+            let mut result = 0usize;
+            SendMessageTimeoutW(
+                hwnd,
+                WM_GETICON,
+                WPARAM(size as usize),
+                LPARAM(0),
+                SMTO_ABORTIFHUNG,
+                ICON_TIMEOUT_MS,
+                Some(&mut result as *mut usize),
+            );
+            if result != 0 {
+                return Some(HICON(result as *mut _));
+            }
+        }
+        
+        // Many games only set the icon on their window class
+        let class_icon = GetClassLongPtrW(hwnd, GCLP_HICON);
+        if class_icon != 0 {
+            return Some(HICON(class_icon as *mut _));
+        }
+        
+        None
+    }
+}
+
+// Reads the color bitmap of `icon` as top-down RGBA pixels
+fn icon_pixels(icon: HICON) -> Option<(u32, u32, Vec<u8>)> {
+    unsafe {
+        let mut info = ICONINFO::default();
+        GetIconInfo(icon, &mut info).ok()?;
+        
+        let result = bitmap_pixels(info.hbmColor);
+        
+        let _ = DeleteObject(info.hbmColor);
+        let _ = DeleteObject(info.hbmMask);
+        result
+    }
+}
+
+fn bitmap_pixels(bitmap: HBITMAP) -> Option<(u32, u32, Vec<u8>)> {
+    unsafe {
+        if bitmap.0.is_null() {
+            return None;
+        }
+        
+        let mut bm = BITMAP::default();
+        let read = GetObjectW(
+            bitmap,
+            std::mem::size_of::<BITMAP>() as i32,
+            Some(&mut bm as *mut BITMAP as *mut _),
+        );
+        if read == 0 || bm.bmWidth <= 0 || bm.bmHeight <= 0 {
+            return None;
+        }
+        
+        let (width, height) = (bm.bmWidth, bm.bmHeight);
+        let mut bmi = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                biHeight: -height, // Negative height = top-down rows
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        let hdc = GetDC(None);
+        let lines = GetDIBits(
+            hdc,
+            bitmap,
+            0,
+            height as u32,
+            Some(pixels.as_mut_ptr() as *mut _),
+            &mut bmi,
+            DIB_RGB_COLORS,
+        );
+        ReleaseDC(None, hdc);
+        
+        if lines == 0 {
+            return None;
+        }
+        
+        // BGRA -> RGBA; icons without an alpha channel are fully opaque
+        let has_alpha = pixels.chunks_exact(4).any(|px| px[3] != 0);
+        for px in pixels.chunks_exact_mut(4) {
+            px.swap(0, 2);
+            if !has_alpha {
+                px[3] = 255;
+            }
+        }
+        
+        Some((width as u32, height as u32, pixels))
+    }
+}
+
+fn icon_to_data_url(icon: HICON) -> Option<String> {
+    let (width, height, pixels) = icon_pixels(icon)?;
+    
+    let mut png_data = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png_data, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().ok()?;
+        writer.write_image_data(&pixels).ok()?;
+    }
+    
+    Some(format!("data:image/png;base64,{}", STANDARD.encode(png_data)))
+}
\ No newline at end of file
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::AppHandle;
+use tracing::error;
+
+use crate::profiles::{self, normalize_exe_name};
+use crate::storage::{self, PROCESS_WATCH_FILE};
+
+use crosshair_engine::backend;
+#[cfg(windows)]
+use windows::{
+    core::PWSTR,
+    Win32::{
+        Foundation::{CloseHandle, HWND},
+        System::Threading::{
+            OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+            PROCESS_QUERY_LIMITED_INFORMATION,
+        },
+        UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId},
+    },
+};
+
+// How often the foreground window is checked for a process switch
+const POLL_INTERVAL_MS: u64 = 1000;
+
+// Processes that are never treated as games when no explicit game list is set
+const NON_GAME_PROCESSES: &[&str] = &[
+    "explorer.exe",
+    "chrome.exe",
+    "msedge.exe",
+    "firefox.exe",
+    "opera.exe",
+    "brave.exe",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoHideSettings {
+    pub enabled: bool,
+    pub games: Vec<String>,  // Executable names the crosshair is shown for
+}
+
+/// A window listed by `process_list`, for picking a game to attach to.
+#[derive(Clone, Serialize)]
+pub struct RunningProcess {
+    pub hwnd: isize,           // Raw window handle, usable with attach_to_window
+    pub title: String,
+    pub exe_name: String,
+    pub icon: Option<String>,  // PNG data URL
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WatchData {
+    auto_hide: AutoHideSettings,
+    #[serde(default)]
+    blacklist: Vec<String>,
+}
+
+static AUTO_HIDE: Mutex<AutoHideSettings> = Mutex::new(AutoHideSettings {
+    enabled: false,
+    games: Vec::new(),
+});
+
+// Processes during which the crosshair is always hidden
+static BLACKLIST: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+static FOREGROUND_EXE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Loads saved watcher settings from disk into memory.
+pub fn load_settings() -> Result<(), String> {
+    let watch_path = storage::file_path(PROCESS_WATCH_FILE)?;
+    if let Some(data) = storage::read_json::<WatchData>(&watch_path)? {
+        *AUTO_HIDE.lock().unwrap() = data.auto_hide;
+        *BLACKLIST.lock().unwrap() = data.blacklist;
+    }
+    Ok(())
+}
+
+fn save_settings() -> Result<(), String> {
+    let data = WatchData {
+        auto_hide: get_auto_hide(),
+        blacklist: get_blacklist(),
+    };
+    let watch_path = storage::file_path(PROCESS_WATCH_FILE)?;
+    storage::write_json(&watch_path, &data)
+}
+
+pub fn get_auto_hide() -> AutoHideSettings {
+    AUTO_HIDE.lock().unwrap().clone()
+}
+
+fn normalize_exe_list(exe_names: &[String]) -> Vec<String> {
+    exe_names
+        .iter()
+        .map(|e| normalize_exe_name(e))
+        .filter(|e| !e.is_empty())
+        .collect()
+}
+
+pub fn set_auto_hide(mut settings: AutoHideSettings) -> Result<(), String> {
+    settings.games = normalize_exe_list(&settings.games);
+    
+    *AUTO_HIDE.lock().unwrap() = settings;
+    save_settings()?;
+    
+    // Re-evaluate right away instead of waiting for the next focus change
+    update_visibility();
+    Ok(())
+}
+
+pub fn get_blacklist() -> Vec<String> {
+    BLACKLIST.lock().unwrap().clone()
+}
+
+pub fn set_blacklist(exe_names: Vec<String>) -> Result<(), String> {
+    *BLACKLIST.lock().unwrap() = normalize_exe_list(&exe_names);
+    save_settings()?;
+    
+    update_visibility();
+    Ok(())
+}
+
+fn is_game(settings: &AutoHideSettings, exe_name: &str) -> bool {
+    if settings.games.iter().any(|g| g == exe_name) || profiles::has_profile(exe_name) {
+        return true;
+    }
+    
+    // Without an explicit list, anything that isn't a known desktop app counts
+    settings.games.is_empty() && !NON_GAME_PROCESSES.contains(&exe_name)
+}
+
+// None leaves the visibility as it is
+fn should_hide(exe_name: Option<&str>) -> Option<bool> {
+    // Blacklisted processes win over everything else
+    if let Some(exe_name) = exe_name {
+        if BLACKLIST.lock().unwrap().iter().any(|b| b == exe_name) {
+            return Some(true);
+        }
+    }
+    
+    let settings = AUTO_HIDE.lock().unwrap();
+    if !settings.enabled {
+        return Some(false);
+    }
+    
+    // An unreadable name is often an elevated game, so don't hide it on a guess
+    exe_name.map(|exe_name| !is_game(&settings, exe_name))
+}
+
+fn update_visibility() {
+    let exe_name = FOREGROUND_EXE.lock().unwrap().clone();
+    let Some(hide) = should_hide(exe_name.as_deref()) else {
+        return;
+    };
+    
+    if let Err(e) = backend().set_suppressed(hide) {
+        error!("Failed to update overlay visibility: {}", e);
+    }
+}
+
+/// Returns the lowercase executable name of the process owning the foreground window.
+#[cfg(windows)]
+pub fn foreground_process_name() -> Option<String> {
+    unsafe { process_name_for_window(GetForegroundWindow()) }
+}
+
+/// Returns the lowercase executable name of the process owning `hwnd`.
+#[cfg(windows)]
+pub fn process_name_for_window(hwnd: HWND) -> Option<String> {
+    unsafe {
+        if hwnd.0.is_null() {
+            return None;
+        }
+        
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid as *mut u32));
+        if pid == 0 {
+            return None;
+        }
+        
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        
+        let mut buffer = [0u16; 260];
+        let mut len = buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(
+            process,
+            PROCESS_NAME_WIN32,
+            PWSTR(buffer.as_mut_ptr()),
+            &mut len,
+        );
+        let _ = CloseHandle(process);
+        result.ok()?;
+        
+        let path = String::from_utf16_lossy(&buffer[..len as usize]);
+        let exe_name = path.rsplit(['\\', '/']).next()?;
+        Some(normalize_exe_name(exe_name))
+    }
+}
+
+/// Spawns the foreground watcher thread. On every foreground process change it
+/// applies the matching game profile and updates auto-hide/blacklist visibility.
+#[cfg(windows)]
+pub fn start_watcher(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let exe_name = foreground_process_name();
+        let changed = *FOREGROUND_EXE.lock().unwrap() != exe_name;
+        
+        if changed {
+            *FOREGROUND_EXE.lock().unwrap() = exe_name.clone();
+            
+            if let Some(exe) = exe_name.as_deref() {
+                profiles::on_foreground_changed(&app, exe);
+            }
+            update_visibility();
+        }
+        
+        std::thread::sleep(std::time::Duration::from_millis(POLL_INTERVAL_MS));
+    });
+}
\ No newline at end of file
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+use tracing::error;
+
+use crate::storage::{self, PROFILES_FILE};
+use crosshair_engine::{backend, CrosshairConfig};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameProfile {
+    pub exe_name: String,   // Lowercase executable name, e.g. "cs2.exe"
+    pub preset_id: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ProfilesData {
+    profiles: Vec<GameProfile>,
+}
+
+#[derive(Clone, Serialize)]
+struct ProfileAppliedPayload {
+    exe_name: String,
+    preset_id: String,
+    config: CrosshairConfig,
+}
+
+static PROFILES: Mutex<Vec<GameProfile>> = Mutex::new(Vec::new());
+
+pub fn normalize_exe_name(exe_name: &str) -> String {
+    exe_name.trim().to_lowercase()
+}
+
+/// Loads saved profiles from disk into memory.
+pub fn load_profiles() -> Result<(), String> {
+    let profiles_path = storage::file_path(PROFILES_FILE)?;
+    let profiles = storage::read_json::<ProfilesData>(&profiles_path)?
+        .map(|data| data.profiles)
+        .unwrap_or_default();
+    
+    *PROFILES.lock().unwrap() = profiles;
+    Ok(())
+}
+
+pub fn get_profiles() -> Vec<GameProfile> {
+    PROFILES.lock().unwrap().clone()
+}
+
+/// Maps `exe_name` to `preset_id`, or removes the mapping when `preset_id` is None.
+pub fn set_profile(exe_name: &str, preset_id: Option<String>) -> Result<(), String> {
+    let exe_name = normalize_exe_name(exe_name);
+    if exe_name.is_empty() {
+        return Err("Executable name cannot be empty".to_string());
+    }
+    
+    let mut profiles = PROFILES.lock().unwrap();
+    profiles.retain(|p| p.exe_name != exe_name);
+    
+    if let Some(preset_id) = preset_id {
+        profiles.push(GameProfile { exe_name, preset_id });
+    }
+    
+    let profiles_path = storage::file_path(PROFILES_FILE)?;
+    storage::write_json(&profiles_path, &ProfilesData { profiles: profiles.clone() })
+}
+
+fn profile_for(exe_name: &str) -> Option<GameProfile> {
+    let exe_name = normalize_exe_name(exe_name);
+    PROFILES
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|p| p.exe_name == exe_name)
+        .cloned()
+}
+
+fn apply_profile(app: &AppHandle, exe_name: &str, profile: &GameProfile) -> Result<(), String> {
+    if !crate::actions::apply_preset(app, &profile.preset_id)? {
+        return Err(format!("Preset not found: {}", profile.preset_id));
+    }
+    
+    app.emit("profile-applied", ProfileAppliedPayload {
+        exe_name: exe_name.to_string(),
+        preset_id: profile.preset_id.clone(),
+        config: backend().config(),
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Applies the preset mapped to `exe_name`, if any. Called by the process
+/// watcher whenever the foreground process changes.
+pub fn on_foreground_changed(app: &AppHandle, exe_name: &str) {
+    if let Some(profile) = profile_for(exe_name) {
+        if let Err(e) = apply_profile(app, exe_name, &profile) {
+            error!("Failed to apply game profile: {}", e);
+        }
+    }
+}
+
+pub fn has_profile(exe_name: &str) -> bool {
+    profile_for(exe_name).is_some()
+}
\ No newline at end of file
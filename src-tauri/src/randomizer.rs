@@ -0,0 +1,132 @@
+//! Random crosshairs within user-set bounds, for variety streams and for
+//! stumbling onto new setups.
+
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::actions;
+use crosshair_engine::color::{self, alpha, from_rgba};
+use crosshair_engine::{CrosshairArms, CrosshairConfig, CrosshairStyle};
+
+// Styles that look right from the shared size, gap and thickness alone
+const DEFAULT_STYLES: &[CrosshairStyle] = &[
+    CrosshairStyle::Classic,
+    CrosshairStyle::Dot,
+    CrosshairStyle::Circle,
+    CrosshairStyle::Square,
+    CrosshairStyle::TShape,
+    CrosshairStyle::X,
+    CrosshairStyle::Diamond,
+    CrosshairStyle::Brackets,
+    CrosshairStyle::DoubleCircle,
+    CrosshairStyle::Chevron,
+    CrosshairStyle::Triangle,
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RandomizeConstraints {
+    pub styles: Vec<CrosshairStyle>,  // Picked from; empty allows every style that needs no extra setup
+    pub colors: Vec<String>,          // "#RRGGBB" palette; empty picks any fully saturated hue
+    pub min_size: i32,
+    pub max_size: i32,
+    pub min_gap: i32,
+    pub max_gap: i32,
+    pub min_thickness: i32,
+    pub max_thickness: i32,
+    pub dot_chance: f32,              // 0.0-1.0, how often the center dot is shown
+}
+
+impl Default for RandomizeConstraints {
+    fn default() -> Self {
+        Self {
+            styles: Vec::new(),
+            colors: Vec::new(),
+            min_size: 4,
+            max_size: 16,
+            min_gap: 0,
+            max_gap: 8,
+            min_thickness: 1,
+            max_thickness: 4,
+            dot_chance: 0.5,
+        }
+    }
+}
+
+/// A random variation of `base` within `constraints`. Position, opacity and
+/// everything else the constraints don't cover stay as they are.
+pub fn randomize(base: &CrosshairConfig, constraints: &RandomizeConstraints) -> Result<CrosshairConfig, String> {
+    let palette = constraints
+        .colors
+        .iter()
+        .map(|hex| color::parse_hex(hex).ok_or(format!("Invalid color \"{}\"", hex)))
+        .collect::<Result<Vec<u32>, String>>()?;
+    let mut rng = Rng::seeded();
+    
+    let styles = if constraints.styles.is_empty() { DEFAULT_STYLES } else { &constraints.styles };
+    let rgb = match rng.pick(&palette) {
+        Some(&color) => color::rgb(color),
+        None => color::hue_to_rgb(rng.range(0, 359) as f32),
+    };
+    let thickness = rng.range(constraints.min_thickness.max(1), constraints.max_thickness.max(1));
+    
+    let mut config = base.clone();
+    config.style = rng.pick(styles).cloned().unwrap_or(CrosshairStyle::Classic);
+    config.color = from_rgba(rgb, alpha(base.color));
+    config.size = rng.range(constraints.min_size.max(0), constraints.max_size.max(0)) as f32;
+    config.gap = rng.range(constraints.min_gap.max(0), constraints.max_gap.max(0)) as f32;
+    config.thickness = thickness as f32;
+    config.show_dot = config.style == CrosshairStyle::Dot || rng.chance(constraints.dot_chance);
+    config.dot_size = rng.range(1, thickness + 1) as f32;
+    // Overrides would hide part of the new look
+    config.arms = CrosshairArms::default();
+    config.dot_color = None;
+    Ok(config)
+}
+
+/// Randomizes the live config, then applies and saves the result.
+pub fn randomize_live(app: &AppHandle, constraints: &RandomizeConstraints) -> Result<CrosshairConfig, String> {
+    let config = randomize(&actions::live_config()?, constraints)?;
+    actions::apply_config(app, config.clone())?;
+    Ok(config)
+}
+
+// xorshift64*: plenty for picking looks, and no extra dependency
+struct Rng(u64);
+
+impl Rng {
+    fn seeded() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        // The state must never be zero
+        Self(nanos | 1)
+    }
+    
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+    
+    // Inclusive on both ends, in either order
+    fn range(&mut self, a: i32, b: i32) -> i32 {
+        let (low, high) = (a.min(b), a.max(b));
+        let span = (high as i64 - low as i64 + 1) as u64;
+        (low as i64 + (self.next() % span) as i64) as i32
+    }
+    
+    fn chance(&mut self, probability: f32) -> bool {
+        // 24 random bits are exact in an f32
+        ((self.next() >> 40) as f32 / (1u64 << 24) as f32) < probability
+    }
+    
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get((self.next() % items.len() as u64) as usize)
+    }
+}
\ No newline at end of file
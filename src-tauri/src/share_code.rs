@@ -0,0 +1,86 @@
+//! Share codes: a crosshair config packed into a short, copy-pasteable string
+//! (`CO1-` followed by URL-safe base64 of the config's JSON), plus the
+//! `crosshair-overlay://import?code=...` links that carry them.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+use crate::preset_check;
+use crosshair_engine::CrosshairConfig;
+
+pub const URI_SCHEME: &str = "crosshair-overlay";
+
+const CODE_PREFIX: &str = "CO1-";
+
+// Far more than any real config needs; keeps pasted garbage from being decoded
+const MAX_CODE_LENGTH: usize = 64 * 1024;
+
+/// Encodes `config` as a share code.
+pub fn encode(config: &CrosshairConfig) -> Result<String, String> {
+    let json = serde_json::to_vec(config).map_err(|e| e.to_string())?;
+    Ok(format!("{}{}", CODE_PREFIX, URL_SAFE_NO_PAD.encode(json)))
+}
+
+/// Decodes a share code back into a config, which must pass the
+/// preset_check limits.
+pub fn decode(code: &str) -> Result<CrosshairConfig, String> {
+    let code = code.trim();
+    if code.len() > MAX_CODE_LENGTH {
+        return Err("Share code is too long".to_string());
+    }
+    let data = code
+        .strip_prefix(CODE_PREFIX)
+        .ok_or("Not a crosshair share code")?;
+    
+    let json = URL_SAFE_NO_PAD
+        .decode(data.trim_end_matches('='))
+        .map_err(|_| "Share code is damaged")?;
+    let config = serde_json::from_slice(&json).map_err(|e| format!("Share code is invalid: {}", e))?;
+    preset_check::check_config(&config)?;
+    Ok(config)
+}
+
+/// Builds the `crosshair-overlay://import?code=...` link for `config`.
+pub fn encode_link(config: &CrosshairConfig) -> Result<String, String> {
+    // URL-safe base64 and the prefix need no percent-encoding
+    Ok(format!("{}://import?code={}", URI_SCHEME, encode(config)?))
+}
+
+/// Extracts the share code from an import link, or `None` if `url` isn't one.
+pub fn code_from_link(url: &str) -> Option<String> {
+    let rest = url.strip_prefix(URI_SCHEME)?.strip_prefix("://")?;
+    let (action, query) = rest.split_once('?')?;
+    if action.trim_end_matches('/') != "import" {
+        return None;
+    }
+    
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("code="))
+        .map(percent_decode)
+}
+
+// Browsers and chat apps may escape characters in links they hand over
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match (bytes[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
\ No newline at end of file
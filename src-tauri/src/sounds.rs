@@ -0,0 +1,55 @@
+//! Sound feedback for the toggle hotkey, from the bundled WAVs or files the
+//! user picked.
+
+use windows::{
+    core::{HSTRING, PCWSTR},
+    Win32::{
+        Foundation::HMODULE,
+        Media::Audio::{PlaySoundW, SND_ASYNC, SND_FILENAME, SND_MEMORY, SND_NODEFAULT},
+    },
+};
+use tracing::warn;
+
+use crate::app_settings;
+
+const TOGGLE_ON_WAV: &[u8] = include_bytes!("../sounds/toggle-on.wav");
+const TOGGLE_OFF_WAV: &[u8] = include_bytes!("../sounds/toggle-off.wav");
+
+/// Plays the on or off sound for a toggle, if sounds are enabled.
+pub fn play_toggle_sound(enabled: bool) {
+    let settings = app_settings::get_settings().sounds;
+    if !settings.enabled {
+        return;
+    }
+    
+    let (custom, bundled) = if enabled {
+        (settings.on_sound, TOGGLE_ON_WAV)
+    } else {
+        (settings.off_sound, TOGGLE_OFF_WAV)
+    };
+    
+    // A custom file that's gone or unreadable falls back to the bundled sound
+    if let Some(path) = custom.filter(|path| !path.trim().is_empty()) {
+        if play_file(&path) {
+            return;
+        }
+        warn!("Failed to play sound {}", path);
+    }
+    play_bundled(bundled);
+}
+
+fn play_file(path: &str) -> bool {
+    let path = HSTRING::from(path);
+    unsafe { PlaySoundW(&path, HMODULE::default(), SND_FILENAME | SND_ASYNC | SND_NODEFAULT).as_bool() }
+}
+
+// Asynchronous playback from memory is fine here since the WAVs are static
+fn play_bundled(wav: &'static [u8]) {
+    unsafe {
+        let _ = PlaySoundW(
+            PCWSTR(wav.as_ptr() as *const u16),
+            HMODULE::default(),
+            SND_MEMORY | SND_ASYNC | SND_NODEFAULT,
+        );
+    }
+}
\ No newline at end of file
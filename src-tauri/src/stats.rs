@@ -0,0 +1,167 @@
+//! Local usage statistics: how long the crosshair was shown, how often it was
+//! toggled and how long each preset was in use. Kept in stats.json and never
+//! sent anywhere.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Listener};
+use tracing::error;
+
+use crate::actions;
+use crate::storage::{self, STATS_FILE};
+
+// How often time is credited and the stats are saved
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+// Events after which the crosshair may be showing something else
+const CHANGE_EVENTS: &[&str] = &["crosshair-config-changed", "preset-applied", "profile-applied"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageStats {
+    pub since: u64,            // Unix seconds when tracking started or was last reset
+    pub enabled_seconds: u64,  // Time the crosshair was shown
+    pub toggle_count: u64,
+    pub presets: Vec<PresetUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetUsage {
+    pub preset_id: String,
+    pub name: String,  // Name when last used
+    pub seconds: u64,  // Time the crosshair was shown with this preset
+}
+
+struct Tracker {
+    stats: UsageStats,
+    last_update: Instant,
+    enabled: bool,
+    preset: Option<(String, String)>,  // Id and name of the preset matching the live config
+}
+
+// None until loaded
+static TRACKER: Mutex<Option<Tracker>> = Mutex::new(None);
+
+/// Loads stats.json and starts counting from the live config.
+pub fn load_stats() -> Result<(), String> {
+    let stats_path = storage::file_path(STATS_FILE)?;
+    let stats = storage::read_json::<UsageStats>(&stats_path)?.unwrap_or_else(fresh_stats);
+    
+    let mut tracker = Tracker {
+        stats,
+        last_update: Instant::now(),
+        enabled: false,
+        preset: None,
+    };
+    observe(&mut tracker);
+    *TRACKER.lock().unwrap() = Some(tracker);
+    Ok(())
+}
+
+/// Follows toggles and config changes, and saves the stats periodically. Call
+/// once at startup after [`load_stats`].
+pub fn start_tracking(app: &AppHandle) {
+    app.listen_any("crosshair-toggled", |_| {
+        update(|tracker| tracker.stats.toggle_count += 1);
+    });
+    for &event in CHANGE_EVENTS {
+        app.listen_any(event, |_| update(|_| {}));
+    }
+    
+    std::thread::spawn(|| loop {
+        std::thread::sleep(SAVE_INTERVAL);
+        update(|_| {});
+        if let Err(e) = save_stats() {
+            error!("Failed to save stats: {}", e);
+        }
+    });
+}
+
+/// The stats up to now.
+pub fn get_stats() -> UsageStats {
+    let mut guard = TRACKER.lock().unwrap();
+    match guard.as_mut() {
+        Some(tracker) => {
+            credit(tracker);
+            tracker.stats.clone()
+        }
+        None => fresh_stats(),
+    }
+}
+
+/// Clears all stats and starts counting again from now.
+pub fn reset_stats() -> Result<UsageStats, String> {
+    let mut guard = TRACKER.lock().unwrap();
+    let tracker = guard.as_mut().ok_or("Stats are not loaded")?;
+    credit(tracker);
+    tracker.stats = fresh_stats();
+    let stats = tracker.stats.clone();
+    drop(guard);
+    
+    save_stats()?;
+    Ok(stats)
+}
+
+fn save_stats() -> Result<(), String> {
+    let Some(stats) = TRACKER.lock().unwrap().as_ref().map(|tracker| tracker.stats.clone()) else {
+        return Ok(());
+    };
+    let stats_path = storage::file_path(STATS_FILE)?;
+    storage::write_json(&stats_path, &stats)
+}
+
+// Credits the time so far to the previous state, applies `change`, then
+// picks up the state after the event
+fn update(change: impl FnOnce(&mut Tracker)) {
+    let mut guard = TRACKER.lock().unwrap();
+    if let Some(tracker) = guard.as_mut() {
+        credit(tracker);
+        change(tracker);
+        observe(tracker);
+    }
+}
+
+// Adds the whole seconds since the last update to whatever was showing;
+// the remainder carries over to the next update
+fn credit(tracker: &mut Tracker) {
+    let seconds = tracker.last_update.elapsed().as_secs();
+    tracker.last_update += Duration::from_secs(seconds);
+    if !tracker.enabled || seconds == 0 {
+        return;
+    }
+    
+    tracker.stats.enabled_seconds += seconds;
+    if let Some((preset_id, name)) = &tracker.preset {
+        let presets = &mut tracker.stats.presets;
+        match presets.iter_mut().find(|usage| usage.preset_id == *preset_id) {
+            Some(usage) => {
+                usage.name = name.clone();
+                usage.seconds += seconds;
+            }
+            None => presets.push(PresetUsage {
+                preset_id: preset_id.clone(),
+                name: name.clone(),
+                seconds,
+            }),
+        }
+    }
+}
+
+fn observe(tracker: &mut Tracker) {
+    let Ok(config) = actions::live_config() else {
+        return;
+    };
+    tracker.enabled = config.enabled;
+    tracker.preset = crate::matching_preset(&config).map(|preset| (preset.id, preset.name));
+}
+
+fn fresh_stats() -> UsageStats {
+    UsageStats {
+        since: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        ..Default::default()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::backup::backup_file;
+
+pub const CONFIG_FILE: &str = "config.json";
+pub const PRESETS_FILE: &str = "presets.json";
+pub const PROFILES_FILE: &str = "profiles.json";
+pub const PROCESS_WATCH_FILE: &str = "process_watch.json";
+pub const HTTP_API_FILE: &str = "http_api.json";
+pub const SYNC_FILE: &str = "sync.json";
+pub const SETTINGS_FILE: &str = "settings.json";
+pub const STATS_FILE: &str = "stats.json";
+
+// Folder inside the config directory holding images imported for image crosshairs
+pub const IMAGES_DIR: &str = "images";
+
+/// Returns the app's config directory, creating it if it doesn't exist yet.
+pub fn config_dir() -> Result<PathBuf, String> {
+    let config_dir = dirs::config_dir()
+        .ok_or("Could not find config directory")?
+        .join("crosshair-overlay");
+    
+    std::fs::create_dir_all(&config_dir)
+        .map_err(|e| e.to_string())?;
+    
+    Ok(config_dir)
+}
+
+/// Returns the path of `name` inside the config directory.
+pub fn file_path(name: &str) -> Result<PathBuf, String> {
+    Ok(config_dir()?.join(name))
+}
+
+/// Writes `contents` to a temp file next to `path` and renames it into place,
+/// so a crash mid-write leaves either the old or the new file, never a partial one.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid file name")?;
+    let tmp_path = path.with_file_name(format!("{}.tmp", file_name));
+    
+    {
+        let mut file = std::fs::File::create(&tmp_path)
+            .map_err(|e| e.to_string())?;
+        file.write_all(contents)
+            .map_err(|e| e.to_string())?;
+        file.sync_all()
+            .map_err(|e| e.to_string())?;
+    }
+    
+    std::fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        e.to_string()
+    })
+}
+
+/// Reads and parses a JSON file, returning `None` if it doesn't exist.
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())?;
+    
+    serde_json::from_str(&contents)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// Backs up the current file and atomically replaces it with `value` as pretty JSON.
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(value)
+        .map_err(|e| e.to_string())?;
+    
+    backup_file(path)?;
+    write_atomic(path, contents.as_bytes())
+}
\ No newline at end of file
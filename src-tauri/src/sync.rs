@@ -0,0 +1,218 @@
+//! Opt-in folder sync: mirrors config.json and presets.json to a folder the
+//! user picks, typically inside OneDrive or Dropbox, so several PCs share one
+//! crosshair setup. When both copies differ the newer file wins; a local file
+//! that loses is backed up first.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tracing::error;
+
+use crate::actions;
+use crate::backup::backup_file;
+use crate::preset_check;
+use crate::storage::{self, CONFIG_FILE, PRESETS_FILE, SYNC_FILE};
+use crate::FavoritesData;
+use crosshair_engine::CrosshairConfig;
+
+// How often the background thread syncs while sync is enabled
+const SYNC_INTERVAL: Duration = Duration::from_secs(60);
+
+const SYNCED_FILES: &[&str] = &[CONFIG_FILE, PRESETS_FILE];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSettings {
+    pub enabled: bool,
+    pub folder: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum SyncAction {
+    Unchanged,
+    Pushed,  // Local copy was newer and replaced the one in the sync folder
+    Pulled,  // Sync folder copy was newer and replaced the local one
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileSyncResult {
+    pub file: String,
+    pub action: SyncAction,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatus {
+    pub enabled: bool,
+    pub folder: String,
+    pub last_sync: Option<u64>,  // Unix seconds of the last successful sync
+    pub last_error: Option<String>,
+    pub files: Vec<FileSyncResult>,  // What the last successful sync did
+}
+
+static SETTINGS: Mutex<SyncSettings> = Mutex::new(SyncSettings {
+    enabled: false,
+    folder: String::new(),
+});
+
+static STATUS: Mutex<SyncStatus> = Mutex::new(SyncStatus {
+    enabled: false,
+    folder: String::new(),
+    last_sync: None,
+    last_error: None,
+    files: Vec::new(),
+});
+
+/// Loads saved sync settings from disk into memory.
+pub fn load_settings() -> Result<(), String> {
+    let sync_path = storage::file_path(SYNC_FILE)?;
+    if let Some(settings) = storage::read_json::<SyncSettings>(&sync_path)? {
+        *SETTINGS.lock().unwrap() = settings;
+    }
+    Ok(())
+}
+
+pub fn get_settings() -> SyncSettings {
+    SETTINGS.lock().unwrap().clone()
+}
+
+pub fn set_settings(app: &AppHandle, mut settings: SyncSettings) -> Result<SyncStatus, String> {
+    settings.folder = settings.folder.trim().to_string();
+    if settings.enabled && !Path::new(&settings.folder).is_dir() {
+        return Err(format!("Sync folder does not exist: {}", settings.folder));
+    }
+    
+    *SETTINGS.lock().unwrap() = settings.clone();
+    let sync_path = storage::file_path(SYNC_FILE)?;
+    storage::write_json(&sync_path, &settings)?;
+    
+    if settings.enabled {
+        sync_now(app)
+    } else {
+        Ok(get_status())
+    }
+}
+
+pub fn get_status() -> SyncStatus {
+    let settings = get_settings();
+    let mut status = STATUS.lock().unwrap().clone();
+    status.enabled = settings.enabled;
+    status.folder = settings.folder;
+    status
+}
+
+/// Syncs every file right away and returns the updated status. Failures are
+/// recorded in the status as well as returned.
+pub fn sync_now(app: &AppHandle) -> Result<SyncStatus, String> {
+    let settings = get_settings();
+    if !settings.enabled {
+        return Err("Sync is not enabled".to_string());
+    }
+    
+    let result = SYNCED_FILES
+        .iter()
+        .map(|file| {
+            let action = sync_file(app, file, Path::new(&settings.folder))?;
+            Ok(FileSyncResult { file: file.to_string(), action })
+        })
+        .collect::<Result<Vec<_>, String>>();
+    
+    let mut status = STATUS.lock().unwrap();
+    match &result {
+        Ok(files) => {
+            status.last_sync = Some(unix_now());
+            status.last_error = None;
+            status.files = files.clone();
+        }
+        Err(e) => status.last_error = Some(e.clone()),
+    }
+    drop(status);
+    
+    result.map(|_| get_status())
+}
+
+/// Spawns the thread that syncs periodically while sync is enabled.
+pub fn start_sync_thread(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        if get_settings().enabled {
+            if let Err(e) = sync_now(&app) {
+                error!("Sync failed: {}", e);
+            }
+        }
+        std::thread::sleep(SYNC_INTERVAL);
+    });
+}
+
+fn sync_file(app: &AppHandle, file: &str, folder: &Path) -> Result<SyncAction, String> {
+    let local = storage::file_path(file)?;
+    let remote = folder.join(file);
+    
+    let local_contents = read_optional(&local)?;
+    let remote_contents = read_optional(&remote)?;
+    
+    match (local_contents, remote_contents) {
+        (None, None) => Ok(SyncAction::Unchanged),
+        (Some(a), Some(b)) if a == b => Ok(SyncAction::Unchanged),
+        (Some(contents), None) => push(&remote, &contents),
+        (None, Some(contents)) => pull(app, file, &local, &contents),
+        (Some(local_contents), Some(remote_contents)) => {
+            if modified(&remote)? > modified(&local)? {
+                pull(app, file, &local, &remote_contents)
+            } else {
+                push(&remote, &local_contents)
+            }
+        }
+    }
+}
+
+fn push(remote: &Path, contents: &str) -> Result<SyncAction, String> {
+    storage::write_atomic(remote, contents.as_bytes())?;
+    Ok(SyncAction::Pushed)
+}
+
+fn pull(app: &AppHandle, file: &str, local: &Path, contents: &str) -> Result<SyncAction, String> {
+    // Never let a broken file from another PC replace a working one
+    let invalid = |e: serde_json::Error| format!("{} in the sync folder is invalid: {}", file, e);
+    if file == CONFIG_FILE {
+        let config: CrosshairConfig = serde_json::from_str(contents).map_err(invalid)?;
+        // Like any config from outside, it may point at another PC's images
+        preset_check::check_config(&config)
+            .map_err(|e| format!("{} in the sync folder is invalid: {}", file, e))?;
+        // Saves the file too; the overlay only exists on Windows
+        if actions::apply_config(app, config).is_ok() {
+            return Ok(SyncAction::Pulled);
+        }
+    } else {
+        let favorites: FavoritesData = serde_json::from_str(contents).map_err(invalid)?;
+        for preset in &favorites.presets {
+            preset_check::check_config(&preset.config)
+                .map_err(|e| format!("Preset \"{}\" in the sync folder is invalid: {}", preset.name, e))?;
+        }
+    }
+    
+    backup_file(local)?;
+    storage::write_atomic(local, contents.as_bytes())?;
+    Ok(SyncAction::Pulled)
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    std::fs::read_to_string(path)
+        .map(Some)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+fn modified(path: &Path) -> Result<SystemTime, String> {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map_err(|e| e.to_string())
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
\ No newline at end of file
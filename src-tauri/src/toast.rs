@@ -0,0 +1,74 @@
+//! Native notifications and the overlay OSD confirming hotkey toggles and
+//! preset switches, so there is feedback even while a fullscreen game hides
+//! the tray.
+
+use serde::Deserialize;
+use tauri::{AppHandle, Listener};
+use tauri_plugin_notification::NotificationExt;
+use tracing::error;
+
+use crate::app_settings;
+use crosshair_engine::CrosshairConfig;
+
+// Shared shape of the preset-applied and profile-applied payloads
+#[derive(Deserialize)]
+struct PresetSwitch {
+    preset_id: String,
+    config: CrosshairConfig,
+}
+
+/// Shows "Crosshair ON — Preset: X" after the toggle hotkey, if enabled.
+pub fn notify_toggled(app: &AppHandle, config: &CrosshairConfig) {
+    if app_settings::get_settings().notifications.on_toggle {
+        show(app, &message(config.enabled, crate::matching_preset_name(config)));
+    }
+}
+
+/// Notifies about every preset or profile switch and flashes the preset name
+/// on the overlay, as enabled. Call once at startup.
+pub fn notify_preset_switches(app: &AppHandle) {
+    for event in ["preset-applied", "profile-applied"] {
+        let app_handle = app.clone();
+        app.listen_any(event, move |event| {
+            let settings = app_settings::get_settings().notifications;
+            let Ok(switch) = serde_json::from_str::<PresetSwitch>(event.payload()) else {
+                return;
+            };
+            let name = preset_name(&switch.preset_id);
+            
+            if settings.osd {
+                if let Some(name) = &name {
+                    if let Err(e) = crosshair_engine::backend().flash_osd(name) {
+                        error!("Failed to show preset name: {}", e);
+                    }
+                }
+            }
+            if settings.on_preset {
+                show(&app_handle, &message(switch.config.enabled, name));
+            }
+        });
+    }
+}
+
+fn message(enabled: bool, preset: Option<String>) -> String {
+    let state = if enabled { "Crosshair ON" } else { "Crosshair OFF" };
+    match preset {
+        Some(name) => format!("{} — Preset: {}", state, name),
+        None => state.to_string(),
+    }
+}
+
+fn preset_name(preset_id: &str) -> Option<String> {
+    crate::load_favorites()
+        .ok()?
+        .presets
+        .into_iter()
+        .find(|preset| preset.id == preset_id)
+        .map(|preset| preset.name)
+}
+
+fn show(app: &AppHandle, body: &str) {
+    if let Err(e) = app.notification().builder().title("Crosshair Overlay").body(body).show() {
+        error!("Failed to show notification: {}", e);
+    }
+}
\ No newline at end of file
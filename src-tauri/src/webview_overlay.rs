@@ -0,0 +1,98 @@
+//! Fallback overlay for platforms without a native backend: a transparent,
+//! click-through Tauri window whose webview draws the crosshair on a canvas.
+
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+use crosshair_engine::{CrosshairConfig, OverlayBackend};
+
+/// Label of the overlay window. The frontend renders the overlay view instead
+/// of the settings UI when loaded with `?overlay`.
+pub const OVERLAY_LABEL: &str = "overlay";
+
+pub struct WebviewBackend {
+    app: AppHandle,
+    config: Mutex<CrosshairConfig>,
+}
+
+impl WebviewBackend {
+    pub fn new(app: AppHandle) -> Self {
+        Self {
+            app,
+            config: Mutex::new(CrosshairConfig::default()),
+        }
+    }
+    
+    // Sends the config to the overlay window and shows or hides it to match
+    fn push(&self, config: &CrosshairConfig) -> Result<(), String> {
+        let Some(window) = self.window() else {
+            return Ok(());
+        };
+        self.app
+            .emit_to(OVERLAY_LABEL, "overlay-config", config)
+            .map_err(|e| e.to_string())?;
+        if config.enabled {
+            window.show().map_err(|e| e.to_string())
+        } else {
+            window.hide().map_err(|e| e.to_string())
+        }
+    }
+    
+    fn window(&self) -> Option<WebviewWindow> {
+        self.app.get_webview_window(OVERLAY_LABEL)
+    }
+}
+
+impl OverlayBackend for WebviewBackend {
+    fn name(&self) -> &'static str {
+        "webview"
+    }
+    
+    fn create(&self) -> Result<(), String> {
+        if self.window().is_some() {
+            return Ok(());
+        }
+        
+        let enabled = self.config.lock().unwrap().enabled;
+        let window = WebviewWindowBuilder::new(&self.app, OVERLAY_LABEL, WebviewUrl::App("index.html?overlay".into()))
+            .title("Crosshair")
+            .transparent(true)
+            .decorations(false)
+            .shadow(false)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .resizable(false)
+            .focused(false)
+            .maximized(true)
+            .visible(enabled)
+            .build()
+            .map_err(|e| e.to_string())?;
+        // Clicks have to reach the game underneath
+        window.set_ignore_cursor_events(true).map_err(|e| e.to_string())
+    }
+    
+    fn update_config(&self, config: CrosshairConfig) -> Result<(), String> {
+        *self.config.lock().unwrap() = config.clone();
+        self.push(&config)
+    }
+    
+    fn toggle(&self, enabled: bool) -> Result<(), String> {
+        let config = {
+            let mut config = self.config.lock().unwrap();
+            config.enabled = enabled;
+            config.clone()
+        };
+        self.push(&config)
+    }
+    
+    fn destroy(&self) -> Result<(), String> {
+        match self.window() {
+            Some(window) => window.destroy().map_err(|e| e.to_string()),
+            None => Ok(()),
+        }
+    }
+    
+    fn config(&self) -> CrosshairConfig {
+        self.config.lock().unwrap().clone()
+    }
+}
\ No newline at end of file
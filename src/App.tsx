@@ -13,7 +13,7 @@ import {
   ArrowLeft,
   Grid
 } from "lucide-react";
-import { CrosshairDesigner } from "./components/CrosshairDesigner";
+import { Color, CrosshairDesigner, CrosshairElement } from "./components/CrosshairDesigner";
 import { FavoritesPanel } from "./components/FavoritesPanel";
 import { CrosshairPreview } from "./components/CrosshairPreview";
 import "./App.css";
@@ -23,8 +23,8 @@ interface CrosshairConfig {
   size: number;
   thickness: number;
   gap: number;
-  color: number;
-  outline_color: number;
+  color: Color;
+  outline_color: Color;
   outline_thickness: number;
   show_dot: boolean;
   dot_size: number;
@@ -36,16 +36,9 @@ interface CrosshairConfig {
   rotation: number;
   t_length: number;
   shadow_enabled: boolean;
-  shadow_color: number;
+  shadow_color: Color;
   shadow_offset: number;
-  lines: Array<{
-    start_x: number;
-    start_y: number;
-    end_x: number;
-    end_y: number;
-    thickness: number;
-    color: number;
-  }>;
+  elements: CrosshairElement[];
 }
 
 const DEFAULT_CONFIG: CrosshairConfig = {
@@ -68,7 +61,7 @@ const DEFAULT_CONFIG: CrosshairConfig = {
   shadow_enabled: false,
   shadow_color: 0x000000,
   shadow_offset: 2,
-  lines: [],
+  elements: [],
 };
 
 
@@ -206,8 +199,16 @@ function App() {
   const updateConfig = async (updates: Partial<CrosshairConfig>) => {
     const newConfig = { ...config, ...updates };
     setConfig(newConfig);
+
+    // Only the fields that actually changed go to the backend
+    const patch = Object.fromEntries(
+      Object.entries(updates).filter(
+        ([key, value]) => JSON.stringify(value) !== JSON.stringify(config[key as keyof CrosshairConfig])
+      )
+    );
+    if (Object.keys(patch).length === 0) return;
     try {
-      await invoke("update_crosshair_config", { config: newConfig });
+      await invoke("patch_crosshair_config", { patch });
     } catch (error) {
       console.error("Failed to update config:", error);
     }
@@ -232,8 +233,12 @@ function App() {
   };
 
   const resetConfig = async () => {
-    setConfig(DEFAULT_CONFIG);
-    await invoke("update_crosshair_config", { config: DEFAULT_CONFIG });
+    try {
+      const defaults = await invoke<CrosshairConfig>("reset_to_defaults");
+      setConfig(defaults);
+    } catch (error) {
+      console.error("Failed to reset config:", error);
+    }
   };
 
   const selectStarter = async (template: typeof STARTER_TEMPLATES[0]) => {
@@ -1,4 +1,5 @@
 import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { 
   Palette, 
   Move, 
@@ -13,22 +14,46 @@ import {
 
 export type CrosshairStyle = 'Classic' | 'Dot' | 'Circle' | 'Square' | 'TShape';
 
+// The backend writes colors as "#RRGGBB" / "#RRGGBBAA" but still accepts numbers
+export type Color = number | string;
+
+export const colorToHex = (color: Color): string => {
+  if (typeof color === "string") {
+    return color.substring(0, 7).toLowerCase();
+  }
+  return "#" + (color & 0xffffff).toString(16).padStart(6, "0");
+};
+
 export interface CrosshairLine {
   start_x: number;
   start_y: number;
   end_x: number;
   end_y: number;
   thickness: number;
-  color: number;
+  color: Color;
 }
 
+export interface ElementPoint {
+  x: number;
+  y: number;
+}
+
+// Primitives of the Custom style, offsets from the crosshair center. A
+// thickness of 0 leaves out the stroke.
+export type CrosshairElement =
+  | ({ type: 'Line' } & CrosshairLine)
+  | { type: 'Circle'; x: number; y: number; radius: number; thickness: number; color: Color; fill?: Color | null }
+  | { type: 'Rect'; x: number; y: number; width: number; height: number; thickness: number; color: Color; fill?: Color | null }
+  | { type: 'Polygon'; points: ElementPoint[]; thickness: number; color: Color; fill?: Color | null }
+  | { type: 'Arc'; x: number; y: number; radius: number; start_angle: number; sweep_angle: number; thickness: number; color: Color };
+
 export interface CrosshairConfig {
   enabled: boolean;
   size: number;
   thickness: number;
   gap: number;
-  color: number;
-  outline_color: number;
+  color: Color;
+  outline_color: Color;
   outline_thickness: number;
   show_dot: boolean;
   dot_size: number;
@@ -40,9 +65,9 @@ export interface CrosshairConfig {
   rotation: number;
   t_length: number;
   shadow_enabled: boolean;
-  shadow_color: number;
+  shadow_color: Color;
   shadow_offset: number;
-  lines: CrosshairLine[];
+  elements: CrosshairElement[];
 }
 
 interface CrosshairDesignerProps {
@@ -79,8 +104,20 @@ export function CrosshairDesigner({ config, onConfigChange }: CrosshairDesignerP
     onConfigChange({ ...config, ...updates });
   };
 
-  const colorToHex = (color: number): string => {
-    return "#" + color.toString(16).padStart(6, "0");
+  // Dragging a slider sends an update per step; the overlay only catches up
+  // when it's let go
+  const handleSliderGrab = (e: React.PointerEvent<HTMLDivElement>) => {
+    const target = e.target as HTMLInputElement;
+    if (target.tagName !== 'INPUT' || target.type !== 'range') return;
+
+    invoke("begin_config_transaction").catch(console.error);
+    const release = () => {
+      window.removeEventListener('pointerup', release);
+      window.removeEventListener('pointercancel', release);
+      invoke("commit_config_transaction").catch(console.error);
+    };
+    window.addEventListener('pointerup', release);
+    window.addEventListener('pointercancel', release);
   };
 
   const hexToColor = (hex: string): number => {
@@ -98,7 +135,8 @@ export function CrosshairDesigner({ config, onConfigChange }: CrosshairDesignerP
     if (!drawStart) {
       setDrawStart({ x, y });
     } else {
-      const newLine: CrosshairLine = {
+      const newLine: CrosshairElement = {
+        type: 'Line',
         start_x: drawStart.x,
         start_y: drawStart.y,
         end_x: x,
@@ -106,19 +144,18 @@ export function CrosshairDesigner({ config, onConfigChange }: CrosshairDesignerP
         thickness: config.thickness,
         color: config.color,
       };
-      const newLines = [...config.lines, newLine];
-      updateConfig({ lines: newLines });
+      updateConfig({ elements: [...config.elements, newLine] });
       setDrawStart(null);
     }
   };
 
   const clearCustomLines = () => {
-    updateConfig({ lines: [] });
+    updateConfig({ elements: [] });
     setDrawStart(null);
   };
 
   return (
-    <div className="designer-container">
+    <div className="designer-container" onPointerDownCapture={handleSliderGrab}>
       <div className="designer-tabs">
         <button 
           className={`tab ${activeTab === 'style' ? 'active' : ''}`}
@@ -1,5 +1,5 @@
 import { useEffect, useRef } from "react";
-import { CrosshairConfig } from "./CrosshairDesigner";
+import { CrosshairConfig, colorToHex } from "./CrosshairDesigner";
 
 interface CrosshairPreviewProps {
   config: CrosshairConfig;
@@ -48,9 +48,9 @@ export function CrosshairPreview({ config, size = 400, showBackground = true }:
     const centerY = size / 2 + config.position_y;
 
     // Convert colors
-    const mainColor = `#${config.color.toString(16).padStart(6, '0')}`;
-    const outlineColor = `#${config.outline_color.toString(16).padStart(6, '0')}`;
-    const shadowColor = `#${config.shadow_color.toString(16).padStart(6, '0')}`;
+    const mainColor = colorToHex(config.color);
+    const outlineColor = colorToHex(config.outline_color);
+    const shadowColor = colorToHex(config.shadow_color);
 
     ctx.save();
     ctx.globalAlpha = config.opacity;
@@ -199,11 +199,39 @@ export function CrosshairPreview({ config, size = 400, showBackground = true }:
   };
 
   const drawCustomCrosshair = (ctx: CanvasRenderingContext2D, x: number, y: number, config: CrosshairConfig) => {
-    for (const line of config.lines) {
+    for (const element of config.elements) {
       ctx.beginPath();
-      ctx.moveTo(x + line.start_x, y + line.start_y);
-      ctx.lineTo(x + line.end_x, y + line.end_y);
-      ctx.stroke();
+      switch (element.type) {
+        case 'Line':
+          ctx.moveTo(x + element.start_x, y + element.start_y);
+          ctx.lineTo(x + element.end_x, y + element.end_y);
+          break;
+        case 'Circle':
+          ctx.arc(x + element.x, y + element.y, element.radius, 0, Math.PI * 2);
+          break;
+        case 'Rect':
+          ctx.rect(x + element.x, y + element.y, element.width, element.height);
+          break;
+        case 'Polygon':
+          element.points.forEach((point, i) =>
+            i === 0 ? ctx.moveTo(x + point.x, y + point.y) : ctx.lineTo(x + point.x, y + point.y)
+          );
+          ctx.closePath();
+          break;
+        case 'Arc': {
+          // Angles are counterclockwise with y pointing up, like the overlay
+          const start = (-element.start_angle * Math.PI) / 180;
+          const end = (-(element.start_angle + element.sweep_angle) * Math.PI) / 180;
+          ctx.arc(x + element.x, y + element.y, element.radius, start, end, element.sweep_angle > 0);
+          break;
+        }
+      }
+      if ('fill' in element && element.fill != null) {
+        ctx.fill();
+      }
+      if (element.thickness > 0) {
+        ctx.stroke();
+      }
     }
   };
 
@@ -10,13 +10,17 @@ import {
   Edit,
   Check
 } from "lucide-react";
-import { CrosshairConfig } from "./CrosshairDesigner";
+import { CrosshairConfig, colorToHex } from "./CrosshairDesigner";
 
 interface CrosshairPreset {
   id: string;
   name: string;
   config: CrosshairConfig;
   created_at: string;
+  author?: string | null;
+  description?: string | null;
+  game?: string | null;
+  source_url?: string | null;
 }
 
 interface FavoritesPanelProps {
@@ -126,14 +130,11 @@ export function FavoritesPanel({ currentConfig, onLoadPreset }: FavoritesPanelPr
     reader.onload = async (e) => {
       try {
         const imported = JSON.parse(e.target?.result as string) as CrosshairPreset[];
-        for (const preset of imported) {
-          preset.id = Date.now().toString() + Math.random();
-          await invoke("save_preset", { preset });
-        }
+        await invoke("import_presets", { presets: imported });
         await loadPresets();
       } catch (error) {
         console.error("Failed to import presets:", error);
-        alert("Failed to import presets. Please check the file format.");
+        alert(`Failed to import presets: ${error}`);
       }
     };
     reader.readAsText(file);
@@ -234,7 +235,17 @@ export function FavoritesPanel({ currentConfig, onLoadPreset }: FavoritesPanelPr
                 ) : (
                   <h4>{preset.name}</h4>
                 )}
-                <small>{formatDate(preset.created_at)}</small>
+                <small>
+                  {[preset.author && `by ${preset.author}`, preset.game, formatDate(preset.created_at)]
+                    .filter(Boolean)
+                    .join(" · ")}
+                </small>
+                {preset.description && <p className="preset-description">{preset.description}</p>}
+                {preset.source_url && (
+                  <small className="preset-source" title={preset.source_url}>
+                    {preset.source_url}
+                  </small>
+                )}
               </div>
               <div className="preset-actions">
                 <button 
@@ -278,8 +289,8 @@ export function FavoritesPanel({ currentConfig, onLoadPreset }: FavoritesPanelPr
 }
 
 function CrosshairMiniPreview({ config }: { config: CrosshairConfig }) {
-  const colorHex = "#" + config.color.toString(16).padStart(6, "0");
-  const outlineColorHex = "#" + config.outline_color.toString(16).padStart(6, "0");
+  const colorHex = colorToHex(config.color);
+  const outlineColorHex = colorToHex(config.outline_color);
   
   return (
     <svg width="50" height="50" viewBox="0 0 50 50" className="mini-preview">
@@ -0,0 +1,41 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { CrosshairConfig } from "./CrosshairDesigner";
+import { CrosshairPreview } from "./CrosshairPreview";
+import "./components.css";
+
+// Fallback overlay for platforms without a native one: the backend opens this
+// view in a transparent, click-through window and pushes every config change
+export function OverlayView() {
+  const [config, setConfig] = useState<CrosshairConfig | null>(null);
+  const [size, setSize] = useState(() => Math.max(window.innerWidth, window.innerHeight));
+
+  useEffect(() => {
+    // The settings UI styles the page; the overlay has to stay see-through
+    document.documentElement.style.background = "transparent";
+    document.body.style.background = "transparent";
+
+    invoke<CrosshairConfig>("get_crosshair_config")
+      .then(setConfig)
+      .catch(error => console.error("Failed to load config:", error));
+
+    const unlisten = listen<CrosshairConfig>("overlay-config", (event) => {
+      setConfig(event.payload);
+    });
+
+    const onResize = () => setSize(Math.max(window.innerWidth, window.innerHeight));
+    window.addEventListener("resize", onResize);
+
+    return () => {
+      unlisten.then(fn => fn());
+      window.removeEventListener("resize", onResize);
+    };
+  }, []);
+
+  return (
+    <div className="overlay-view">
+      {config?.enabled && <CrosshairPreview config={config} size={size} showBackground={false} />}
+    </div>
+  );
+}
@@ -1,9 +1,13 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "./App";
+import { OverlayView } from "./components/OverlayView";
+
+// The fallback overlay window loads the same page with ?overlay
+const isOverlay = new URLSearchParams(window.location.search).has("overlay");
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    {isOverlay ? <OverlayView /> : <App />}
   </React.StrictMode>,
 );